@@ -0,0 +1,39 @@
+//Run with `cargo bench`.  Prints the same tables as `monopoly-space-calc bench`, and with BENCH_BASELINE set to a file saved by
+//`bench --save` fails if either engine has got more than 10% slower at any player count
+use std::{env, fs, process};
+use std::io::BufReader;
+
+use monopoly_space_calc::game_simulation::benchmark::{self, EngineEnum};
+
+
+const GAME_COUNT: u32 = 500;
+const TURN_COUNT: u32 = 1000;
+
+const PHASE_ITERATIONS: u32 = 5_000_000;
+
+
+
+fn main() {
+    let results = benchmark::run_benchmarks(TURN_COUNT, GAME_COUNT);
+    benchmark::print_benchmark_results(&results);
+
+    let phase_rates = benchmark::measure_phase_rates(4, 50_000);
+
+    for engine in EngineEnum::ALL {
+        println!();
+        benchmark::print_phase_times(engine, &benchmark::time_phases(engine, PHASE_ITERATIONS), PHASE_ITERATIONS, &phase_rates);
+    }
+
+    if let Ok(baseline_path) = env::var("BENCH_BASELINE") {
+        let baseline = fs::File::open(&baseline_path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| benchmark::read_baseline(BufReader::new(file)))
+            .unwrap_or_else(|e| panic!("Error reading baseline {}: {}", baseline_path, e));
+
+        println!();
+
+        if !benchmark::compare_to_baseline(&results, &baseline) {
+            process::exit(1);
+        }
+    }
+}
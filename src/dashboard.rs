@@ -0,0 +1,629 @@
+use std::{fs, io, thread};
+use std::io::Write;
+use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crossterm::{cursor, event, execute, queue, style, terminal};
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Color;
+
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::dice_model;
+use monopoly_space_calc::game_simulation::game_board::SpaceGroupEnum;
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+
+
+//Rule presets the r key cycles through after the rules the dashboard was started with, each on top of the edition's defaults
+const RULE_PRESETS: [&str; 5] = ["default", "official", "official,free_parking_jackpot", "official,double_salary_on_go", "official,max_doubles=0"];
+
+//Turns in the first batch after a (re)start, used to time the engine and size every batch after it
+const CALIBRATION_TURNS: u32 = 1000;
+const MIN_BATCH_TURNS: u32 = 1000;
+const MAX_BATCH_TURNS: u32 = 10_000_000;
+const TARGET_BATCH_TIME: Duration = Duration::from_millis(200);
+
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+//Confidence intervals are 95% batch means intervals, the same as --precision's default
+const Z_SCORE: f64 = 1.96;
+
+const HEATMAP_CELL_WIDTH: u16 = 5;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+
+
+//Settings picked on the command line for the dashboard
+pub struct DashboardOptions {
+    pub edition: GameEditionEnum,
+    pub dice_spec: String,
+    pub rules: RuleSet,
+    pub rules_spec: String,
+    pub player_count: u32,
+    pub seed: u64
+}
+
+
+
+//What the worker thread plays with.  Changing it bumps the generation so batches from the old settings get thrown away
+#[derive(Clone)]
+struct RunSettings {
+    generation: u64,
+    player_count: u32,
+    rules: RuleSet
+}
+
+
+//Shared between the UI and the worker thread
+struct WorkerControl {
+    settings: Mutex<RunSettings>,
+    paused: AtomicBool,
+    quit: AtomicBool,
+    //Cuts the batch under way short, once it's out of date or the dashboard is paused or closing
+    stop_batch: AtomicBool
+}
+
+impl WorkerControl {
+    fn stop_batch(&self) {
+        self.stop_batch.store(true, Ordering::Relaxed);
+    }
+}
+
+
+//One finished batch, landed counts in board order
+struct BatchResult {
+    generation: u64,
+    landed_counts: Vec<u64>,
+    turn_count: u32,
+    elapsed: Duration,
+    calibration: bool,
+    fast_engine: bool
+}
+
+
+
+//Everything the current settings have produced so far
+struct DashboardStats {
+    landed_counts: Vec<u64>,
+    //Batch means, see precision.rs.  The calibration batch is a different length so it only goes into the landed counts
+    share_sums: Vec<f64>,
+    share_square_sums: Vec<f64>,
+    batch_count: u32,
+    turns_played: u64,
+    running_time: Duration,
+    //Largest half width after each batch, and turns per second for each batch, oldest first
+    convergence: Vec<f64>,
+    throughput: Vec<f64>,
+    fast_engine: bool
+}
+
+impl DashboardStats {
+    fn new(space_count: usize) -> Self {
+        DashboardStats {
+            landed_counts: vec![0; space_count],
+            share_sums: vec![0.0; space_count],
+            share_square_sums: vec![0.0; space_count],
+            batch_count: 0,
+            turns_played: 0,
+            running_time: Duration::ZERO,
+            convergence: Vec::new(),
+            throughput: Vec::new(),
+            fast_engine: true
+        }
+    }
+
+
+    fn record_batch(&mut self, batch: &BatchResult) {
+        let batch_total: u64 = batch.landed_counts.iter().sum();
+
+        for (landed_count, batch_count) in self.landed_counts.iter_mut().zip(batch.landed_counts.iter()) {
+            *landed_count += batch_count;
+        }
+
+        if !batch.calibration && batch_total > 0 {
+            for (space, batch_count) in batch.landed_counts.iter().enumerate() {
+                let share = *batch_count as f64 / batch_total as f64;
+
+                self.share_sums[space] += share;
+                self.share_square_sums[space] += share * share;
+            }
+
+            self.batch_count += 1;
+        }
+
+        self.turns_played += batch.turn_count as u64;
+        self.running_time += batch.elapsed;
+        self.fast_engine = batch.fast_engine;
+        self.throughput.push(batch.turn_count as f64 / batch.elapsed.as_secs_f64().max(f64::EPSILON));
+
+        if let Some(largest_half_width) = self.get_half_widths().into_iter().flatten().reduce(f64::max) {
+            self.convergence.push(largest_half_width);
+        }
+    }
+
+
+    fn get_total_landed(&self) -> u64 {
+        self.landed_counts.iter().sum()
+    }
+
+    fn get_probabilities(&self) -> Vec<f64> {
+        let total_landed = self.get_total_landed().max(1) as f64;
+
+        self.landed_counts.iter().map(|landed_count| *landed_count as f64 / total_landed).collect()
+    }
+
+    //None for every space until there are two batches to compare
+    fn get_half_widths(&self) -> Vec<Option<f64>> {
+        if self.batch_count < 2 {
+            return vec![None; self.share_sums.len()];
+        }
+
+        let batches = self.batch_count as f64;
+
+        self.share_sums.iter()
+            .zip(self.share_square_sums.iter())
+            .map(|(sum, square_sum)| {
+                let variance = ((square_sum - sum * sum / batches) / (batches - 1.0)).max(0.0);
+                Some(Z_SCORE * (variance / batches).sqrt())
+            })
+            .collect()
+    }
+
+    fn get_turns_per_second(&self) -> f64 {
+        self.turns_played as f64 / self.running_time.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+
+
+//Names and groups of the edition's spaces, for labelling
+struct SpaceLabel {
+    name: String,
+    group: SpaceGroupEnum
+}
+
+
+
+//Puts the terminal back however the dashboard exits, panics included
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), style::ResetColor, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+
+
+//Full screen view of a simulation playing batch after batch in the background, until q or Ctrl-C
+pub fn run_dashboard(options: DashboardOptions) -> Result<(), String> {
+    //Fail on a bad dice spec here rather than on the worker thread
+    dice_model::from_spec(&options.dice_spec)?;
+
+    let labels: Vec<SpaceLabel> = options.edition.create_board().iter()
+        .map(|space| SpaceLabel { name: (**space.get_space_name()).to_string(), group: space.get_space_group() })
+        .collect();
+
+    let mut rule_presets = vec![(describe_rules_spec(&options.rules_spec), options.rules.clone())];
+    for preset in RULE_PRESETS {
+        rule_presets.push((preset.to_string(), options.edition.get_default_rules().with_spec(preset)?));
+    }
+
+    let control = Arc::new(WorkerControl {
+        settings: Mutex::new(RunSettings { generation: 0, player_count: options.player_count, rules: options.rules.clone() }),
+        paused: AtomicBool::new(false),
+        quit: AtomicBool::new(false),
+        stop_batch: AtomicBool::new(false)
+    });
+
+    let (sender, receiver) = mpsc::channel();
+    let worker = {
+        let control = Arc::clone(&control);
+        let (edition, dice_spec, seed) = (options.edition, options.dice_spec.clone(), options.seed);
+        thread::spawn(move || play_batches(edition, &dice_spec, seed, &control, sender))
+    };
+
+    let mut dashboard = Dashboard {
+        stats: DashboardStats::new(labels.len()),
+        options,
+        labels,
+        rule_presets,
+        rule_preset: 0,
+        status_message: String::new()
+    };
+
+    let outcome = {
+        let _guard = TerminalGuard::enter().map_err(|e| format!("Couldn't start the dashboard: {}", e))?;
+        dashboard.run(&control, &receiver).map_err(|e| format!("Dashboard error: {}", e))
+    };
+
+    control.quit.store(true, Ordering::Relaxed);
+    control.stop_batch();
+    let _ = worker.join();
+
+    outcome
+}
+
+
+fn describe_rules_spec(rules_spec: &str) -> String {
+    if rules_spec.trim().is_empty() {
+        String::from("edition defaults")
+    } else {
+        rules_spec.to_string()
+    }
+}
+
+
+
+//Plays consecutive batches with whatever the settings are at the time, handing each one to the UI until told to quit
+fn play_batches(edition: GameEditionEnum, dice_spec: &str, seed: u64, control: &WorkerControl, sender: mpsc::Sender<BatchResult>) {
+    let mut batch_turns = CALIBRATION_TURNS;
+    let mut current_generation = None;
+    let mut batch_index: u64 = 0;
+
+    while !control.quit.load(Ordering::Relaxed) {
+        if control.paused.load(Ordering::Relaxed) {
+            thread::sleep(PAUSED_POLL_INTERVAL);
+            continue;
+        }
+
+        //Cleared before reading the settings, so a change made while the batch runs always stops it
+        control.stop_batch.store(false, Ordering::Relaxed);
+        let settings = control.settings.lock().unwrap().clone();
+
+        let calibration = current_generation != Some(settings.generation);
+        if calibration {
+            batch_turns = CALIBRATION_TURNS;
+            current_generation = Some(settings.generation);
+        }
+
+        let simulation = || Simulation::new()
+            .edition(edition)
+            .rules(settings.rules.clone())
+            .dice_model(dice_model::from_spec(dice_spec).unwrap())
+            .player_count(settings.player_count)
+            .turn_count(batch_turns)
+            .seed(seed.wrapping_add(batch_index))
+            .stop_when(&control.stop_batch);
+
+        let started = Instant::now();
+
+        //Settings the fast engine can't play (the speed die, dice other than 2d6) fall back to the standard engine
+        let (results, fast_engine): (SimulationResults, bool) = match simulation().run_fast() {
+            Ok(results) => (results, true),
+            Err(_) => (simulation().run(), false)
+        };
+
+        let elapsed = started.elapsed();
+        batch_index += 1;
+
+        //A batch cut short is from settings that are out of date, or would be a different length to the rest
+        if results.interrupted {
+            current_generation = None;
+            continue;
+        }
+
+        if calibration {
+            let scale = TARGET_BATCH_TIME.as_secs_f64() / elapsed.as_secs_f64().max(f64::EPSILON);
+            batch_turns = (batch_turns as f64 * scale).clamp(MIN_BATCH_TURNS as f64, MAX_BATCH_TURNS as f64) as u32;
+        }
+
+        let batch = BatchResult {
+            generation: settings.generation,
+            landed_counts: results.spaces.iter().map(|space| space.landed_count).collect(),
+            turn_count: results.turn_count,
+            elapsed,
+            calibration,
+            fast_engine
+        };
+
+        if sender.send(batch).is_err() {
+            break;
+        }
+    }
+}
+
+
+
+struct Dashboard {
+    options: DashboardOptions,
+    labels: Vec<SpaceLabel>,
+    //Label and rules for each preset, the rules the dashboard was started with first
+    rule_presets: Vec<(String, RuleSet)>,
+    rule_preset: usize,
+    stats: DashboardStats,
+    status_message: String
+}
+
+impl Dashboard {
+    fn run(&mut self, control: &WorkerControl, receiver: &mpsc::Receiver<BatchResult>) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        let mut generation = 0;
+        let mut last_redraw = None::<Instant>;
+
+        loop {
+            for batch in receiver.try_iter() {
+                if batch.generation == generation {
+                    self.stats.record_batch(&batch);
+                }
+            }
+
+            if last_redraw.is_none_or(|last_redraw| last_redraw.elapsed() >= REDRAW_INTERVAL) {
+                self.draw(&mut stdout, control.paused.load(Ordering::Relaxed))?;
+                last_redraw = Some(Instant::now());
+            }
+
+            if !event::poll(REDRAW_INTERVAL / 5)? {
+                continue;
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                Event::Resize(_, _) => {
+                    last_redraw = None;
+                    continue;
+                },
+                _ => continue
+            };
+
+            let mut new_settings = None;
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                KeyCode::Char('p') | KeyCode::Char(' ') => {
+                    let paused = !control.paused.load(Ordering::Relaxed);
+                    control.paused.store(paused, Ordering::Relaxed);
+
+                    if paused {
+                        control.stop_batch();
+                    }
+                },
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Right if self.options.player_count < 8 => {
+                    self.options.player_count += 1;
+                    new_settings = Some(format!("Now playing with {} players", self.options.player_count));
+                },
+                KeyCode::Char('-') | KeyCode::Left if self.options.player_count > 2 => {
+                    self.options.player_count -= 1;
+                    new_settings = Some(format!("Now playing with {} players", self.options.player_count));
+                },
+                KeyCode::Char('r') => {
+                    self.rule_preset = (self.rule_preset + 1) % self.rule_presets.len();
+                    new_settings = Some(format!("Now playing with rules: {}", self.rule_presets[self.rule_preset].0));
+                },
+                KeyCode::Char('e') => {
+                    self.status_message = match self.export_snapshot() {
+                        Ok(path) => format!("Snapshot written to {}", path),
+                        Err(e) => format!("Error writing snapshot: {}", e)
+                    };
+                },
+                _ => continue
+            }
+
+            //Start over from nothing, the numbers so far are for settings no longer in play
+            if let Some(message) = new_settings {
+                generation += 1;
+                self.stats = DashboardStats::new(self.labels.len());
+                self.status_message = message;
+
+                *control.settings.lock().unwrap() = RunSettings {
+                    generation,
+                    player_count: self.options.player_count,
+                    rules: self.rule_presets[self.rule_preset].1.clone()
+                };
+                control.stop_batch();
+            }
+
+            last_redraw = None;
+        }
+    }
+
+
+    //Writes the current figures to a CSV file in the working directory, returning its name
+    fn export_snapshot(&self) -> io::Result<String> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or(0);
+        let path = format!("dashboard-{}-{}p-{}.csv", self.options.edition.get_edition_name(), self.options.player_count, timestamp);
+
+        let probabilities = self.stats.get_probabilities();
+        let half_widths = self.stats.get_half_widths();
+
+        let mut file = io::BufWriter::new(fs::File::create(&path)?);
+        writeln!(file, "# edition={} players={} rules={} dice={} turns={}",
+            self.options.edition.get_edition_name(),
+            self.options.player_count,
+            self.rule_presets[self.rule_preset].1.to_spec(),
+            self.options.dice_spec,
+            self.stats.turns_played)?;
+        writeln!(file, "space_index,space,group,landed_count,probability,half_width")?;
+
+        for (space_index, label) in self.labels.iter().enumerate() {
+            writeln!(file, "{},\"{}\",{},{},{:.6},{}",
+                space_index,
+                label.name,
+                label.group.get_group_name(),
+                self.stats.landed_counts[space_index],
+                probabilities[space_index],
+                half_widths[space_index].map(|half_width| format!("{:.6}", half_width)).unwrap_or_default())?;
+        }
+
+        file.flush()?;
+        Ok(path)
+    }
+
+
+    fn draw(&self, stdout: &mut io::Stdout, paused: bool) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let probabilities = self.stats.get_probabilities();
+        let half_widths = self.stats.get_half_widths();
+
+        queue!(stdout, style::ResetColor, terminal::Clear(terminal::ClearType::All))?;
+
+        let status = if paused { "PAUSED" } else { "RUNNING" };
+        let engine = if self.stats.fast_engine { "fast engine" } else { "standard engine" };
+        draw_text(stdout, 0, 0, width, &format!("Monopoly space calc - {}, {} players, rules: {}   [{}, {}]",
+            self.options.edition.get_edition_name(),
+            self.options.player_count,
+            self.rule_presets[self.rule_preset].0,
+            status,
+            engine))?;
+
+        let largest_half_width = self.stats.convergence.last()
+            .map(|half_width| format!("+/- {:.4}%", half_width * 100.0))
+            .unwrap_or_else(|| String::from("-"));
+        draw_text(stdout, 0, 1, width, &format!("Turns {}   Throughput {} turns/s   Batches {}   Largest 95% interval {}",
+            format_count(self.stats.turns_played as f64),
+            format_count(self.stats.get_turns_per_second()),
+            self.stats.batch_count,
+            largest_half_width))?;
+
+        let heatmap_bottom = self.draw_heatmap(stdout, 0, 3, &probabilities)?;
+
+        let sparkline_width = width.min(heatmap_bottom.0.max(40));
+        let mut row = heatmap_bottom.1 + 1;
+        for (title, values) in [("Convergence (largest interval)", &self.stats.convergence), ("Throughput (turns/s)", &self.stats.throughput)] {
+            if row + 1 >= height.saturating_sub(2) {
+                break;
+            }
+
+            let latest = values.last().copied().unwrap_or(0.0);
+            let latest = if title.starts_with("Convergence") { format!("{:.4}%", latest * 100.0) } else { format_count(latest) };
+            draw_text(stdout, 0, row, width, &format!("{}  now {}", title, latest))?;
+            draw_text(stdout, 0, row + 1, width, &sparkline(values, sparkline_width as usize))?;
+            row += 3;
+        }
+
+        let table_column = heatmap_bottom.0 + 3;
+        if table_column + 30 < width {
+            self.draw_probability_table(stdout, table_column, 3, height.saturating_sub(6), &probabilities, &half_widths)?;
+        }
+
+        let footer = if self.status_message.is_empty() {
+            String::new()
+        } else {
+            format!("{}   ", self.status_message)
+        };
+        draw_text(stdout, 0, height.saturating_sub(1), width, &format!("{}p pause/resume  +/- players  r rules  e export  q quit", footer))?;
+
+        stdout.flush()
+    }
+
+
+    //Board laid out as a ring, Go in the bottom right corner like the real thing, each space shaded by how often it's landed on.
+    //Returns the bottom right corner it drew up to
+    fn draw_heatmap(&self, stdout: &mut io::Stdout, left: u16, top: u16, probabilities: &[f64]) -> io::Result<(u16, u16)> {
+        let side = probabilities.len().div_ceil(4).max(1);
+        let lowest = probabilities.iter().copied().fold(f64::INFINITY, f64::min);
+        let highest = probabilities.iter().copied().fold(0.0, f64::max);
+
+        for (space_index, probability) in probabilities.iter().enumerate() {
+            let (row, column) = ring_position(space_index, side);
+            let shade = if highest > lowest { (probability - lowest) / (highest - lowest) } else { 0.0 };
+
+            queue!(stdout,
+                cursor::MoveTo(left + column as u16 * HEATMAP_CELL_WIDTH, top + row as u16),
+                style::SetBackgroundColor(heat_color(shade)),
+                style::SetForegroundColor(Color::Black),
+                style::Print(format!("{:>4.2}", probability * 100.0).chars().take(HEATMAP_CELL_WIDTH as usize - 1).collect::<String>()),
+                style::ResetColor,
+                style::Print(" "))?;
+        }
+
+        Ok(((side as u16 + 1) * HEATMAP_CELL_WIDTH, top + side as u16 + 1))
+    }
+
+
+    fn draw_probability_table(&self, stdout: &mut io::Stdout, left: u16, top: u16, rows: u16, probabilities: &[f64], half_widths: &[Option<f64>]) -> io::Result<()> {
+        let mut sorted_spaces: Vec<usize> = (0..probabilities.len()).collect();
+        sorted_spaces.sort_by(|a, b| probabilities[*b].total_cmp(&probabilities[*a]));
+
+        queue!(stdout, cursor::MoveTo(left, top), style::Print(format!("{:>3}  {:<24} {:>7}  {:>9}", "#", "Space", "Landed", "95% +/-")))?;
+
+        for (rank, space_index) in sorted_spaces.iter().take(rows.saturating_sub(1) as usize).enumerate() {
+            let label = &self.labels[*space_index];
+            let name: String = label.name.chars().take(24).collect();
+            let half_width = half_widths[*space_index].map(|half_width| format!("{:.4}%", half_width * 100.0)).unwrap_or_default();
+
+            queue!(stdout,
+                cursor::MoveTo(left, top + 1 + rank as u16),
+                style::Print(format!("{:>3}  ", rank + 1)),
+                style::Print(label.group.colorize(&format!("{:<24}", name))),
+                style::Print(format!(" {:>6.3}%  {:>9}", probabilities[*space_index] * 100.0, half_width)))?;
+        }
+
+        Ok(())
+    }
+}
+
+
+
+//Row and column of a space on a ring with side spaces to a side, counting anticlockwise from the bottom right corner
+fn ring_position(space_index: usize, side: usize) -> (usize, usize) {
+    let along = space_index % side;
+
+    match space_index / side {
+        0 => (side, side - along),
+        1 => (side - along, 0),
+        2 => (0, along),
+        _ => (along, side)
+    }
+}
+
+
+//Cold blue through yellow to hot red
+fn heat_color(shade: f64) -> Color {
+    let blend = |from: (f64, f64, f64), to: (f64, f64, f64), amount: f64| Color::Rgb {
+        r: (from.0 + (to.0 - from.0) * amount) as u8,
+        g: (from.1 + (to.1 - from.1) * amount) as u8,
+        b: (from.2 + (to.2 - from.2) * amount) as u8
+    };
+
+    let (blue, yellow, red) = ((70.0, 110.0, 200.0), (230.0, 210.0, 80.0), (220.0, 60.0, 50.0));
+
+    if shade < 0.5 {
+        blend(blue, yellow, shade * 2.0)
+    } else {
+        blend(yellow, red, (shade - 0.5) * 2.0)
+    }
+}
+
+
+//The most recent values, scaled between the smallest and largest of them
+fn sparkline(values: &[f64], width: usize) -> String {
+    let recent = &values[values.len().saturating_sub(width)..];
+    let lowest = recent.iter().copied().fold(f64::INFINITY, f64::min);
+    let highest = recent.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    recent.iter()
+        .map(|value| {
+            let level = if highest > lowest { (value - lowest) / (highest - lowest) } else { 0.5 };
+            SPARKLINE_LEVELS[(level * (SPARKLINE_LEVELS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+
+fn format_count(count: f64) -> String {
+    match count {
+        count if count >= 1e9 => format!("{:.2}B", count / 1e9),
+        count if count >= 1e6 => format!("{:.2}M", count / 1e6),
+        count if count >= 1e3 => format!("{:.1}K", count / 1e3),
+        count => format!("{:.0}", count)
+    }
+}
+
+
+fn draw_text(stdout: &mut io::Stdout, column: u16, row: u16, width: u16, text: &str) -> io::Result<()> {
+    let text: String = text.chars().take(width.saturating_sub(column) as usize).collect();
+    queue!(stdout, cursor::MoveTo(column, row), style::Print(text))
+}
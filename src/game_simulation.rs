@@ -1,123 +1,700 @@
-use rand::{thread_rng, prelude::ThreadRng, Rng};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
 
-mod player;
+use rand::{thread_rng, Rng};
+
+pub mod player;
 use player::Player;
 
-mod game_cards;
-use game_cards::{CardDecks};
+pub mod game_cards;
+use game_cards::{CardDecks, CardMoveEnum};
+
+pub mod game_board;
+use game_board::{BoardSpace, SpaceActionEnum};
+
+pub mod rent_returns;
+
+pub mod simulation_results;
+use simulation_results::{SimulationResults, SpaceResult, RuleStatistics};
+
+pub mod simulation_events;
+use simulation_events::{SimulationEventEnum, SimulationObserver, JailReasonEnum, notify_observers};
+
+mod chance_source;
+use chance_source::{ChanceSource, RngChanceSource};
+
+pub mod replay_log;
+use replay_log::{ReplayLog, ReplayChanceSource};
+
+pub mod rule_set;
+use rule_set::RuleSet;
+
+pub mod game_edition;
+use game_edition::GameEditionEnum;
+
+pub mod speed_die;
+use speed_die::SpeedDieEnum;
+
+pub mod dice_model;
+use dice_model::{DiceModel, StandardDice};
+
+pub mod markov_chain;
+
+mod fast_engine;
+use fast_engine::FastEngine;
+
+pub mod benchmark;
+
+pub mod precision;
+use precision::{PrecisionTarget, PrecisionTracker};
+
+mod checkpoint;
+use checkpoint::{Checkpoint, CheckpointSettings};
+
+pub mod saved_results;
+
+pub mod comparison;
+
+pub mod sweep;
+
+pub mod passage_times;
 
-mod game_board;
-use game_board::{BoardSpace, SpaceActionEnum, CardDeckEnum};
+pub mod progress;
+use progress::{ProgressReporter, RunMonitor, PROGRESS_INTERVAL_TURNS};
 
+pub mod serve;
 
 
-pub async fn run_simulation(player_count: u32, turn_count: u32) {
 
-    let mut players: Vec<_> = (1..=player_count).map(|i| Player::new(format!("Player {}", i).to_string())).collect();
+//Builder for a single simulation run.  Anything not set falls back to the classic US game: 4 players, 100 turns, a random seed,
+//two six sided dice, the standard board and the standard Chance/Community Chest decks.  Nothing is printed unless an observer is registered
+pub struct Simulation<'a>
+{
+    player_count: u32,
+    turn_count: u32,
+    starting_positions: Vec<u8>,
+    seed: Option<u64>,
+    board: Option<Vec<Box<dyn BoardSpace>>>,
+    card_decks: Option<CardDecks>,
+    dice_model: Option<Box<dyn DiceModel>>,
+    rules: RuleSet,
+    edition: GameEditionEnum,
+    observers: Vec<&'a mut dyn SimulationObserver>,
+    replay_log: Option<&'a ReplayLog>,
+    precision: Option<PrecisionTarget>,
+    progress_reporter: Option<&'a mut dyn ProgressReporter>,
+    stop_flag: Option<&'a AtomicBool>
+}
+
+impl<'a> Simulation<'a> {
+    pub fn new() -> Self {
+        Simulation { player_count: 4, turn_count: 100, starting_positions: Vec::new(), seed: None, board: None, card_decks: None, dice_model: None, rules: RuleSet::default(), edition: GameEditionEnum::Classic, observers: Vec::new(), replay_log: None, precision: None, progress_reporter: None, stop_flag: None }
+    }
+
+    pub fn player_count(mut self, player_count: u32) -> Self {
+        self.player_count = player_count;
+        self
+    }
+
+    pub fn turn_count(mut self, turn_count: u32) -> Self {
+        self.turn_count = turn_count;
+        self
+    }
+
+    //Where each player starts, in player order.  Anyone not given a space starts on Go
+    pub fn starting_positions(mut self, starting_positions: Vec<u8>) -> Self {
+        self.starting_positions = starting_positions;
+        self
+    }
+
+    //Runs with the same seed (and same settings) always produce the same results
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    //Any number of spaces up to game_board::MAX_BOARD_SPACES, with exactly one jail.  Running on a board that breaks either rule
+    //panics, or is an error on the fast engine
+    pub fn board(mut self, board: Vec<Box<dyn BoardSpace>>) -> Self {
+        self.board = Some(board);
+        self
+    }
+
+    pub fn card_decks(mut self, card_decks: CardDecks) -> Self {
+        self.card_decks = Some(card_decks);
+        self
+    }
+
+
+    pub fn dice_model(mut self, dice_model: Box<dyn DiceModel>) -> Self {
+        self.dice_model = Some(dice_model);
+        self
+    }
+
 
-    let mut rng = thread_rng();
-    let mut game_board = game_board::initialize_game_board();
+    pub fn rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+
+    //Plays on the edition's board with its decks and default rules.  Set any rule changes after picking the edition
+    pub fn edition(mut self, edition: GameEditionEnum) -> Self {
+        self.edition = edition;
+        self.rules = edition.get_default_rules();
+        self
+    }
+
+
+    //Takes every dice roll and card draw from the log instead of the RNG, along with its player and turn counts.
+    //See replay_log::verify_replay for checking the replay ended up where the log did
+    pub fn replaying(mut self, replay_log: &'a ReplayLog) -> Self {
+        self.player_count = replay_log.player_count;
+        self.turn_count = replay_log.turn_count;
+        self.seed = Some(replay_log.seed);
+        self.edition = replay_log.edition;
+        //Only so the results say which dice were used, the rolls themselves all come from the log
+        self.dice_model = dice_model::from_spec(&replay_log.dice_model).ok();
+        self.rules = replay_log.rules.clone();
+        self.replay_log = Some(replay_log);
+        self.precision = None;
+        self
+    }
+
+
+    //Plays on in batches until every space's landing probability is known as precisely as the target asks, instead of for a
+    //set number of turns.  The results say how many turns that took and how precise each space ended up
+    pub fn until_precision(mut self, target: PrecisionTarget) -> Self {
+        self.precision = Some(target);
+        self
+    }
+
+
+    //Kept up to date with turns played every few thousand turns, on either engine
+    pub fn progress(mut self, progress_reporter: &'a mut dyn ProgressReporter) -> Self {
+        self.progress_reporter = Some(progress_reporter);
+        self
+    }
+
+
+    //Setting the flag (from a Ctrl-C handler, say) stops the run cleanly within a few thousand turns.  The results cover the turns
+    //played so far and are marked as interrupted
+    pub fn stop_when(mut self, stop_flag: &'a AtomicBool) -> Self {
+        self.stop_flag = Some(stop_flag);
+        self
+    }
+
+
+    //Observers are handed every event as it happens, in the order they were added
+    pub fn add_observer(mut self, observer: &'a mut dyn SimulationObserver) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+
+    pub fn run(mut self) -> SimulationResults {
+        //Pick a seed up front when one wasn't given so the results can always say how to reproduce them
+        let seed = self.seed.unwrap_or_else(|| thread_rng().gen());
+
+        let mut players: Vec<_> = (1..=self.player_count).map(|i| Player::new(format!("Player {}", i))).collect();
+
+        for (player, starting_space) in players.iter_mut().zip(self.starting_positions.iter()) {
+            player.set_current_space(*starting_space);
+        }
+
+        let dice_model = self.dice_model.unwrap_or_else(|| Box::new(StandardDice));
+        let dice_model_spec = dice_model.get_model_spec();
+
+        let mut chance_source: Box<dyn ChanceSource + 'a> = match self.replay_log {
+            Some(replay_log) => Box::new(ReplayChanceSource::new(replay_log)),
+            None => Box::new(RngChanceSource::new(seed, dice_model))
+        };
+
+        let edition = self.edition;
+        let board = self.board.unwrap_or_else(|| edition.create_board());
+
+        let jail_space = game_board::get_jail_space(&board).unwrap_or_else(|e| panic!("{}", e));
+
+        let mut card_decks = self.card_decks.unwrap_or_else(|| edition.create_card_decks());
+        card_decks.for_board(&board).unwrap_or_else(|e| panic!("{}", e));
+
+        let mut game_state = GameState {
+            owners: vec![None; board.len()],
+            board,
+            jail_space,
+            card_decks,
+            rules: self.rules,
+            rule_statistics: RuleStatistics::default(),
+            free_parking_pot: 0,
+            bus_tickets_left: speed_die::BUS_TICKET_COUNT
+        };
+
+        let mut precision_tracker = self.precision.map(|target| PrecisionTracker::new(target, game_state.board.len()));
+        let max_turns = self.precision.map_or(self.turn_count, |target| target.get_max_turns());
+        let mut turns_played = 0;
+        let mut run_monitor = RunMonitor::new(self.progress_reporter, self.stop_flag, 0);
+        let mut interrupted = false;
+
+        while turns_played < max_turns {
+            turns_played += 1;
+            notify_observers(&mut self.observers, SimulationEventEnum::TurnStarted { turn_number: turns_played });
+
+            for (player_index, player) in players.iter_mut().enumerate() {
+                take_player_turn(player, player_index, &mut game_state, chance_source.as_mut(), &mut self.observers, 0);
+            }
+
+            if let Some(tracker) = precision_tracker.as_mut() {
+                if tracker.is_batch_end(turns_played) && tracker.record_batch(game_state.board.iter().map(|space| space.get_landed_count() as u64)) {
+                    break;
+                }
+            }
+
+            if run_monitor.is_check_due(turns_played) && run_monitor.check(turns_played, max_turns) {
+                interrupted = turns_played < max_turns;
+                break;
+            }
+        }
+
+        run_monitor.report(turns_played, if interrupted { max_turns } else { turns_played });
+
+
+        let spaces = game_state.board.iter()
+            .enumerate()
+            .map(|(idx, space)| SpaceResult::from_space(idx as u8, space.as_ref()))
+            .collect();
+
+        SimulationResults {
+            player_count: self.player_count,
+            turn_count: turns_played,
+            seed,
+            edition,
+            dice_model: dice_model_spec,
+            rules: game_state.rules,
+            spaces,
+            final_positions: players.iter().map(|player| player.get_current_space()).collect(),
+            final_in_jail: players.iter().map(|player| player.is_in_jail()).collect(),
+            rule_statistics: game_state.rule_statistics,
+            precision: precision_tracker.map(PrecisionTracker::finish),
+            interrupted
+        }
+    }
+
+
+    //Same game on the fast engine: tens of millions of turns a second, for when only the numbers at the end matter.  Needs the
+    //standard two dice, no observers or replay, and no rules that depend on who owns what (the speed die, bus tickets)
+    pub fn run_fast(self) -> Result<SimulationResults, String> {
+        self.run_fast_with_checkpoints(None)
+    }
+
+
+    //run_fast for very long runs, saving everything to checkpoint_path every checkpoint_turns turns.  If the checkpoint is already
+    //there the run carries on from it instead of starting over, and ends with exactly the same results as a run that was never
+    //interrupted.  A checkpoint saved with different settings is an error rather than something to overwrite.  Without a seed
+    //set, a resumed run takes the checkpoint's
+    pub fn run_fast_checkpointed(self, checkpoint_path: &Path, checkpoint_turns: u32) -> Result<SimulationResults, String> {
+        self.run_fast_with_checkpoints(Some((checkpoint_path, checkpoint_turns.max(1))))
+    }
+
+
+    fn run_fast_with_checkpoints(self, checkpointing: Option<(&Path, u32)>) -> Result<SimulationResults, String> {
+        if !self.observers.is_empty() || self.replay_log.is_some() {
+            return Err(String::from("The fast engine doesn't publish events or play back replays"));
+        }
 
-    let mut card_decks = CardDecks::new();
+        if let Some(dice_model) = self.dice_model.as_ref() {
+            if dice_model.get_model_spec() != StandardDice.get_model_spec() {
+                return Err(format!("The fast engine only rolls two six sided dice, not {}", dice_model.get_model_spec()));
+            }
+        }
 
-    for _turn in 1..=turn_count {
-        for player in players.iter_mut() {
-            take_player_turn(player, &mut rng, &mut game_board, &mut card_decks, 0);
+        let saved_checkpoint = match checkpointing {
+            Some((checkpoint_path, _)) if checkpoint_path.exists() => Some(Checkpoint::load(checkpoint_path)?),
+            _ => None
+        };
+
+        let settings = CheckpointSettings {
+            player_count: self.player_count,
+            turn_count: self.precision.map_or(self.turn_count, |target| target.get_max_turns()),
+            seed: self.seed
+                .or(saved_checkpoint.as_ref().map(|checkpoint| checkpoint.settings.seed))
+                .unwrap_or_else(|| thread_rng().gen()),
+            edition: self.edition,
+            rules: self.rules,
+            starting_positions: self.starting_positions,
+            precision: self.precision
+        };
+
+        let edition = settings.edition;
+        let board = self.board.unwrap_or_else(|| edition.create_board());
+        let mut card_decks = self.card_decks.unwrap_or_else(|| edition.create_card_decks());
+
+        let mut fast_run = FastEngine::new(&board, &mut card_decks, &settings.rules)?
+            .start(settings.player_count, &settings.starting_positions, settings.seed);
+        let mut precision_tracker = settings.precision.map(|target| PrecisionTracker::new(target, board.len()));
+
+        if let Some(checkpoint) = saved_checkpoint {
+            if let Some(difference) = checkpoint.settings.find_difference(&settings) {
+                return Err(format!("The checkpoint was saved from a different simulation, it has {}", difference));
+            }
+
+            fast_run.restore_state(checkpoint.run_state)?;
+            precision_tracker = checkpoint.precision_state;
         }
+
+        let mut run_monitor = RunMonitor::new(self.progress_reporter, self.stop_flag, fast_run.get_turns_played());
+
+        //Played in stretches, stopping to report progress and check for a stop request, and to save a checkpoint when one is due
+        let interrupted = loop {
+            //A checkpoint saved once the target was reached has nothing left to play
+            let target_reached = precision_tracker.as_ref().is_some_and(|tracker| tracker.is_target_reached());
+
+            let next_stop = |interval: u32| (fast_run.get_turns_played() / interval + 1).saturating_mul(interval);
+            let stop_at = match checkpointing {
+                Some((_, checkpoint_turns)) => next_stop(checkpoint_turns).min(next_stop(PROGRESS_INTERVAL_TURNS)),
+                None => next_stop(PROGRESS_INTERVAL_TURNS)
+            }.min(settings.turn_count);
+
+            let finished = target_reached || fast_run.play(stop_at, precision_tracker.as_mut()) || fast_run.get_turns_played() >= settings.turn_count;
+            let interrupted = !finished && run_monitor.check(fast_run.get_turns_played(), settings.turn_count);
+
+            if let Some((checkpoint_path, checkpoint_turns)) = checkpointing {
+                if finished || interrupted || fast_run.get_turns_played().is_multiple_of(checkpoint_turns) {
+                    Checkpoint { settings: settings.clone(), run_state: fast_run.save_state(), precision_state: precision_tracker.clone() }.save(checkpoint_path)?;
+                }
+            }
+
+            if finished || interrupted {
+                break interrupted;
+            }
+        };
+
+        let turns_played = fast_run.get_turns_played();
+        run_monitor.report(turns_played, if interrupted { settings.turn_count } else { turns_played });
+
+        let fast_results = fast_run.finish();
+
+        let spaces = board.iter()
+            .enumerate()
+            .map(|(idx, space)| SpaceResult { landed_count: fast_results.landed_counts[idx], ..SpaceResult::from_space(idx as u8, space.as_ref()) })
+            .collect();
+
+        Ok(SimulationResults {
+            player_count: settings.player_count,
+            turn_count: fast_results.turns_played,
+            seed: settings.seed,
+            edition,
+            dice_model: StandardDice.get_model_spec(),
+            rules: settings.rules,
+            spaces,
+            final_positions: fast_results.final_positions,
+            final_in_jail: fast_results.final_in_jail,
+            rule_statistics: fast_results.rule_statistics,
+            precision: precision_tracker.map(PrecisionTracker::finish),
+            interrupted
+        })
+    }
+}
+
+impl Default for Simulation<'_> {
+    fn default() -> Self {
+        Self::new()
     }
+}
+
+
+
+
 
+//Everything a turn can change besides the player taking it
+struct GameState {
+    board: Vec<Box<dyn BoardSpace>>,
+    jail_space: u8,
+    //Index of the player owning each space, if anyone does
+    owners: Vec<Option<usize>>,
+    card_decks: CardDecks,
+    rules: RuleSet,
+    rule_statistics: RuleStatistics,
+    //Only collects anything under the Free Parking jackpot house rule
+    free_parking_pot: u64,
+    //Bus tickets still in the bank.  Every player draws from the same pile, and used tickets go back into it
+    bus_tickets_left: u8
+}
 
-    println!("-----------------------RESULTS-----------------------");
-    
+impl GameState {
+    //Taxes, fees and fines all go to the bank, or into the pot when playing with the Free Parking jackpot
+    fn pay_bank(&mut self, amount: u32) {
+        if self.rules.free_parking_jackpot {
+            self.free_parking_pot += amount as u64;
+        }
+    }
 
+    fn is_purchasable(&self, space: u8) -> bool {
+        self.board[space as usize].is_purchasable()
+    }
 
-    //Sort the results by most landed to least landed
-    game_board.sort_by(|a,b| {
-        b.get_landed_count().cmp(&a.get_landed_count())
-    });
+    fn get_owner(&self, space: u8) -> Option<usize> {
+        self.owners[space as usize]
+    }
 
-    //Print game results (space landed counts)
-    for space in game_board {
-        println!("{}|{}", space.get_landed_count(), space.get_space_name())
+    //Moves forward from a space, wrapping round past Go.  Loaded dice can have faces big enough to go all the way round, more
+    //than once on a small board
+    fn advance(&self, from_space: u8, distance: u8) -> u8 {
+        ((from_space as usize + distance as usize) % self.board.len()) as u8
     }
 
-    println!("-----------------------------------------------------");
+    //Whether moving forward from a space reaches or goes past Go, which the landed space alone can't say once a roll can go all
+    //the way round
+    fn passes_go(&self, from_space: u8, distance: u8) -> bool {
+        from_space as usize + distance as usize >= self.board.len()
+    }
 }
 
 
 
+fn take_player_turn(player: &mut Player, player_index: usize, state: &mut GameState, chance_source: &mut dyn ChanceSource, observers: &mut [&mut dyn SimulationObserver], mut doubles_count: u8) {
+    //Paying the fine straight away means a completely normal roll, doubles and all
+    if player.is_in_jail() && state.rules.max_jail_turns == 0 {
+        leave_jail(player, state, observers, true);
+    }
+
+    //A bus ticket is used instead of rolling, but only when it can reach a property nobody owns yet
+    if doubles_count == 0 && !player.is_in_jail() && player.get_bus_tickets() > 0 {
+        if let Some(destination) = speed_die::choose_bus_ticket_destination(state, player.get_current_space()) {
+            player.use_bus_ticket();
+            state.bus_tickets_left += 1;
+            chance_source.use_bus_ticket();
 
+            notify_observers(observers, SimulationEventEnum::BusTicketUsed { player_name: player.get_player_name(), to_space: destination });
 
-fn take_player_turn(player: &mut Player, rng: &mut ThreadRng, board: &mut Vec<Box<dyn BoardSpace>>, card_decks: &mut CardDecks, mut doubles_count: u8) {
-    let dice1 = rng.gen_range(1..=6);
-    let dice2 = rng.gen_range(1..=6);
+            let final_space = resolve_landing(player, player_index, state, chance_source, observers, destination);
+            finish_roll(player, observers, final_space);
 
-    let mut landed_space = player.get_current_space() + dice1 + dice2;
+            return;
+        }
+    }
+
+    let roll = chance_source.roll_dice();
+
+    let mut speed_die = None;
     let mut roll_again = false;
+    let mut sent_to_jail = false;
 
-    if dice1 == dice2 {
-        doubles_count = doubles_count + 1;
+    if player.is_in_jail() {
+        //Trying to roll doubles out of jail.  Getting out that way doesn't earn another roll
+        notify_observers(observers, SimulationEventEnum::DiceRolled { player_name: player.get_player_name(), roll, doubles_count: 0 });
 
-        if doubles_count == 1 {
-            println!("{} Rolled {} and {} totaling {}.  Doubles Once.", player.get_player_name(), dice1, dice2, dice1+dice2);
-            roll_again = true;
+        if roll.is_doubles() {
+            leave_jail(player, state, observers, false);
+        }
+        else {
+            player.add_jail_turn();
+
+            if player.get_jail_turns() >= state.rules.max_jail_turns {
+                //Out of chances, pay up and move with this roll
+                leave_jail(player, state, observers, true);
+            }
+            else {
+                state.rule_statistics.jail_turns_served += 1;
+
+                notify_observers(observers, SimulationEventEnum::StayedInJail { player_name: player.get_player_name(), jail_turns: player.get_jail_turns() });
+                notify_observers(observers, SimulationEventEnum::RollFinished { player_name: player.get_player_name(), final_space: player.get_current_space() });
+
+                return;
+            }
+        }
+    }
+    else {
+        if roll.is_doubles() {
+            doubles_count += 1;
+        }
+        else {
+            doubles_count = 0;
+        }
+
+        notify_observers(observers, SimulationEventEnum::DiceRolled { player_name: player.get_player_name(), roll, doubles_count });
+
+        //Only the white dice count towards doubles
+        if state.rules.speed_die && player.has_passed_go() {
+            let face = chance_source.roll_speed_die();
+
+            notify_observers(observers, SimulationEventEnum::SpeedDieRolled { player_name: player.get_player_name(), face });
+
+            speed_die = Some(face);
+        }
+
+        if doubles_count > 0 && doubles_count == state.rules.max_doubles_before_jail {
+            notify_observers(observers, SimulationEventEnum::SentToJail { player_name: player.get_player_name(), reason: JailReasonEnum::TooManyDoubles });
+            player.send_to_jail();
+
+            sent_to_jail = true;
         }
-        else if doubles_count == 2 {
-            println!("{} Rolled {} and {} totaling {}.  Doubles Twice.", player.get_player_name(), dice1, dice2, dice1+dice2);
+        else if doubles_count > 0 {
             roll_again = true;
         }
-        else if doubles_count == 3 {
-            println!("{} Rolled {} and {} totaling {}.  Doubles Thrice. Go directly to jail, do not pass go, do not collect $200", player.get_player_name(), dice1, dice2, dice1+dice2);
+    }
 
+    let current_space = player.get_current_space();
+
+    let distance =
+        match speed_die {
+            //Going to jail, the dice don't move the player anywhere
+            _ if sent_to_jail => None,
+            //Triples move the player anywhere they like, and end the turn
+            Some(SpeedDieEnum::Number(number)) if roll.is_doubles() && roll.get_dice()[0] == number => {
+                roll_again = false;
+                None
+            },
+            Some(SpeedDieEnum::Number(number)) => Some(roll.get_total() + number),
+            Some(SpeedDieEnum::Bus) => Some(speed_die::choose_bus_move(state, current_space, &roll)),
+            _ => Some(roll.get_total())
+        };
+
+    let (landed_space, passed_go) =
+        match distance {
             //Override the landed space to be the jail!
-            landed_space = 10;
-        }
+            _ if sent_to_jail => (state.jail_space, false),
+            Some(distance) => (state.advance(current_space, distance), state.passes_go(current_space, distance)),
+            None => {
+                let destination = speed_die::choose_triples_destination(state, current_space);
+                (destination, destination < current_space)
+            }
+        };
+
+    if !sent_to_jail && passed_go {
+        player.set_passed_go();
     }
-    else {
-        println!("{} Rolled {} and {} totaling {}", player.get_player_name(), dice1, dice2, dice1+dice2);
+
+    let mut final_space = resolve_landing(player, player_index, state, chance_source, observers, landed_space);
+
+    //Once the roll has been dealt with, Mr. Monopoly moves on to the next unowned property, or failing that, the next one
+    //somebody else owns
+    if speed_die == Some(SpeedDieEnum::MrMonopoly) && !player.is_in_jail() {
+        let next_space = speed_die::next_unowned_property(state, final_space)
+            .or_else(|| speed_die::next_opponent_property(state, final_space, player_index));
+
+        if let Some(next_space) = next_space {
+            notify_observers(observers, SimulationEventEnum::PlayerMoved { player_name: player.get_player_name(), from_space: final_space, to_space: next_space });
+
+            final_space = resolve_landing(player, player_index, state, chance_source, observers, next_space);
+        }
     }
 
-    //Handle "passing go"
-    if landed_space > 39 {
-        landed_space -= 40;
+    finish_roll(player, observers, final_space);
+
+
+    //Now that we're done processing the players turn, if they rolled doubles they need to take another turn.  Going to jail ends the turn
+    if roll_again && !player.is_in_jail() {
+        //RECURSION!
+        take_player_turn(player, player_index, state, chance_source, observers, doubles_count);
     }
-    
+}
+
 
-    //Process any special space behaviors (go to jail, draw cards)
+
+//Process any special space behaviors (go to jail, draw cards) starting from the space the player moved to.  Returns where they
+//ended up once everything has been resolved
+fn resolve_landing(player: &mut Player, player_index: usize, state: &mut GameState, chance_source: &mut dyn ChanceSource, observers: &mut [&mut dyn SimulationObserver], mut landed_space: u8) -> u8 {
     loop {
-        let space = board.get_mut(landed_space as usize).unwrap();
-        let space_action = space.increment_landed(player);
+        let space = state.board.get_mut(landed_space as usize).unwrap();
+        let space_action = space.increment_landed();
+
+        notify_observers(observers, SimulationEventEnum::LandedOnSpace { player_name: player.get_player_name(), space_index: landed_space, space_name: space.get_space_name() });
 
-        landed_space = 
+        if state.is_purchasable(landed_space) && state.get_owner(landed_space).is_none() {
+            state.owners[landed_space as usize] = Some(player_index);
+
+            notify_observers(observers, SimulationEventEnum::PropertyBought { player_name: player.get_player_name(), space_index: landed_space });
+        }
+
+        let moved_space =
             match space_action {
                 SpaceActionEnum::NoAction => break,
                 SpaceActionEnum::DrawCard(deck) => {
-                    let card = match deck {
-                        CardDeckEnum::Chance => card_decks.get_chance_deck().draw_card(rng).unwrap(),
-                        CardDeckEnum::CommunityChest => card_decks.get_community_chest_deck().draw_card(rng).unwrap()
-                    };
+                    let card_index = chance_source.draw_card(deck, &mut state.card_decks);
+                    let card = state.card_decks.get_deck(deck).get_card(card_index).unwrap();
+                    let (card_fee, card_move) = (card.get_fee(), card.get_card_move());
+
+                    notify_observers(observers, SimulationEventEnum::CardDrawn { player_name: player.get_player_name(), deck, card_index, card_text: card.get_card_text() });
 
-                    println!("{} Drew Card {}", player.get_player_name(), card.get_card_text());
+                    state.rule_statistics.card_fees_paid += card_fee as u64;
+                    state.pay_bank(card_fee);
 
-                    match (card.get_move_action())(landed_space) {
+                    match card_move.resolve(&state.board, landed_space) {
                         None => break,
-                        Some(card_moved_space) => card_moved_space
+                        Some(card_moved_space) => {
+                            if card_move == CardMoveEnum::GoToJail {
+                                notify_observers(observers, SimulationEventEnum::SentToJail { player_name: player.get_player_name(), reason: JailReasonEnum::Card });
+                                player.send_to_jail();
+                            }
+                            else if card_move.passes_go(landed_space, card_moved_space) {
+                                player.set_passed_go();
+                            }
+
+                            card_moved_space
+                        }
                     }
                 },
-                SpaceActionEnum::MovePlayer(new_space) => new_space
+                SpaceActionEnum::SendToJail => {
+                    notify_observers(observers, SimulationEventEnum::SentToJail { player_name: player.get_player_name(), reason: JailReasonEnum::GoToJailSpace });
+                    player.send_to_jail();
+
+                    state.jail_space
+                },
+                SpaceActionEnum::PayTax(amount) => {
+                    state.rule_statistics.taxes_paid += amount as u64;
+                    state.pay_bank(amount);
+                    break;
+                },
+                SpaceActionEnum::LandOnGo => {
+                    player.set_passed_go();
+
+                    //Passing Go already paid one salary, landing on it exactly pays a second
+                    if state.rules.double_salary_on_go {
+                        state.rule_statistics.go_bonus_paid += state.rules.go_salary as u64;
+                    }
+                    break;
+                },
+                SpaceActionEnum::CollectFreeParking => {
+                    state.rule_statistics.free_parking_paid_out += state.free_parking_pot;
+                    state.free_parking_pot = 0;
+                    break;
+                },
+                SpaceActionEnum::TakeBusTicket => {
+                    //Nothing to take once the bank has run out
+                    if state.bus_tickets_left > 0 {
+                        state.bus_tickets_left -= 1;
+                        player.add_bus_ticket();
+                    }
+                    break;
+                }
             };
+
+        notify_observers(observers, SimulationEventEnum::PlayerMoved { player_name: player.get_player_name(), from_space: landed_space, to_space: moved_space });
+
+        landed_space = moved_space;
     }
 
+    landed_space
+}
 
-    //Now that we've finalized our ending place, update the player's saved location
-    player.set_current_space(landed_space);
-    
 
 
+//Now that we've finalized our ending place, update the player's saved location
+fn finish_roll(player: &mut Player, observers: &mut [&mut dyn SimulationObserver], final_space: u8) {
+    player.set_current_space(final_space);
 
-    //Now that we're done processing the players turn, if they rolled doubles they need to take another turn
-    if roll_again {
-        //RECURSION!
-        take_player_turn(player, rng, board, card_decks, doubles_count);
+    notify_observers(observers, SimulationEventEnum::RollFinished { player_name: player.get_player_name(), final_space });
+}
+
+
+
+fn leave_jail(player: &mut Player, state: &mut GameState, observers: &mut [&mut dyn SimulationObserver], paid_fine: bool) {
+    if paid_fine {
+        state.rule_statistics.jail_fines_paid += state.rules.jail_fine as u64;
+        state.pay_bank(state.rules.jail_fine);
     }
-}
\ No newline at end of file
+
+    player.leave_jail();
+
+    notify_observers(observers, SimulationEventEnum::LeftJail { player_name: player.get_player_name(), paid_fine });
+}
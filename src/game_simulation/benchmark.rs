@@ -0,0 +1,349 @@
+use std::hint::black_box;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::game_simulation::{GameState, Simulation};
+use crate::game_simulation::chance_source::{ChanceSource, RngChanceSource};
+use crate::game_simulation::dice_model::StandardDice;
+use crate::game_simulation::fast_engine::FastEngine;
+use crate::game_simulation::game_board::{self, CardDeckEnum, SpaceActionEnum};
+use crate::game_simulation::game_cards::CardDecks;
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::simulation_events::{SimulationEventEnum, SimulationObserver, notify_observers};
+use crate::game_simulation::simulation_results::RuleStatistics;
+use crate::game_simulation::speed_die;
+
+
+//Player counts every benchmark run covers
+pub const BENCHMARK_PLAYER_COUNTS: [u32; 4] = [2, 4, 6, 8];
+
+//Turns per second dropping by more than this against a baseline counts as a regression
+pub const REGRESSION_THRESHOLD: f64 = 0.10;
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EngineEnum {
+    //Simulation::run, the full rules engine with events
+    Standard,
+    //Simulation::run_fast
+    Fast
+}
+
+impl EngineEnum {
+    pub const ALL: [EngineEnum; 2] = [
+        EngineEnum::Standard,
+        EngineEnum::Fast
+    ];
+
+    pub fn get_engine_name(&self) -> &'static str {
+        match self {
+            EngineEnum::Standard => "standard",
+            EngineEnum::Fast => "fast"
+        }
+    }
+
+    pub fn from_engine_name(name: &str) -> Option<Self> {
+        EngineEnum::ALL.into_iter().find(|engine| engine.get_engine_name() == name)
+    }
+}
+
+
+
+//How long one engine took to play a batch of whole games
+pub struct BenchmarkResult {
+    pub engine: EngineEnum,
+    pub player_count: u32,
+    pub turn_count: u32,
+    pub game_count: u32,
+    pub elapsed: Duration
+}
+
+impl BenchmarkResult {
+    //Turns taken by a single player, the same unit as SimulationResults::get_total_player_turns
+    pub fn get_turns_per_second(&self) -> f64 {
+        (self.player_count as u64 * self.turn_count as u64 * self.game_count as u64) as f64 / self.elapsed.as_secs_f64()
+    }
+
+    pub fn get_games_per_second(&self) -> f64 {
+        self.game_count as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+
+
+//Time spent on each phase of a turn, each one timed on its own over the same number of iterations
+pub struct PhaseTimes {
+    pub dice: Duration,
+    pub movement: Duration,
+    pub card_resolution: Duration,
+    pub stats_collection: Duration
+}
+
+
+
+//How often each phase happens in an average player turn, counted from a real game
+pub struct PhaseRates {
+    pub rolls_per_turn: f64,
+    pub card_draws_per_turn: f64,
+    pub landings_per_turn: f64
+}
+
+
+
+//Plays game_count games of turn_count turns each, one seed per game
+pub fn benchmark_engine(engine: EngineEnum, player_count: u32, turn_count: u32, game_count: u32) -> BenchmarkResult {
+    let start = Instant::now();
+
+    for seed in 0..game_count as u64 {
+        let simulation = Simulation::new().player_count(player_count).turn_count(turn_count).seed(seed);
+
+        let results = match engine {
+            EngineEnum::Standard => simulation.run(),
+            EngineEnum::Fast => simulation.run_fast().expect("the classic game always runs on the fast engine")
+        };
+
+        black_box(results);
+    }
+
+    BenchmarkResult { engine, player_count, turn_count, game_count, elapsed: start.elapsed() }
+}
+
+
+//Every engine at every player count in BENCHMARK_PLAYER_COUNTS
+pub fn run_benchmarks(turn_count: u32, game_count: u32) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+
+    for engine in EngineEnum::ALL {
+        for player_count in BENCHMARK_PLAYER_COUNTS {
+            results.push(benchmark_engine(engine, player_count, turn_count, game_count));
+        }
+    }
+
+    results
+}
+
+
+pub fn time_phases(engine: EngineEnum, iterations: u32) -> PhaseTimes {
+    let board = game_board::initialize_game_board();
+    let mut card_decks = CardDecks::new();
+
+    match engine {
+        EngineEnum::Standard => time_standard_phases(iterations),
+        EngineEnum::Fast => FastEngine::new(&board, &mut card_decks, &RuleSet::default())
+            .expect("the classic game always runs on the fast engine")
+            .time_phases(iterations, 0)
+    }
+}
+
+
+pub fn measure_phase_rates(player_count: u32, turn_count: u32) -> PhaseRates {
+    let mut counter = PhaseCounter::default();
+
+    let results = Simulation::new()
+        .player_count(player_count)
+        .turn_count(turn_count)
+        .seed(0)
+        .add_observer(&mut counter)
+        .run();
+
+    let total_player_turns = results.get_total_player_turns() as f64;
+
+    PhaseRates {
+        rolls_per_turn: counter.rolls as f64 / total_player_turns,
+        card_draws_per_turn: counter.card_draws as f64 / total_player_turns,
+        landings_per_turn: counter.landings as f64 / total_player_turns
+    }
+}
+
+
+
+pub fn print_benchmark_results(results: &[BenchmarkResult]) {
+    println!("----------------------BENCHMARK----------------------");
+    println!("Engine|Players|Turns per second|Games per second");
+
+    for result in results {
+        println!("{}|{}|{:.0}|{:.1}", result.engine.get_engine_name(), result.player_count, result.get_turns_per_second(), result.get_games_per_second());
+    }
+
+    println!("-----------------------------------------------------");
+}
+
+
+//Each phase's time per operation, and its estimated share of a turn given how often it happens per turn
+pub fn print_phase_times(engine: EngineEnum, phase_times: &PhaseTimes, iterations: u32, rates: &PhaseRates) {
+    let phases = [
+        ("Dice", phase_times.dice, rates.rolls_per_turn),
+        ("Movement", phase_times.movement, rates.rolls_per_turn),
+        ("Card resolution", phase_times.card_resolution, rates.card_draws_per_turn),
+        ("Stats collection", phase_times.stats_collection, rates.landings_per_turn)
+    ];
+
+    let nanos_per_turn = |elapsed: Duration, per_turn: f64| elapsed.as_nanos() as f64 / iterations as f64 * per_turn;
+    let total_nanos_per_turn: f64 = phases.iter().map(|(_, elapsed, per_turn)| nanos_per_turn(*elapsed, *per_turn)).sum();
+
+    println!("-------------------PHASES ({})-------------------", engine.get_engine_name());
+    println!("Phase|ns per operation|Operations per turn|Share of phase time");
+
+    for (phase_name, elapsed, per_turn) in phases {
+        println!("{}|{:.2}|{:.3}|{:.1}%",
+            phase_name,
+            elapsed.as_nanos() as f64 / iterations as f64,
+            per_turn,
+            nanos_per_turn(elapsed, per_turn) / total_nanos_per_turn * 100.0);
+    }
+
+    println!("-----------------------------------------------------");
+}
+
+
+
+//Baseline format, one line per engine and player count:  <engine> <players> <turns per second>
+pub fn write_baseline(results: &[BenchmarkResult], writer: &mut impl Write) -> io::Result<()> {
+    for result in results {
+        writeln!(writer, "{} {} {:.0}", result.engine.get_engine_name(), result.player_count, result.get_turns_per_second())?;
+    }
+
+    Ok(())
+}
+
+
+pub fn read_baseline(reader: impl BufRead) -> Result<Vec<(EngineEnum, u32, f64)>, String> {
+    let mut baseline = Vec::new();
+
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Error reading line {}: {}", line_idx + 1, e))?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        let entry = match tokens.as_slice() {
+            [engine, player_count, turns_per_second] => (
+                EngineEnum::from_engine_name(engine),
+                player_count.parse().ok(),
+                turns_per_second.parse().ok()
+            ),
+            [] => continue,
+            _ => (None, None, None)
+        };
+
+        match entry {
+            (Some(engine), Some(player_count), Some(turns_per_second)) => baseline.push((engine, player_count, turns_per_second)),
+            _ => return Err(format!("Line {}: expected '<engine> <players> <turns per second>', got '{}'", line_idx + 1, line))
+        }
+    }
+
+    Ok(baseline)
+}
+
+
+//Prints how each result moved against the baseline.  Returns false if anything got slower by more than REGRESSION_THRESHOLD
+pub fn compare_to_baseline(results: &[BenchmarkResult], baseline: &[(EngineEnum, u32, f64)]) -> bool {
+    let mut no_regressions = true;
+
+    println!("----------------------BASELINE-----------------------");
+    println!("Engine|Players|Baseline turns per second|Change");
+
+    for result in results {
+        let baseline_entry = baseline.iter().find(|(engine, player_count, _)| *engine == result.engine && *player_count == result.player_count);
+
+        if let Some((_, _, baseline_turns_per_second)) = baseline_entry {
+            let change = result.get_turns_per_second() / baseline_turns_per_second - 1.0;
+            let regressed = change < -REGRESSION_THRESHOLD;
+
+            println!("{}|{}|{:.0}|{:+.1}%{}", result.engine.get_engine_name(), result.player_count, baseline_turns_per_second, change * 100.0, if regressed { "  REGRESSION" } else { "" });
+
+            no_regressions &= !regressed;
+        }
+    }
+
+    println!("-----------------------------------------------------");
+
+    no_regressions
+}
+
+
+
+//The standard engine's phases, timed through the same code take_player_turn calls
+fn time_standard_phases(iterations: u32) -> PhaseTimes {
+    let board = game_board::initialize_game_board();
+    let jail_space = game_board::get_jail_space(&board).unwrap();
+    let card_spaces: Vec<(CardDeckEnum, u8)> = board.iter()
+        .enumerate()
+        .filter_map(|(space, board_space)| match board_space.get_space_action() {
+            SpaceActionEnum::DrawCard(deck) => Some((deck, space as u8)),
+            _ => None
+        })
+        .collect();
+
+    let mut card_decks = CardDecks::new();
+    card_decks.for_board(&board).unwrap();
+
+    let mut state = GameState {
+        owners: vec![None; board.len()],
+        board,
+        jail_space,
+        card_decks,
+        rules: RuleSet::default(),
+        rule_statistics: RuleStatistics::default(),
+        free_parking_pot: 0,
+        bus_tickets_left: speed_die::BUS_TICKET_COUNT
+    };
+
+    let mut chance_source = RngChanceSource::new(0, Box::new(StandardDice));
+    let mut observers: Vec<&mut dyn SimulationObserver> = Vec::new();
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(chance_source.roll_dice());
+    }
+    let dice = start.elapsed();
+
+    let start = Instant::now();
+    let mut space = 0u8;
+    for iteration in 0..iterations {
+        space = state.advance(space, black_box(iteration % 11 + 2) as u8);
+    }
+    black_box(space);
+    let movement = start.elapsed();
+
+    //A board without any card spaces has no draws to time
+    let start = Instant::now();
+    for (deck, space) in card_spaces.iter().copied().cycle().take(iterations as usize) {
+        let card_index = chance_source.draw_card(deck, &mut state.card_decks);
+        let card = state.card_decks.get_deck(deck).get_card(card_index).unwrap();
+
+        black_box((card.get_fee(), card.get_card_move().resolve(&state.board, space)));
+    }
+    let card_resolution = start.elapsed();
+
+    let start = Instant::now();
+    for iteration in 0..iterations as usize {
+        let space_index = black_box(iteration % state.board.len());
+        let space = &mut state.board[space_index];
+
+        black_box(space.increment_landed());
+        notify_observers(&mut observers, SimulationEventEnum::LandedOnSpace { player_name: "Player 1", space_index: space_index as u8, space_name: space.get_space_name() });
+    }
+    let stats_collection = start.elapsed();
+
+    PhaseTimes { dice, movement, card_resolution, stats_collection }
+}
+
+
+
+#[derive(Default)]
+struct PhaseCounter {
+    rolls: u64,
+    card_draws: u64,
+    landings: u64
+}
+
+impl SimulationObserver for PhaseCounter {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        match event {
+            SimulationEventEnum::DiceRolled { .. } => self.rolls += 1,
+            SimulationEventEnum::CardDrawn { .. } => self.card_draws += 1,
+            SimulationEventEnum::LandedOnSpace { .. } => self.landings += 1,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::game_simulation::game_board::CardDeckEnum;
+use crate::game_simulation::game_cards::CardDecks;
+use crate::game_simulation::speed_die::SpeedDieEnum;
+use crate::game_simulation::dice_model::{DiceModel, DiceRoll};
+
+
+//Where dice rolls and card draws come from.  Normally the seeded RNG, but a replay log can stand in for it so the rules engine
+//plays out exactly the same game without needing the same RNG
+pub(crate) trait ChanceSource {
+    fn roll_dice(&mut self) -> DiceRoll;
+
+    fn roll_speed_die(&mut self) -> SpeedDieEnum;
+
+    //A bus ticket move takes the place of a roll.  Nothing to do unless the source is keeping track of rolls
+    fn use_bus_ticket(&mut self) {}
+
+    //Returns the index of the drawn card within its deck
+    fn draw_card(&mut self, deck: CardDeckEnum, card_decks: &mut CardDecks) -> usize;
+}
+
+
+
+pub(crate) struct RngChanceSource {
+    rng: StdRng,
+    dice_model: Box<dyn DiceModel>
+}
+impl RngChanceSource {
+    pub fn new(seed: u64, dice_model: Box<dyn DiceModel>) -> Self {
+        RngChanceSource { rng: StdRng::seed_from_u64(seed), dice_model }
+    }
+}
+impl ChanceSource for RngChanceSource {
+    fn roll_dice(&mut self) -> DiceRoll {
+        self.dice_model.roll(&mut self.rng)
+    }
+
+    fn roll_speed_die(&mut self) -> SpeedDieEnum {
+        self.dice_model.roll_speed_die(&mut self.rng)
+    }
+
+    fn draw_card(&mut self, deck: CardDeckEnum, card_decks: &mut CardDecks) -> usize {
+        card_decks.get_deck(deck).draw_card(&mut self.rng).expect("card decks should never be empty")
+    }
+}
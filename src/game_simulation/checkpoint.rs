@@ -0,0 +1,246 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::game_simulation::fast_engine::FastRunState;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::precision::{PrecisionTarget, PrecisionTracker};
+use crate::game_simulation::replay_log::{parse_keyed_value, parse_keyed_numbers, join_numbers};
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::simulation_results::RuleStatistics;
+
+
+//Bump whenever the checkpoint format changes, older checkpoints are rejected rather than misread
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+const CHECKPOINT_HEADER: &str = "MONOPOLY-CHECKPOINT";
+
+
+
+//The settings a checkpoint was saved under.  A run only picks up a checkpoint saved with exactly the same settings, anything
+//else would quietly give a different answer than the uninterrupted run.  Custom boards and card decks aren't recorded, resume
+//those with the same board and decks they started with
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct CheckpointSettings {
+    pub player_count: u32,
+    //Turns asked for, or the precision target's turn limit
+    pub turn_count: u32,
+    pub seed: u64,
+    pub edition: GameEditionEnum,
+    pub rules: RuleSet,
+    pub starting_positions: Vec<u8>,
+    pub precision: Option<PrecisionTarget>
+}
+
+impl CheckpointSettings {
+    //The first setting that doesn't match, described for an error message
+    pub fn find_difference(&self, other: &CheckpointSettings) -> Option<String> {
+        let settings = [
+            ("players", self.player_count.to_string(), other.player_count.to_string()),
+            ("turns", self.turn_count.to_string(), other.turn_count.to_string()),
+            ("seed", self.seed.to_string(), other.seed.to_string()),
+            ("edition", self.edition.get_edition_name().to_string(), other.edition.get_edition_name().to_string()),
+            ("rules", self.rules.to_spec(), other.rules.to_spec()),
+            ("starting positions", join_numbers(&self.starting_positions), join_numbers(&other.starting_positions)),
+            ("precision target", get_precision_spec(self.precision), get_precision_spec(other.precision))
+        ];
+
+        settings.into_iter()
+            .find(|(_, setting, other_setting)| setting != other_setting)
+            .map(|(setting_name, setting, other_setting)| format!("{} '{}' rather than '{}'", setting_name, setting, other_setting))
+    }
+}
+
+
+
+//A fast run saved part way through: its settings, the RNG state, every player, both decks, the counts so far and, for runs with a
+//precision target, the batch means so far
+pub(crate) struct Checkpoint {
+    pub settings: CheckpointSettings,
+    pub run_state: FastRunState,
+    pub precision_state: Option<PrecisionTracker>
+}
+
+impl Checkpoint {
+    //Writes to a temporary file first and renames it over the old checkpoint, so being killed part way through a save never
+    //leaves a half written checkpoint behind
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+
+        fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                self.write_to(&mut file)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, path))
+            .map_err(|e| format!("Error saving checkpoint {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        fs::File::open(path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| Checkpoint::read_from(BufReader::new(file)))
+            .map_err(|e| format!("Error reading checkpoint {}: {}", path.display(), e))
+    }
+
+
+    //Text format, one value or list of values per line.  Floats are written in full so they read back bit for bit
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let settings = &self.settings;
+        let state = &self.run_state;
+        let stats = &state.rule_statistics;
+
+        writeln!(writer, "{} {}", CHECKPOINT_HEADER, CHECKPOINT_VERSION)?;
+        writeln!(writer, "players {}", settings.player_count)?;
+        writeln!(writer, "turns {}", settings.turn_count)?;
+        writeln!(writer, "seed {}", settings.seed)?;
+        writeln!(writer, "edition {}", settings.edition.get_edition_name())?;
+        writeln!(writer, "rules {}", settings.rules.to_spec())?;
+        writeln!(writer, "start {}", join_numbers(&settings.starting_positions))?;
+
+        writeln!(writer, "precision {}", get_precision_spec(settings.precision))?;
+
+        writeln!(writer, "played {}", state.turns_played)?;
+        writeln!(writer, "rng {}", join_numbers(&state.rng_state))?;
+        writeln!(writer, "positions {}", join_numbers(&state.positions))?;
+        writeln!(writer, "in_jail {}", join_numbers(&state.in_jail))?;
+        writeln!(writer, "jail_turns {}", join_numbers(&state.jail_turns))?;
+        writeln!(writer, "chance {} {}", state.deck_next_cards[0], join_numbers(&state.deck_orders[0]))?;
+        writeln!(writer, "community_chest {} {}", state.deck_next_cards[1], join_numbers(&state.deck_orders[1]))?;
+        writeln!(writer, "landed {}", join_numbers(&state.landed_counts))?;
+        writeln!(writer, "statistics {} {} {} {} {} {}", stats.go_bonus_paid, stats.taxes_paid, stats.card_fees_paid, stats.jail_fines_paid, stats.free_parking_paid_out, stats.jail_turns_served)?;
+        writeln!(writer, "pot {}", state.free_parking_pot)?;
+
+        if let Some(tracker) = &self.precision_state {
+            writeln!(writer, "batches {}", tracker.batch_count)?;
+            writeln!(writer, "batch_counts {}", join_numbers(&tracker.previous_counts))?;
+            writeln!(writer, "share_sums {}", join_numbers(&tracker.share_sums))?;
+            writeln!(writer, "share_square_sums {}", join_numbers(&tracker.share_square_sums))?;
+        }
+
+        Ok(())
+    }
+
+
+    pub fn read_from(reader: impl BufRead) -> Result<Self, String> {
+        let mut lines = reader.lines().enumerate();
+
+        let mut next_line = || -> Result<(usize, String), String> {
+            match lines.next() {
+                Some((line_idx, Ok(line))) => Ok((line_idx + 1, line)),
+                Some((line_idx, Err(e))) => Err(format!("Error reading line {}: {}", line_idx + 1, e)),
+                None => Err(String::from("Checkpoint ended early"))
+            }
+        };
+
+        let (_, header) = next_line()?;
+        let expected_header = format!("{} {}", CHECKPOINT_HEADER, CHECKPOINT_VERSION);
+
+        if header.trim() != expected_header {
+            return Err(format!("Not a version {} checkpoint (header was '{}')", CHECKPOINT_VERSION, header.trim()));
+        }
+
+        let player_count = parse_keyed_value(next_line()?, "players")?;
+        let turn_count = parse_keyed_value(next_line()?, "turns")?;
+        let seed = parse_keyed_value(next_line()?, "seed")?;
+        let edition_name: String = parse_keyed_value(next_line()?, "edition")?;
+        let edition = GameEditionEnum::from_edition_name(&edition_name).ok_or(format!("Unknown edition '{}'", edition_name))?;
+        let rules_spec: String = parse_keyed_value(next_line()?, "rules")?;
+        let rules = RuleSet::from_spec(&rules_spec)?;
+        let starting_positions = parse_keyed_numbers(next_line()?, "start")?;
+
+        let (line_num, precision_line) = next_line()?;
+        let precision = match precision_line.strip_prefix("precision ").map(|values| values.split_whitespace().collect::<Vec<_>>()) {
+            Some(values) if values == ["none"] => None,
+            Some(values) if values.len() == 4 => {
+                let invalid = || format!("Line {}: invalid precision target '{}'", line_num, precision_line);
+                let (half_width, confidence): (f64, f64) = (values[0].parse().map_err(|_| invalid())?, values[1].parse().map_err(|_| invalid())?);
+                let max_turns = values[3].parse().map_err(|_| invalid())?;
+
+                let target = match half_width {
+                    0.0 => PrecisionTarget::fixed_turns(max_turns, confidence)?,
+                    _ => PrecisionTarget::new(half_width, confidence)?
+                };
+
+                Some(target.batch_turns(values[2].parse().map_err(|_| invalid())?).max_turns(max_turns))
+            },
+            _ => return Err(format!("Line {}: expected 'precision <half width> <confidence> <batch turns> <max turns>' or 'precision none', got '{}'", line_num, precision_line))
+        };
+
+        let settings = CheckpointSettings { player_count, turn_count, seed, edition, rules, starting_positions, precision };
+
+        let turns_played = parse_keyed_value(next_line()?, "played")?;
+        let rng_state: Vec<u64> = parse_keyed_numbers(next_line()?, "rng")?;
+        let positions = parse_keyed_numbers(next_line()?, "positions")?;
+        let in_jail = parse_keyed_numbers(next_line()?, "in_jail")?;
+        let jail_turns = parse_keyed_numbers(next_line()?, "jail_turns")?;
+        let chance: Vec<u8> = parse_keyed_numbers(next_line()?, "chance")?;
+        let community_chest: Vec<u8> = parse_keyed_numbers(next_line()?, "community_chest")?;
+        let landed_counts = parse_keyed_numbers(next_line()?, "landed")?;
+        let stats: Vec<u64> = parse_keyed_numbers(next_line()?, "statistics")?;
+        let free_parking_pot = parse_keyed_value(next_line()?, "pot")?;
+
+        let rng_state: [u64; 4] = rng_state.try_into().map_err(|_| String::from("The RNG state needs 4 numbers"))?;
+
+        let (chance_next_card, chance_order) = chance.split_first().ok_or("The Chance deck line is empty")?;
+        let (community_chest_next_card, community_chest_order) = community_chest.split_first().ok_or("The Community Chest deck line is empty")?;
+
+        let rule_statistics = match stats.as_slice() {
+            [go_bonus_paid, taxes_paid, card_fees_paid, jail_fines_paid, free_parking_paid_out, jail_turns_served] => RuleStatistics {
+                go_bonus_paid: *go_bonus_paid,
+                taxes_paid: *taxes_paid,
+                card_fees_paid: *card_fees_paid,
+                jail_fines_paid: *jail_fines_paid,
+                free_parking_paid_out: *free_parking_paid_out,
+                jail_turns_served: *jail_turns_served
+            },
+            _ => return Err(String::from("The statistics line needs 6 numbers"))
+        };
+
+        let run_state = FastRunState {
+            turns_played,
+            rng_state,
+            positions,
+            in_jail,
+            jail_turns,
+            deck_orders: [chance_order.to_vec(), community_chest_order.to_vec()],
+            deck_next_cards: [*chance_next_card, *community_chest_next_card],
+            landed_counts,
+            rule_statistics,
+            free_parking_pot
+        };
+
+        let precision_state = match precision {
+            Some(target) => {
+                let mut tracker = PrecisionTracker::new(target, run_state.landed_counts.len());
+
+                tracker.batch_count = parse_keyed_value(next_line()?, "batches")?;
+                tracker.previous_counts = parse_keyed_numbers(next_line()?, "batch_counts")?;
+                tracker.share_sums = parse_keyed_numbers(next_line()?, "share_sums")?;
+                tracker.share_square_sums = parse_keyed_numbers(next_line()?, "share_square_sums")?;
+
+                let space_count = run_state.landed_counts.len();
+
+                if [tracker.previous_counts.len(), tracker.share_sums.len(), tracker.share_square_sums.len()].iter().any(|count| *count != space_count) {
+                    return Err(format!("The precision batches need {} spaces each", space_count));
+                }
+
+                Some(tracker)
+            },
+            None => None
+        };
+
+        Ok(Checkpoint { settings, run_state, precision_state })
+    }
+}
+
+
+
+//<half width> <confidence> <batch turns> <max turns>, or none
+fn get_precision_spec(precision: Option<PrecisionTarget>) -> String {
+    match precision {
+        Some(target) => format!("{} {} {} {}", target.get_half_width(), target.get_confidence(), target.get_batch_turns(), target.get_max_turns()),
+        None => String::from("none")
+    }
+}
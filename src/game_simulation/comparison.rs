@@ -0,0 +1,235 @@
+use crate::game_simulation::game_board::SpaceGroupEnum;
+use crate::game_simulation::precision::PrecisionTarget;
+use crate::game_simulation::saved_results::SavedResults;
+
+
+pub const DEFAULT_SIGNIFICANCE: f64 = 0.05;
+
+//Batches shorter than this depend on the one before enough that their intervals come out too narrow
+const SHORT_BATCH_TURNS: u32 = 1000;
+
+
+
+//How one space (or group) moved between two runs
+#[derive(Clone, Debug)]
+pub struct Difference {
+    pub name: String,
+    pub group: SpaceGroupEnum,
+    pub probability_a: f64,
+    pub probability_b: f64,
+    pub standard_error: f64,
+    //Two sided, for the change being nothing but noise
+    pub p_value: f64,
+    //Still significant once the test is corrected for the number of spaces (or groups) tested together
+    pub significant: bool
+}
+
+impl Difference {
+    pub fn get_change(&self) -> f64 {
+        self.probability_b - self.probability_a
+    }
+}
+
+
+
+//Every space and group of run A against run B.  Each run's intervals give the standard error of each of its probabilities, the
+//runs are independent so the errors of a difference add in quadrature, and a z-test gives its p-value.  Testing 40 spaces at 5%
+//would flag a couple of them on noise alone, so Holm-Bonferroni decides which changes are real: together, the chance of
+//flagging any space that didn't really change stays under the significance level
+pub struct Comparison {
+    pub significance: f64,
+    pub spaces: Vec<Difference>,
+    pub groups: Vec<Difference>,
+    //Which settings the two runs were played with differently, e.g. "rules"
+    pub setting_differences: Vec<&'static str>,
+    pub warnings: Vec<String>
+}
+
+impl Comparison {
+    pub fn get_significant_spaces(&self) -> impl Iterator<Item = &Difference> {
+        self.spaces.iter().filter(|difference| difference.significant)
+    }
+
+    pub fn get_significant_groups(&self) -> impl Iterator<Item = &Difference> {
+        self.groups.iter().filter(|difference| difference.significant)
+    }
+
+
+    pub fn print_comparison(&self) {
+        println!("---------------------COMPARISON----------------------");
+
+        if self.setting_differences.is_empty() {
+            println!("Played with the same settings");
+        }
+        else {
+            println!("Played with different {}", self.setting_differences.join(", "));
+        }
+
+        for warning in self.warnings.iter() {
+            println!("Warning: {}", warning);
+        }
+
+        println!("Changes marked * are significant at {}% after Holm-Bonferroni: {} of {} spaces, {} of {} groups",
+            self.significance * 100.0,
+            self.get_significant_spaces().count(),
+            self.spaces.len(),
+            self.get_significant_groups().count(),
+            self.groups.len());
+
+        println!("A -> B|Change|p-value|Space");
+        for difference in self.spaces.iter() {
+            print_difference(difference);
+        }
+
+        println!("A -> B|Change|p-value|Group");
+        for difference in self.groups.iter() {
+            print_difference(difference);
+        }
+
+        println!("-----------------------------------------------------");
+    }
+}
+
+
+fn print_difference(difference: &Difference) {
+    println!("{:.3}% -> {:.3}%|{:+.3}%|{:.4}{}|{}",
+        difference.probability_a * 100.0,
+        difference.probability_b * 100.0,
+        difference.get_change() * 100.0,
+        difference.p_value,
+        if difference.significant { "*" } else { " " },
+        difference.group.colorize(&difference.name));
+}
+
+
+
+//Runs have to be on boards of the same size, compared space by space.  Significance is the family-wise error rate, e.g. 0.05
+pub fn compare_results(results_a: &SavedResults, results_b: &SavedResults, significance: f64) -> Result<Comparison, String> {
+    if !(significance > 0.0 && significance < 1.0) {
+        return Err(format!("Significance must be between 0 and 1, got {}", significance));
+    }
+
+    if results_a.spaces.len() != results_b.spaces.len() {
+        return Err(format!("Can't compare a {} space board with a {} space board", results_a.spaces.len(), results_b.spaces.len()));
+    }
+
+    let mut warnings = Vec::new();
+
+    for (space_index, (space_a, space_b)) in results_a.spaces.iter().zip(results_b.spaces.iter()).enumerate() {
+        if space_a.name != space_b.name {
+            warnings.push(format!("space {} is {} in A but {} in B", space_index, space_a.name, space_b.name));
+        }
+    }
+
+    for (label, results) in [("A", results_a), ("B", results_b)] {
+        if results.batch_turns < SHORT_BATCH_TURNS {
+            warnings.push(format!("{}'s intervals come from batches of only {} turns, so some noise may be flagged as real", label, results.batch_turns));
+        }
+    }
+
+    let standard_errors_a = get_standard_errors(results_a)?;
+    let standard_errors_b = get_standard_errors(results_b)?;
+
+    let mut spaces: Vec<Difference> = results_a.spaces.iter()
+        .enumerate()
+        .map(|(space_index, space)| new_difference(
+            space.name.clone(),
+            space.group,
+            (results_a.get_landing_probability(space_index), results_b.get_landing_probability(space_index)),
+            (standard_errors_a[space_index], standard_errors_b[space_index])))
+        .collect();
+
+    //The intervals only cover single spaces.  The error of a group's total is at most the sum of its spaces' errors (spaces that
+    //always moved together), so group tests are conservative: they can miss a small change, never flag noise
+    let mut groups: Vec<Difference> = SpaceGroupEnum::ALL.into_iter()
+        .filter(|group| results_a.spaces.iter().any(|space| space.group == *group))
+        .map(|group| {
+            let members: Vec<usize> = (0..results_a.spaces.len()).filter(|space_index| results_a.spaces[*space_index].group == group).collect();
+            let total = |results: &SavedResults, standard_errors: &[f64]| members.iter()
+                .fold((0.0, 0.0), |(probability, standard_error), space_index| (probability + results.get_landing_probability(*space_index), standard_error + standard_errors[*space_index]));
+
+            let (probability_a, standard_error_a) = total(results_a, &standard_errors_a);
+            let (probability_b, standard_error_b) = total(results_b, &standard_errors_b);
+
+            new_difference(group.get_group_name().to_string(), group, (probability_a, probability_b), (standard_error_a, standard_error_b))
+        })
+        .collect();
+
+    apply_holm_bonferroni(&mut spaces, significance);
+    apply_holm_bonferroni(&mut groups, significance);
+
+    let setting_differences = [
+        ("edition", results_a.edition != results_b.edition),
+        ("players", results_a.player_count != results_b.player_count),
+        ("turns", results_a.turn_count != results_b.turn_count),
+        ("seed", results_a.seed != results_b.seed),
+        ("dice", results_a.dice_model != results_b.dice_model),
+        ("rules", results_a.rules != results_b.rules)
+    ].into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(setting, _)| setting)
+        .collect();
+
+    Ok(Comparison { significance, spaces, groups, setting_differences, warnings })
+}
+
+
+//Standard error of each space's landing probability, backed out of its interval
+fn get_standard_errors(results: &SavedResults) -> Result<Vec<f64>, String> {
+    let z_score = PrecisionTarget::new(0.5, results.confidence)?.get_z_score();
+
+    Ok(results.spaces.iter().map(|space| space.half_width / z_score).collect())
+}
+
+
+fn new_difference(name: String, group: SpaceGroupEnum, (probability_a, probability_b): (f64, f64), (standard_error_a, standard_error_b): (f64, f64)) -> Difference {
+    let standard_error = (standard_error_a * standard_error_a + standard_error_b * standard_error_b).sqrt();
+    let change = probability_b - probability_a;
+
+    //A space neither run has any spread on only changed if its probability did
+    let p_value = if standard_error > 0.0 {
+        erfc((change / standard_error).abs() / std::f64::consts::SQRT_2)
+    }
+    else if change == 0.0 {
+        1.0
+    }
+    else {
+        0.0
+    };
+
+    Difference { name, group, probability_a, probability_b, standard_error, p_value, significant: false }
+}
+
+
+//Smallest p-value first, the k-th smallest of m has to be under significance / (m - k), and everything after the first that
+//isn't stays unflagged
+fn apply_holm_bonferroni(differences: &mut [Difference], significance: f64) {
+    let mut order: Vec<usize> = (0..differences.len()).collect();
+    order.sort_by(|a, b| differences[*a].p_value.total_cmp(&differences[*b].p_value));
+
+    let test_count = differences.len();
+
+    for (rank, index) in order.into_iter().enumerate() {
+        if differences[index].p_value > significance / (test_count - rank) as f64 {
+            break;
+        }
+
+        differences[index].significant = true;
+    }
+}
+
+
+//Complementary error function, to within 1.2e-7 relative error everywhere (Numerical Recipes' erfcc)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+
+    let polynomial = [-1.26551223, 1.00002368, 0.37409196, 0.09678418, -0.18628806, 0.27886807, -1.13520398, 1.48851587, -0.82215223, 0.17087277]
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| coefficient + t * sum);
+
+    let result = t * (-z * z + polynomial).exp();
+
+    if x >= 0.0 { result } else { 2.0 - result }
+}
@@ -0,0 +1,250 @@
+use rand::{rngs::StdRng, Rng};
+use rand::distributions::{Distribution, WeightedIndex};
+
+use crate::game_simulation::speed_die::SpeedDieEnum;
+
+
+//Most dice a single roll can be made of
+pub const MAX_DICE: usize = 3;
+
+//Biggest face a die can show.  Keeps every move, three dice plus the speed die, within a u8
+pub const MAX_FACE: u8 = 60;
+
+
+
+//The dice thrown for one roll, in the order they were rolled
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiceRoll {
+    dice: [u8; MAX_DICE],
+    dice_count: u8
+}
+
+impl DiceRoll {
+    pub fn new(dice: &[u8]) -> Self {
+        assert!(!dice.is_empty() && dice.len() <= MAX_DICE, "a roll needs between 1 and {} dice, got {}", MAX_DICE, dice.len());
+
+        let mut roll = DiceRoll { dice: [0; MAX_DICE], dice_count: dice.len() as u8 };
+        roll.dice[..dice.len()].copy_from_slice(dice);
+
+        roll
+    }
+
+    pub fn get_dice(&self) -> &[u8] {
+        &self.dice[..self.dice_count as usize]
+    }
+
+    pub fn get_total(&self) -> u8 {
+        self.get_dice().iter().sum()
+    }
+
+    //Doubles only mean something with exactly two dice.  Any other model never rolls doubles, so never rolls again and only
+    //leaves jail by paying
+    pub fn is_doubles(&self) -> bool {
+        self.dice_count == 2 && self.dice[0] == self.dice[1]
+    }
+
+    //Written as the dice joined with '+', e.g. "3+4".  Used by replay logs and scripted dice
+    pub fn to_spec(&self) -> String {
+        self.get_dice().iter().map(|die| die.to_string()).collect::<Vec<_>>().join("+")
+    }
+
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        let dice = spec.split('+')
+            .map(|die| die.trim().parse::<u8>().map_err(|_| format!("Invalid die '{}' in roll '{}'", die, spec)))
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        if dice.len() > MAX_DICE {
+            return Err(format!("Roll '{}' has more than {} dice", spec, MAX_DICE));
+        }
+
+        if dice.iter().any(|die| *die == 0 || *die > MAX_FACE) {
+            return Err(format!("Roll '{}' has a die showing something other than 1-{}", spec, MAX_FACE));
+        }
+
+        Ok(DiceRoll::new(&dice))
+    }
+}
+
+
+
+//How the dice for each roll are produced.  Models that need randomness take it from the simulation's seeded RNG so runs stay
+//reproducible
+pub trait DiceModel {
+    fn roll(&mut self, rng: &mut StdRng) -> DiceRoll;
+
+    //The Mega edition speed die is always fair, unless a test is scripting it
+    fn roll_speed_die(&mut self, rng: &mut StdRng) -> SpeedDieEnum {
+        SpeedDieEnum::from_face(rng.gen_range(1..=6))
+    }
+
+    //Round trips through from_spec, and is how results say which dice were used
+    fn get_model_spec(&self) -> String;
+}
+
+
+
+//Parses a dice model from the command line: 2d6 (the default), 1d6, 3d6, weighted:<dice>:<face>=<weight>,... or
+//scripted:<roll>,<roll>,...  e.g. weighted:2:1=1,2=1,3=1,4=1,5=1,6=1.5 or scripted:3+4,6+6,1+2
+pub fn from_spec(spec: &str) -> Result<Box<dyn DiceModel>, String> {
+    let spec = spec.trim();
+
+    if let Some(weighted_spec) = spec.strip_prefix("weighted:") {
+        let (dice_count, faces_spec) = weighted_spec.split_once(':').ok_or(format!("Expected weighted:<dice>:<faces>, got '{}'", spec))?;
+        let dice_count = dice_count.parse().map_err(|_| format!("Invalid number of dice '{}'", dice_count))?;
+
+        let face_weights = faces_spec.split(',')
+            .map(|face_weight| {
+                let (face, weight) = face_weight.split_once('=').ok_or(format!("Expected <face>=<weight>, got '{}'", face_weight))?;
+                let face = face.trim().parse().map_err(|_| format!("Invalid face '{}'", face))?;
+                let weight = weight.trim().parse().map_err(|_| format!("Invalid weight '{}'", weight))?;
+
+                Ok((face, weight))
+            })
+            .collect::<Result<Vec<(u8, f64)>, String>>()?;
+
+        return Ok(Box::new(WeightedDice::new(dice_count, face_weights)?));
+    }
+
+    if let Some(rolls_spec) = spec.strip_prefix("scripted:") {
+        let rolls = rolls_spec.split(',').map(DiceRoll::from_spec).collect::<Result<Vec<DiceRoll>, String>>()?;
+
+        return Ok(Box::new(ScriptedDice::new(rolls)?));
+    }
+
+    match spec {
+        "2d6" => Ok(Box::new(StandardDice)),
+        "1d6" => Ok(Box::new(SingleDie)),
+        "3d6" => Ok(Box::new(ThreeDice)),
+        _ => Err(format!("Unknown dice model '{}'", spec))
+    }
+}
+
+
+
+//Two fair six sided dice, as in the box
+pub struct StandardDice;
+impl DiceModel for StandardDice {
+    fn roll(&mut self, rng: &mut StdRng) -> DiceRoll {
+        DiceRoll::new(&[rng.gen_range(1..=6), rng.gen_range(1..=6)])
+    }
+
+    fn get_model_spec(&self) -> String {
+        String::from("2d6")
+    }
+}
+
+
+pub struct SingleDie;
+impl DiceModel for SingleDie {
+    fn roll(&mut self, rng: &mut StdRng) -> DiceRoll {
+        DiceRoll::new(&[rng.gen_range(1..=6)])
+    }
+
+    fn get_model_spec(&self) -> String {
+        String::from("1d6")
+    }
+}
+
+
+pub struct ThreeDice;
+impl DiceModel for ThreeDice {
+    fn roll(&mut self, rng: &mut StdRng) -> DiceRoll {
+        DiceRoll::new(&[rng.gen_range(1..=6), rng.gen_range(1..=6), rng.gen_range(1..=6)])
+    }
+
+    fn get_model_spec(&self) -> String {
+        String::from("3d6")
+    }
+}
+
+
+//Identical dice with any faces and any weighting, for worn or loaded dice
+pub struct WeightedDice {
+    dice_count: u8,
+    face_weights: Vec<(u8, f64)>,
+    distribution: WeightedIndex<f64>
+}
+impl WeightedDice {
+    pub fn new(dice_count: u8, face_weights: Vec<(u8, f64)>) -> Result<Self, String> {
+        if dice_count == 0 || dice_count as usize > MAX_DICE {
+            return Err(format!("Weighted dice need between 1 and {} dice, got {}", MAX_DICE, dice_count));
+        }
+
+        if face_weights.iter().any(|(face, _)| *face == 0 || *face > MAX_FACE) {
+            return Err(format!("Weighted dice faces have to be 1-{}", MAX_FACE));
+        }
+
+        let distribution = WeightedIndex::new(face_weights.iter().map(|(_, weight)| *weight))
+            .map_err(|e| format!("Invalid face weights: {}", e))?;
+
+        Ok(WeightedDice { dice_count, face_weights, distribution })
+    }
+}
+impl DiceModel for WeightedDice {
+    fn roll(&mut self, rng: &mut StdRng) -> DiceRoll {
+        let mut dice = [0; MAX_DICE];
+
+        for die in dice.iter_mut().take(self.dice_count as usize) {
+            *die = self.face_weights[self.distribution.sample(rng)].0;
+        }
+
+        DiceRoll::new(&dice[..self.dice_count as usize])
+    }
+
+    fn get_model_spec(&self) -> String {
+        let faces_spec = self.face_weights.iter().map(|(face, weight)| format!("{}={}", face, weight)).collect::<Vec<_>>().join(",");
+
+        format!("weighted:{}:{}", self.dice_count, faces_spec)
+    }
+}
+
+
+//Plays back a fixed list of rolls, starting over from the top once it runs out.  Lets tests force exact roll sequences
+pub struct ScriptedDice {
+    rolls: Vec<DiceRoll>,
+    next_roll: usize,
+    //Played back the same way when set, otherwise the speed die is rolled fair.  Not part of the spec
+    speed_die_faces: Vec<SpeedDieEnum>,
+    next_speed_die: usize
+}
+impl ScriptedDice {
+    pub fn new(rolls: Vec<DiceRoll>) -> Result<Self, String> {
+        if rolls.is_empty() {
+            return Err(String::from("Scripted dice need at least one roll"));
+        }
+
+        Ok(ScriptedDice { rolls, next_roll: 0, speed_die_faces: Vec::new(), next_speed_die: 0 })
+    }
+
+    pub fn with_speed_die(mut self, speed_die_faces: Vec<SpeedDieEnum>) -> Self {
+        self.speed_die_faces = speed_die_faces;
+        self
+    }
+}
+impl DiceModel for ScriptedDice {
+    fn roll(&mut self, _rng: &mut StdRng) -> DiceRoll {
+        let roll = self.rolls[self.next_roll];
+
+        self.next_roll = (self.next_roll + 1) % self.rolls.len();
+
+        roll
+    }
+
+    fn roll_speed_die(&mut self, rng: &mut StdRng) -> SpeedDieEnum {
+        if self.speed_die_faces.is_empty() {
+            return SpeedDieEnum::from_face(rng.gen_range(1..=6));
+        }
+
+        let face = self.speed_die_faces[self.next_speed_die];
+
+        self.next_speed_die = (self.next_speed_die + 1) % self.speed_die_faces.len();
+
+        face
+    }
+
+    fn get_model_spec(&self) -> String {
+        let rolls_spec = self.rolls.iter().map(|roll| roll.to_spec()).collect::<Vec<_>>().join(",");
+
+        format!("scripted:{}", rolls_spec)
+    }
+}
@@ -0,0 +1,561 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::game_simulation::benchmark::PhaseTimes;
+use crate::game_simulation::game_board::{self, BoardSpace, CardDeckEnum, SpaceActionEnum};
+use crate::game_simulation::game_cards::{CardDecks, CardMoveEnum};
+use crate::game_simulation::precision::PrecisionTracker;
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::simulation_results::RuleStatistics;
+
+
+//Biggest board and deck the fast engine has room for
+pub const MAX_SPACES: usize = 64;
+pub const MAX_CARDS: usize = 32;
+
+//Card doesn't move the player
+const NO_MOVE: u8 = u8::MAX;
+
+
+
+//Everything the fast engine needs to know about a space, small enough to copy around
+#[derive(Clone, Copy)]
+enum FastSpaceEnum {
+    Plain,
+    DrawCard(u8),
+    GoToJail,
+    PayTax(u32),
+    Go,
+    FreeParking
+}
+
+
+
+//A deck boiled down to card numbers.  What each card does is looked up from tables built once up front rather than calling
+//the card's closure every draw
+#[derive(Clone, Copy)]
+struct FastDeck {
+    card_count: u8,
+    order: [u8; MAX_CARDS],
+    //Cards drawn since the last shuffle.  Starts full so the first draw shuffles, same as GameActionCardDeck
+    next_card: u8,
+    fees: [u32; MAX_CARDS],
+    //Where each card sends a player drawing it on each space
+    moves: [[u8; MAX_SPACES]; MAX_CARDS],
+    sends_to_jail: [bool; MAX_CARDS]
+}
+
+impl FastDeck {
+    fn draw(&mut self, rng: &mut FastRng) -> u8 {
+        if self.next_card == self.card_count {
+            //Fisher-Yates
+            for card in (1..self.card_count as usize).rev() {
+                self.order.swap(card, rng.below(card as u32 + 1) as usize);
+            }
+
+            self.next_card = 0;
+        }
+
+        let card = self.order[self.next_card as usize];
+        self.next_card += 1;
+
+        card
+    }
+}
+
+
+
+#[derive(Clone, Copy, Default)]
+struct FastPlayer {
+    space: u8,
+    in_jail: bool,
+    jail_turns: u8
+}
+
+
+
+//What a fast run produced, ready to be turned into SimulationResults
+pub(crate) struct FastRunResults {
+    //Fewer than asked for when a precision target stopped the run early
+    pub turns_played: u32,
+    pub landed_counts: [u64; MAX_SPACES],
+    pub rule_statistics: RuleStatistics,
+    pub final_positions: Vec<u8>,
+    pub final_in_jail: Vec<bool>
+}
+
+
+
+//Everything a fast run changes as it goes, enough to pick it back up exactly where it was.  See checkpoint.rs
+pub(crate) struct FastRunState {
+    pub turns_played: u32,
+    pub rng_state: [u64; 4],
+    pub positions: Vec<u8>,
+    pub in_jail: Vec<bool>,
+    pub jail_turns: Vec<u8>,
+    //Card order and cards drawn since the last shuffle, for Chance then Community Chest
+    pub deck_orders: [Vec<u8>; 2],
+    pub deck_next_cards: [u8; 2],
+    pub landed_counts: Vec<u64>,
+    pub rule_statistics: RuleStatistics,
+    pub free_parking_pot: u64
+}
+
+
+
+//Same rules as take_player_turn, minus everything that costs time without changing landing counts: no events, no names, no
+//virtual calls, no allocation once running.  Two fair dice only, and nothing that depends on who owns what (the speed die and
+//bus tickets)
+pub(crate) struct FastEngine {
+    spaces: [FastSpaceEnum; MAX_SPACES],
+    space_count: u8,
+    jail_space: u8,
+    decks: [FastDeck; 2],
+    rules: RuleSet,
+    landed_counts: [u64; MAX_SPACES],
+    rule_statistics: RuleStatistics,
+    free_parking_pot: u64
+}
+
+impl FastEngine {
+    pub fn new(board: &[Box<dyn BoardSpace>], card_decks: &mut CardDecks, rules: &RuleSet) -> Result<Self, String> {
+        if rules.speed_die {
+            return Err(String::from("The fast engine doesn't support the speed die"));
+        }
+
+        if board.len() > MAX_SPACES {
+            return Err(format!("The fast engine supports boards of up to {} spaces, this one has {}", MAX_SPACES, board.len()));
+        }
+
+        let jail_space = game_board::get_jail_space(board)?;
+        card_decks.for_board(board)?;
+
+        let mut spaces = [FastSpaceEnum::Plain; MAX_SPACES];
+
+        for (space_index, space) in board.iter().enumerate() {
+            spaces[space_index] = match space.get_space_action() {
+                SpaceActionEnum::NoAction => FastSpaceEnum::Plain,
+                SpaceActionEnum::DrawCard(deck) => FastSpaceEnum::DrawCard(get_deck_index(deck)),
+                SpaceActionEnum::SendToJail => FastSpaceEnum::GoToJail,
+                SpaceActionEnum::PayTax(amount) => FastSpaceEnum::PayTax(amount),
+                SpaceActionEnum::LandOnGo => FastSpaceEnum::Go,
+                SpaceActionEnum::CollectFreeParking => FastSpaceEnum::FreeParking,
+                SpaceActionEnum::TakeBusTicket => return Err(String::from("The fast engine doesn't support bus tickets"))
+            };
+        }
+
+        let decks = [
+            build_fast_deck(board, card_decks, CardDeckEnum::Chance)?,
+            build_fast_deck(board, card_decks, CardDeckEnum::CommunityChest)?
+        ];
+
+        Ok(FastEngine {
+            spaces,
+            space_count: board.len() as u8,
+            jail_space,
+            decks,
+            rules: rules.clone(),
+            landed_counts: [0; MAX_SPACES],
+            rule_statistics: RuleStatistics::default(),
+            free_parking_pot: 0
+        })
+    }
+
+
+    pub fn start(self, player_count: u32, starting_positions: &[u8], seed: u64) -> FastRun {
+        let mut players = vec![FastPlayer::default(); player_count as usize];
+
+        for (player, starting_space) in players.iter_mut().zip(starting_positions.iter()) {
+            player.space = *starting_space;
+        }
+
+        FastRun { engine: self, rng: FastRng::new(seed), players, turns_played: 0 }
+    }
+
+
+    //Times each phase of a turn on its own, so the benchmark can break down where a turn's time goes
+    pub fn time_phases(mut self, iterations: u32, seed: u64) -> PhaseTimes {
+        let mut rng = FastRng::new(seed);
+        let card_spaces: Vec<(usize, u8)> = (0..self.space_count)
+            .filter_map(|space| match self.spaces[space as usize] {
+                FastSpaceEnum::DrawCard(deck_index) => Some((deck_index as usize, space)),
+                _ => None
+            })
+            .collect();
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(rng.roll_dice());
+        }
+        let dice = start.elapsed();
+
+        let start = Instant::now();
+        let mut space = 0u8;
+        for iteration in 0..iterations {
+            space += black_box(iteration % 11 + 2) as u8;
+
+            while space >= self.space_count {
+                space -= self.space_count;
+            }
+        }
+        black_box(space);
+        let movement = start.elapsed();
+
+        //A board without any card spaces has no draws to time
+        let start = Instant::now();
+        for (deck_index, space) in card_spaces.iter().copied().cycle().take(iterations as usize) {
+            let deck = &mut self.decks[deck_index];
+            let card = deck.draw(&mut rng) as usize;
+
+            black_box((deck.fees[card], deck.moves[card][space as usize]));
+        }
+        let card_resolution = start.elapsed();
+
+        let start = Instant::now();
+        for iteration in 0..iterations as usize {
+            self.landed_counts[black_box(iteration % self.space_count as usize)] += 1;
+        }
+        black_box(&self.landed_counts);
+        let stats_collection = start.elapsed();
+
+        PhaseTimes { dice, movement, card_resolution, stats_collection }
+    }
+
+
+    fn take_turn(&mut self, player: &mut FastPlayer, rng: &mut FastRng) {
+        //Paying the fine straight away means a completely normal roll, doubles and all
+        if player.in_jail && self.rules.max_jail_turns == 0 {
+            self.leave_jail(player, true);
+        }
+
+        let mut doubles_count = 0;
+
+        loop {
+            let (dice1, dice2) = rng.roll_dice();
+            let doubles = dice1 == dice2;
+            let mut roll_again = false;
+
+            if player.in_jail {
+                //Getting out with doubles doesn't earn another roll
+                if doubles {
+                    self.leave_jail(player, false);
+                }
+                else {
+                    player.jail_turns += 1;
+
+                    if player.jail_turns >= self.rules.max_jail_turns {
+                        self.leave_jail(player, true);
+                    }
+                    else {
+                        self.rule_statistics.jail_turns_served += 1;
+                        return;
+                    }
+                }
+            }
+            else {
+                doubles_count = if doubles { doubles_count + 1 } else { 0 };
+
+                if doubles_count > 0 && doubles_count == self.rules.max_doubles_before_jail {
+                    player.in_jail = true;
+                    player.jail_turns = 0;
+                    player.space = self.resolve_landing(player, self.jail_space, rng);
+                    return;
+                }
+
+                roll_again = doubles;
+            }
+
+            let mut landed_space = player.space + dice1 + dice2;
+
+            while landed_space >= self.space_count {
+                landed_space -= self.space_count;
+            }
+
+            player.space = self.resolve_landing(player, landed_space, rng);
+
+            //Going to jail ends the turn
+            if !roll_again || player.in_jail {
+                return;
+            }
+        }
+    }
+
+
+    #[inline]
+    fn resolve_landing(&mut self, player: &mut FastPlayer, mut landed_space: u8, rng: &mut FastRng) -> u8 {
+        loop {
+            self.landed_counts[landed_space as usize] += 1;
+
+            match self.spaces[landed_space as usize] {
+                FastSpaceEnum::Plain => return landed_space,
+                FastSpaceEnum::DrawCard(deck_index) => {
+                    let deck = &mut self.decks[deck_index as usize];
+                    let card = deck.draw(rng) as usize;
+                    let (card_fee, card_move, sends_to_jail) = (deck.fees[card], deck.moves[card][landed_space as usize], deck.sends_to_jail[card]);
+
+                    if card_fee > 0 {
+                        self.rule_statistics.card_fees_paid += card_fee as u64;
+                        self.pay_bank(card_fee);
+                    }
+
+                    if card_move == NO_MOVE {
+                        return landed_space;
+                    }
+
+                    if sends_to_jail {
+                        player.in_jail = true;
+                        player.jail_turns = 0;
+                    }
+
+                    landed_space = card_move;
+                },
+                FastSpaceEnum::GoToJail => {
+                    player.in_jail = true;
+                    player.jail_turns = 0;
+                    landed_space = self.jail_space;
+                },
+                FastSpaceEnum::PayTax(amount) => {
+                    self.rule_statistics.taxes_paid += amount as u64;
+                    self.pay_bank(amount);
+                    return landed_space;
+                },
+                FastSpaceEnum::Go => {
+                    if self.rules.double_salary_on_go {
+                        self.rule_statistics.go_bonus_paid += self.rules.go_salary as u64;
+                    }
+                    return landed_space;
+                },
+                FastSpaceEnum::FreeParking => {
+                    self.rule_statistics.free_parking_paid_out += self.free_parking_pot;
+                    self.free_parking_pot = 0;
+                    return landed_space;
+                }
+            }
+        }
+    }
+
+
+    fn leave_jail(&mut self, player: &mut FastPlayer, paid_fine: bool) {
+        if paid_fine {
+            self.rule_statistics.jail_fines_paid += self.rules.jail_fine as u64;
+            self.pay_bank(self.rules.jail_fine);
+        }
+
+        player.in_jail = false;
+        player.jail_turns = 0;
+    }
+
+
+    fn pay_bank(&mut self, amount: u32) {
+        if self.rules.free_parking_jackpot {
+            self.free_parking_pot += amount as u64;
+        }
+    }
+}
+
+
+
+//A fast game in progress.  It can be stopped after any turn, its state saved, and carried on later with exactly the same result
+//as if it had never stopped
+pub(crate) struct FastRun {
+    engine: FastEngine,
+    rng: FastRng,
+    players: Vec<FastPlayer>,
+    turns_played: u32
+}
+
+impl FastRun {
+    //Plays on until turn_count turns have been played in total, or until the precision tracker has what it needs.  True if the
+    //tracker stopped it
+    pub fn play(&mut self, turn_count: u32, mut precision_tracker: Option<&mut PrecisionTracker>) -> bool {
+        let engine = &mut self.engine;
+
+        while self.turns_played < turn_count {
+            self.turns_played += 1;
+
+            for player in self.players.iter_mut() {
+                engine.take_turn(player, &mut self.rng);
+            }
+
+            if let Some(tracker) = precision_tracker.as_mut() {
+                if tracker.is_batch_end(self.turns_played) && tracker.record_batch(engine.landed_counts[..engine.space_count as usize].iter().copied()) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn get_turns_played(&self) -> u32 {
+        self.turns_played
+    }
+
+    pub fn save_state(&self) -> FastRunState {
+        let engine = &self.engine;
+
+        FastRunState {
+            turns_played: self.turns_played,
+            rng_state: self.rng.state,
+            positions: self.players.iter().map(|player| player.space).collect(),
+            in_jail: self.players.iter().map(|player| player.in_jail).collect(),
+            jail_turns: self.players.iter().map(|player| player.jail_turns).collect(),
+            deck_orders: engine.decks.map(|deck| deck.order[..deck.card_count as usize].to_vec()),
+            deck_next_cards: engine.decks.map(|deck| deck.next_card),
+            landed_counts: engine.landed_counts[..engine.space_count as usize].to_vec(),
+            rule_statistics: engine.rule_statistics.clone(),
+            free_parking_pot: engine.free_parking_pot
+        }
+    }
+
+    //Checks the saved state fits this game (same number of players, spaces and cards) before touching anything
+    pub fn restore_state(&mut self, state: FastRunState) -> Result<(), String> {
+        let engine = &mut self.engine;
+        let player_count = self.players.len();
+
+        if [state.positions.len(), state.in_jail.len(), state.jail_turns.len()].iter().any(|count| *count != player_count) {
+            return Err(format!("Saved state is for a different number of players, expected {}", player_count));
+        }
+
+        if state.landed_counts.len() != engine.space_count as usize || state.positions.iter().any(|space| *space >= engine.space_count) {
+            return Err(format!("Saved state is for a different board, expected {} spaces", engine.space_count));
+        }
+
+        for (deck, (order, next_card)) in engine.decks.iter().zip(state.deck_orders.iter().zip(state.deck_next_cards.iter())) {
+            let mut sorted_order = order.clone();
+            sorted_order.sort_unstable();
+
+            if sorted_order != (0..deck.card_count).collect::<Vec<u8>>() || *next_card > deck.card_count {
+                return Err(format!("Saved state is for a different deck, expected {} cards", deck.card_count));
+            }
+        }
+
+        self.turns_played = state.turns_played;
+        self.rng.state = state.rng_state;
+
+        for (player_index, player) in self.players.iter_mut().enumerate() {
+            *player = FastPlayer { space: state.positions[player_index], in_jail: state.in_jail[player_index], jail_turns: state.jail_turns[player_index] };
+        }
+
+        for (deck_index, deck) in engine.decks.iter_mut().enumerate() {
+            deck.order[..deck.card_count as usize].copy_from_slice(&state.deck_orders[deck_index]);
+            deck.next_card = state.deck_next_cards[deck_index];
+        }
+
+        engine.landed_counts[..engine.space_count as usize].copy_from_slice(&state.landed_counts);
+        engine.rule_statistics = state.rule_statistics;
+        engine.free_parking_pot = state.free_parking_pot;
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> FastRunResults {
+        FastRunResults {
+            turns_played: self.turns_played,
+            landed_counts: self.engine.landed_counts,
+            rule_statistics: self.engine.rule_statistics,
+            final_positions: self.players.iter().map(|player| player.space).collect(),
+            final_in_jail: self.players.iter().map(|player| player.in_jail).collect()
+        }
+    }
+}
+
+
+
+fn get_deck_index(deck: CardDeckEnum) -> u8 {
+    match deck {
+        CardDeckEnum::Chance => 0,
+        CardDeckEnum::CommunityChest => 1
+    }
+}
+
+
+//Resolves every card's move once for every space its deck is drawn from
+fn build_fast_deck(board: &[Box<dyn BoardSpace>], card_decks: &mut CardDecks, deck_enum: CardDeckEnum) -> Result<FastDeck, String> {
+    let deck = card_decks.get_deck(deck_enum);
+
+    if deck.get_card_count() > MAX_CARDS {
+        return Err(format!("The fast engine supports decks of up to {} cards, the {:?} deck has {}", MAX_CARDS, deck_enum, deck.get_card_count()));
+    }
+
+    let mut fast_deck = FastDeck {
+        card_count: deck.get_card_count() as u8,
+        order: [0; MAX_CARDS],
+        next_card: deck.get_card_count() as u8,
+        fees: [0; MAX_CARDS],
+        moves: [[NO_MOVE; MAX_SPACES]; MAX_CARDS],
+        sends_to_jail: [false; MAX_CARDS]
+    };
+
+    for card_index in 0..deck.get_card_count() {
+        let card = deck.get_card(card_index).unwrap();
+
+        fast_deck.order[card_index] = card_index as u8;
+        fast_deck.fees[card_index] = card.get_fee();
+        fast_deck.sends_to_jail[card_index] = card.get_card_move() == CardMoveEnum::GoToJail;
+
+        for (space_index, space) in board.iter().enumerate() {
+            if !matches!(space.get_space_action(), SpaceActionEnum::DrawCard(space_deck) if space_deck == deck_enum) {
+                continue;
+            }
+
+            if let Some(moved_space) = card.get_card_move().resolve(board, space_index as u8) {
+                fast_deck.moves[card_index][space_index] = moved_space;
+            }
+        }
+    }
+
+    Ok(fast_deck)
+}
+
+
+
+//xoshiro256++, seeded through splitmix64.  Far faster than StdRng and plenty random enough for dice and shuffles
+struct FastRng {
+    state: [u64; 4]
+}
+
+impl FastRng {
+    fn new(seed: u64) -> Self {
+        let mut splitmix_state = seed;
+        let mut next_splitmix = || {
+            splitmix_state = splitmix_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+            let mut z = splitmix_state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+
+        FastRng { state: [next_splitmix(), next_splitmix(), next_splitmix(), next_splitmix()] }
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let result = self.state[0].wrapping_add(self.state[3]).rotate_left(23).wrapping_add(self.state[0]);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    //Uniform in 0..range by multiplying instead of dividing.  The bias is below range / 2^32, far too small to ever show up
+    #[inline]
+    fn below(&mut self, range: u32) -> u32 {
+        (((self.next_u64() >> 32) * range as u64) >> 32) as u32
+    }
+
+    //Both dice from a single draw
+    #[inline]
+    fn roll_dice(&mut self) -> (u8, u8) {
+        let roll = self.below(36) as u8;
+
+        (roll / 6 + 1, roll % 6 + 1)
+    }
+}
@@ -1,6 +1,6 @@
-use crate::game_simulation::player::Player;
 use colored::{Colorize, ColoredString};
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CardDeckEnum {
     Chance,
     CommunityChest
@@ -9,7 +9,83 @@ pub enum CardDeckEnum {
 pub enum SpaceActionEnum {
     NoAction,
     DrawCard(CardDeckEnum),
-    MovePlayer(u8),
+    SendToJail,
+    PayTax(u32),
+    //The next two only matter for house rules (double salary on Go, Free Parking jackpot)
+    LandOnGo,
+    CollectFreeParking,
+    //Mega edition Bus Ticket and Birthday Gift spaces
+    TakeBusTicket,
+}
+
+
+
+//The color group (or set) a space belongs to.  Purchase decisions are made by group, so reports aggregate on this
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpaceGroupEnum {
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+    Railroads,
+    Utilities,
+    Special
+}
+impl SpaceGroupEnum {
+    //Every group, in the order they're first reached going around the board
+    pub const ALL: [SpaceGroupEnum; 11] = [
+        SpaceGroupEnum::Brown,
+        SpaceGroupEnum::LightBlue,
+        SpaceGroupEnum::Pink,
+        SpaceGroupEnum::Orange,
+        SpaceGroupEnum::Red,
+        SpaceGroupEnum::Yellow,
+        SpaceGroupEnum::Green,
+        SpaceGroupEnum::DarkBlue,
+        SpaceGroupEnum::Railroads,
+        SpaceGroupEnum::Utilities,
+        SpaceGroupEnum::Special
+    ];
+
+    pub fn get_group_name(&self) -> &'static str {
+        match self {
+            SpaceGroupEnum::Brown => "Brown",
+            SpaceGroupEnum::LightBlue => "Light Blue",
+            SpaceGroupEnum::Pink => "Pink",
+            SpaceGroupEnum::Orange => "Orange",
+            SpaceGroupEnum::Red => "Red",
+            SpaceGroupEnum::Yellow => "Yellow",
+            SpaceGroupEnum::Green => "Green",
+            SpaceGroupEnum::DarkBlue => "Dark Blue",
+            SpaceGroupEnum::Railroads => "Railroads",
+            SpaceGroupEnum::Utilities => "Utilities",
+            SpaceGroupEnum::Special => "Special"
+        }
+    }
+
+    pub fn from_group_name(name: &str) -> Option<Self> {
+        SpaceGroupEnum::ALL.into_iter().find(|group| group.get_group_name() == name)
+    }
+
+    //Applies the group's display color to a space (or group) name
+    pub fn colorize(&self, text: &str) -> ColoredString {
+        match self {
+            SpaceGroupEnum::Brown => text.truecolor(138, 43, 226).bold(),
+            SpaceGroupEnum::LightBlue => text.truecolor(135, 206, 250).bold(),
+            SpaceGroupEnum::Pink => text.bright_magenta().bold(),
+            SpaceGroupEnum::Orange => text.truecolor(255, 140, 0).bold(),
+            SpaceGroupEnum::Red => text.red().bold(),
+            SpaceGroupEnum::Yellow => text.yellow().bold(),
+            SpaceGroupEnum::Green => text.truecolor(34, 139, 34).bold(),
+            SpaceGroupEnum::DarkBlue => text.blue().bold(),
+            SpaceGroupEnum::Utilities => text.truecolor(152, 251, 152).bold(),
+            SpaceGroupEnum::Railroads | SpaceGroupEnum::Special => text.white().bold()
+        }
+    }
 }
 
 
@@ -17,19 +93,49 @@ pub enum SpaceActionEnum {
 
 pub trait BoardSpace {
     fn get_space_name(&self) -> &ColoredString;
+    fn get_space_group(&self) -> SpaceGroupEnum;
     fn get_landed_count(&self) -> u32;
-    fn increment_landed(&mut self, player: &Player) -> SpaceActionEnum;
+    //What happens to a player landing here, without counting it as a landing
+    fn get_space_action(&self) -> SpaceActionEnum;
+    fn increment_landed(&mut self) -> SpaceActionEnum;
+
+    //Only streets have a purchase price and rent schedule, every other space has nothing to report
+    fn get_street_details(&self) -> Option<&StreetDetails> {
+        None
+    }
+
+    //The space players are sent to, as opposed to the Go To Jail space that sends them
+    fn is_jail(&self) -> bool {
+        false
+    }
+
+    //Anything in a color group, a railroad or a utility can be bought
+    fn is_purchasable(&self) -> bool {
+        self.get_space_group() != SpaceGroupEnum::Special
+    }
+}
+
+
+
+//The official purchase price, house cost and rent schedule for a street
+#[derive(Clone, Copy, Debug)]
+pub struct StreetDetails {
+    pub price: u32,
+    pub house_cost: u32,
+    //Rent unimproved, then with 1 through 4 houses, then with a hotel
+    pub rents: [u32; 6]
 }
 
 
 
 struct BasicSpace {
     name : ColoredString,
+    group : SpaceGroupEnum,
     landed_count : u32
 }
 impl BasicSpace {
-    fn new(name: ColoredString) -> Self {
-        Self { name: name, landed_count: 0 }
+    fn new(name: &str, group: SpaceGroupEnum) -> Self {
+        Self { name: group.colorize(name), group, landed_count: 0 }
     }
 }
 impl BoardSpace for BasicSpace {
@@ -37,15 +143,67 @@ impl BoardSpace for BasicSpace {
         &self.name
     }
 
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        self.group
+    }
+
     fn get_landed_count(&self) -> u32 {
         self.landed_count
     }
 
-    fn increment_landed(&mut self, player: &Player) -> SpaceActionEnum {
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::NoAction
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
         self.landed_count += 1;
-        println!("{} is on {}", player.get_player_name(), self.name);
+        self.get_space_action()
+    }
+}
+
+
+struct StreetSpace {
+    name : ColoredString,
+    group : SpaceGroupEnum,
+    details : StreetDetails,
+    landed_count : u32
+}
+impl StreetSpace {
+    fn new(name: &str, group: SpaceGroupEnum, price: u32, house_cost: u32, rents: [u32; 6]) -> Self {
+        Self { name: group.colorize(name), group, details: StreetDetails { price, house_cost, rents }, landed_count: 0 }
+    }
+
+    fn in_brown(mut self) -> Self {
+        let (red, green, blue) = INTERNATIONAL_BROWN;
+        self.name = self.name.truecolor(red, green, blue);
+        self
+    }
+}
+impl BoardSpace for StreetSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        self.group
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
         SpaceActionEnum::NoAction
     }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+
+    fn get_street_details(&self) -> Option<&StreetDetails> {
+        Some(&self.details)
+    }
 }
 
 
@@ -63,15 +221,22 @@ impl BoardSpace for ChanceSpace {
         &self.name
     }
 
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
     fn get_landed_count(&self) -> u32 {
         self.landed_count
     }
 
-    fn increment_landed(&mut self, player: &Player) -> SpaceActionEnum {
-        self.landed_count += 1;
-        println!("{} is on {}", player.get_player_name(), self.name);
+    fn get_space_action(&self) -> SpaceActionEnum {
         SpaceActionEnum::DrawCard(CardDeckEnum::Chance)
     }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
 }
 
 
@@ -90,15 +255,202 @@ impl BoardSpace for CommunityChestSpace {
         &self.name
     }
 
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
     fn get_landed_count(&self) -> u32 {
         self.landed_count
     }
 
-    fn increment_landed(&mut self, player: &Player) -> SpaceActionEnum {
-        self.landed_count += 1;
-        println!("{} is on {}", player.get_player_name(), self.name);
+    fn get_space_action(&self) -> SpaceActionEnum {
         SpaceActionEnum::DrawCard(CardDeckEnum::CommunityChest)
     }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+}
+
+
+
+
+struct GoSpace {
+    name : ColoredString,
+    landed_count : u32
+}
+impl GoSpace {
+    fn new() -> Self {
+        Self { name: "Go".white().bold(), landed_count: 0 }
+    }
+}
+impl BoardSpace for GoSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::LandOnGo
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+}
+
+
+
+
+struct TaxSpace {
+    name : ColoredString,
+    amount : u32,
+    landed_count : u32
+}
+impl TaxSpace {
+    fn new(name: &str, amount: u32) -> Self {
+        Self { name: name.white().bold(), amount, landed_count: 0 }
+    }
+}
+impl BoardSpace for TaxSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::PayTax(self.amount)
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+}
+
+
+
+
+struct FreeParkingSpace {
+    name : ColoredString,
+    landed_count : u32
+}
+impl FreeParkingSpace {
+    fn new() -> Self {
+        Self { name: "Free Parking".white().bold(), landed_count: 0 }
+    }
+}
+impl BoardSpace for FreeParkingSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::CollectFreeParking
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+}
+
+
+
+
+struct JailSpace {
+    name : ColoredString,
+    landed_count : u32
+}
+impl JailSpace {
+    fn new() -> Self {
+        Self { name: "Jail".white().bold(), landed_count: 0 }
+    }
+}
+impl BoardSpace for JailSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::NoAction
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+
+    fn is_jail(&self) -> bool {
+        true
+    }
+}
+
+
+
+
+struct BusTicketSpace {
+    name : ColoredString,
+    landed_count : u32
+}
+impl BusTicketSpace {
+    fn new(name: &str) -> Self {
+        Self { name: name.white().bold(), landed_count: 0 }
+    }
+}
+impl BoardSpace for BusTicketSpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::TakeBusTicket
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
 }
 
 
@@ -118,14 +470,43 @@ impl BoardSpace for GoToJailSpace {
         &self.name
     }
 
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        SpaceGroupEnum::Special
+    }
+
     fn get_landed_count(&self) -> u32 {
         self.landed_count
     }
 
-    fn increment_landed(&mut self, player: &Player) -> SpaceActionEnum {
+    fn get_space_action(&self) -> SpaceActionEnum {
+        SpaceActionEnum::SendToJail
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
         self.landed_count += 1;
-        println!("{} is on {}", player.get_player_name(), self.name);
-        SpaceActionEnum::MovePlayer(10)
+        self.get_space_action()
+    }
+}
+
+
+
+
+//Positions are a u8, so no board can have more spaces than this
+pub const MAX_BOARD_SPACES: usize = u8::MAX as usize + 1;
+
+//Checks a board can be played on and finds its jail, where Go To Jail, the Go to Jail cards and too many doubles all send players.
+//Nothing else is assumed about a board's size or layout
+pub fn get_jail_space(board: &[Box<dyn BoardSpace>]) -> Result<u8, String> {
+    if board.is_empty() || board.len() > MAX_BOARD_SPACES {
+        return Err(format!("Boards need between 1 and {} spaces, this one has {}", MAX_BOARD_SPACES, board.len()));
+    }
+
+    let mut jail_spaces = board.iter().enumerate().filter(|(_, space)| space.is_jail()).map(|(space_index, _)| space_index as u8);
+
+    match (jail_spaces.next(), jail_spaces.next()) {
+        (Some(jail_space), None) => Ok(jail_space),
+        (None, _) => Err(String::from("The board has no jail space")),
+        (Some(_), Some(_)) => Err(String::from("The board has more than one jail space"))
     }
 }
 
@@ -136,46 +517,243 @@ pub fn initialize_game_board() -> Vec::<Box<dyn BoardSpace>> {
     let mut space_defs = Vec::<Box<dyn BoardSpace>>::with_capacity(40);
 
 
-    space_defs.insert(0, Box::new(BasicSpace::new("Go".white().bold())));
-    space_defs.insert(1, Box::new(BasicSpace::new("Mediterranean Avenue".truecolor(138, 43, 226).bold())));
+    space_defs.insert(0, Box::new(GoSpace::new()));
+    space_defs.insert(1, Box::new(StreetSpace::new("Mediterranean Avenue", SpaceGroupEnum::Brown, 60, 50, [2, 10, 30, 90, 160, 250])));
     space_defs.insert(2, Box::new(CommunityChestSpace::new(2)));
-    space_defs.insert(3, Box::new(BasicSpace::new("Baltic Avenue".truecolor(138, 43, 226).bold())));
-    space_defs.insert(4, Box::new(BasicSpace::new("Income Tax".white().bold())));
-    space_defs.insert(5, Box::new(BasicSpace::new("Reading Railroad".white().bold())));
-    space_defs.insert(6, Box::new(BasicSpace::new("Oriental Avenue".truecolor(135, 206, 250).bold())));
+    space_defs.insert(3, Box::new(StreetSpace::new("Baltic Avenue", SpaceGroupEnum::Brown, 60, 50, [4, 20, 60, 180, 320, 450])));
+    space_defs.insert(4, Box::new(TaxSpace::new("Income Tax", 200)));
+    space_defs.insert(5, Box::new(BasicSpace::new("Reading Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(6, Box::new(StreetSpace::new("Oriental Avenue", SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
     space_defs.insert(7, Box::new(ChanceSpace::new(7)));
-    space_defs.insert(8, Box::new(BasicSpace::new("Vermont Avenue".truecolor(135, 206, 250).bold())));
-    space_defs.insert(9, Box::new(BasicSpace::new("Connecticut Avenue".truecolor(135, 206, 250).bold())));
-    space_defs.insert(10, Box::new(BasicSpace::new("Jail".white().bold())));
-    space_defs.insert(11, Box::new(BasicSpace::new("St. Charles Place".bright_magenta().bold())));
-    space_defs.insert(12, Box::new(BasicSpace::new("Electric Company".truecolor(152, 251, 152).bold())));
-    space_defs.insert(13, Box::new(BasicSpace::new("States Avenue".bright_magenta().bold())));
-    space_defs.insert(14, Box::new(BasicSpace::new("Virginia Avenue".bright_magenta().bold())));
-    space_defs.insert(15, Box::new(BasicSpace::new("Pennsylvania Railroad".white().bold())));
-    space_defs.insert(16, Box::new(BasicSpace::new("St. James Place".truecolor(255, 140, 0).bold())));
+    space_defs.insert(8, Box::new(StreetSpace::new("Vermont Avenue", SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(9, Box::new(StreetSpace::new("Connecticut Avenue", SpaceGroupEnum::LightBlue, 120, 50, [8, 40, 100, 300, 450, 600])));
+    space_defs.insert(10, Box::new(JailSpace::new()));
+    space_defs.insert(11, Box::new(StreetSpace::new("St. Charles Place", SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(12, Box::new(BasicSpace::new("Electric Company", SpaceGroupEnum::Utilities)));
+    space_defs.insert(13, Box::new(StreetSpace::new("States Avenue", SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(14, Box::new(StreetSpace::new("Virginia Avenue", SpaceGroupEnum::Pink, 160, 100, [12, 60, 180, 500, 700, 900])));
+    space_defs.insert(15, Box::new(BasicSpace::new("Pennsylvania Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(16, Box::new(StreetSpace::new("St. James Place", SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
     space_defs.insert(17, Box::new(CommunityChestSpace::new(17)));
-    space_defs.insert(18, Box::new(BasicSpace::new("Tennessee Avenue".truecolor(255, 140, 0).bold())));
-    space_defs.insert(19, Box::new(BasicSpace::new("New York Avenue".truecolor(255, 140, 0).bold())));
-    space_defs.insert(20, Box::new(BasicSpace::new("Free Parking".white().bold())));
-    space_defs.insert(21, Box::new(BasicSpace::new("Kentucky Avenue".red().bold())));
+    space_defs.insert(18, Box::new(StreetSpace::new("Tennessee Avenue", SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
+    space_defs.insert(19, Box::new(StreetSpace::new("New York Avenue", SpaceGroupEnum::Orange, 200, 100, [16, 80, 220, 600, 800, 1000])));
+    space_defs.insert(20, Box::new(FreeParkingSpace::new()));
+    space_defs.insert(21, Box::new(StreetSpace::new("Kentucky Avenue", SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
     space_defs.insert(22, Box::new(ChanceSpace::new(22)));
-    space_defs.insert(23, Box::new(BasicSpace::new("Indiana Avenue".red().bold())));
-    space_defs.insert(24, Box::new(BasicSpace::new("Illinois Avenue".red().bold())));
-    space_defs.insert(25, Box::new(BasicSpace::new("B & O Railroad".white().bold())));
-    space_defs.insert(26, Box::new(BasicSpace::new("Atlantic Avenue".yellow().bold())));
-    space_defs.insert(27, Box::new(BasicSpace::new("Ventnor Avenue".yellow().bold())));
-    space_defs.insert(28, Box::new(BasicSpace::new("Waterworks".truecolor(152, 251, 152).bold())));
-    space_defs.insert(29, Box::new(BasicSpace::new("Marvin Gardens".yellow().bold())));
+    space_defs.insert(23, Box::new(StreetSpace::new("Indiana Avenue", SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
+    space_defs.insert(24, Box::new(StreetSpace::new("Illinois Avenue", SpaceGroupEnum::Red, 240, 150, [20, 100, 300, 750, 925, 1100])));
+    space_defs.insert(25, Box::new(BasicSpace::new("B & O Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(26, Box::new(StreetSpace::new("Atlantic Avenue", SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(27, Box::new(StreetSpace::new("Ventnor Avenue", SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(28, Box::new(BasicSpace::new("Waterworks", SpaceGroupEnum::Utilities)));
+    space_defs.insert(29, Box::new(StreetSpace::new("Marvin Gardens", SpaceGroupEnum::Yellow, 280, 150, [24, 120, 360, 850, 1025, 1200])));
     space_defs.insert(30, Box::new(GoToJailSpace::new()));
-    space_defs.insert(31, Box::new(BasicSpace::new("Pacific Avenue".truecolor(34, 139, 34).bold())));
-    space_defs.insert(32, Box::new(BasicSpace::new("North Carolina Avenue".truecolor(34, 139, 34).bold())));
+    space_defs.insert(31, Box::new(StreetSpace::new("Pacific Avenue", SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(32, Box::new(StreetSpace::new("North Carolina Avenue", SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
     space_defs.insert(33, Box::new(CommunityChestSpace::new(33)));
-    space_defs.insert(34, Box::new(BasicSpace::new("Pennsylvania Avenue".truecolor(34, 139, 34).bold())));
-    space_defs.insert(35, Box::new(BasicSpace::new("Short Line Railroad".white().bold())));
+    space_defs.insert(34, Box::new(StreetSpace::new("Pennsylvania Avenue", SpaceGroupEnum::Green, 320, 200, [28, 150, 450, 1000, 1200, 1400])));
+    space_defs.insert(35, Box::new(BasicSpace::new("Short Line Railroad", SpaceGroupEnum::Railroads)));
     space_defs.insert(36, Box::new(ChanceSpace::new(36)));
-    space_defs.insert(37, Box::new(BasicSpace::new("Park Place".blue().bold())));
-    space_defs.insert(38, Box::new(BasicSpace::new("Luxury Tax".white().bold())));
-    space_defs.insert(39, Box::new(BasicSpace::new("Boardwalk".blue().bold())));
+    space_defs.insert(37, Box::new(StreetSpace::new("Park Place", SpaceGroupEnum::DarkBlue, 350, 200, [35, 175, 500, 1100, 1300, 1500])));
+    space_defs.insert(38, Box::new(TaxSpace::new("Luxury Tax", 100)));
+    space_defs.insert(39, Box::new(StreetSpace::new("Boardwalk", SpaceGroupEnum::DarkBlue, 400, 200, [50, 200, 600, 1400, 1700, 2000])));
 
     space_defs
-}
\ No newline at end of file
+}
+
+
+
+//The 52 space board from the 2006 US Mega Edition.  Each street only found on this board costs and rents the same as the
+//street priced like it in its group, apart from Arctic Avenue at the top of the Browns
+pub fn initialize_mega_board() -> Vec::<Box<dyn BoardSpace>> {
+    let mut space_defs = Vec::<Box<dyn BoardSpace>>::with_capacity(52);
+
+
+    space_defs.insert(0, Box::new(GoSpace::new()));
+    space_defs.insert(1, Box::new(StreetSpace::new("Mediterranean Avenue", SpaceGroupEnum::Brown, 60, 50, [2, 10, 30, 90, 160, 250])));
+    space_defs.insert(2, Box::new(CommunityChestSpace::new(2)));
+    space_defs.insert(3, Box::new(StreetSpace::new("Baltic Avenue", SpaceGroupEnum::Brown, 60, 50, [4, 20, 60, 180, 320, 450])));
+    space_defs.insert(4, Box::new(StreetSpace::new("Arctic Avenue", SpaceGroupEnum::Brown, 80, 50, [5, 30, 80, 240, 360, 500])));
+    space_defs.insert(5, Box::new(TaxSpace::new("Income Tax", 200)));
+    space_defs.insert(6, Box::new(BasicSpace::new("Reading Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(7, Box::new(StreetSpace::new("Massachusetts Avenue", SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(8, Box::new(StreetSpace::new("Oriental Avenue", SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(9, Box::new(ChanceSpace::new(9)));
+    space_defs.insert(10, Box::new(BasicSpace::new("Gas Company", SpaceGroupEnum::Utilities)));
+    space_defs.insert(11, Box::new(StreetSpace::new("Vermont Avenue", SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(12, Box::new(StreetSpace::new("Connecticut Avenue", SpaceGroupEnum::LightBlue, 120, 50, [8, 40, 100, 300, 450, 600])));
+    space_defs.insert(13, Box::new(JailSpace::new()));
+    space_defs.insert(14, Box::new(BasicSpace::new("Auction", SpaceGroupEnum::Special)));
+    space_defs.insert(15, Box::new(StreetSpace::new("Maryland Avenue", SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(16, Box::new(StreetSpace::new("St. Charles Place", SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(17, Box::new(BasicSpace::new("Electric Company", SpaceGroupEnum::Utilities)));
+    space_defs.insert(18, Box::new(StreetSpace::new("States Avenue", SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(19, Box::new(StreetSpace::new("Virginia Avenue", SpaceGroupEnum::Pink, 160, 100, [12, 60, 180, 500, 700, 900])));
+    space_defs.insert(20, Box::new(BasicSpace::new("Pennsylvania Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(21, Box::new(StreetSpace::new("St. James Place", SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
+    space_defs.insert(22, Box::new(CommunityChestSpace::new(22)));
+    space_defs.insert(23, Box::new(StreetSpace::new("Tennessee Avenue", SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
+    space_defs.insert(24, Box::new(StreetSpace::new("New York Avenue", SpaceGroupEnum::Orange, 200, 100, [16, 80, 220, 600, 800, 1000])));
+    space_defs.insert(25, Box::new(StreetSpace::new("New Jersey Avenue", SpaceGroupEnum::Orange, 200, 100, [16, 80, 220, 600, 800, 1000])));
+    space_defs.insert(26, Box::new(FreeParkingSpace::new()));
+    space_defs.insert(27, Box::new(StreetSpace::new("Kentucky Avenue", SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
+    space_defs.insert(28, Box::new(ChanceSpace::new(28)));
+    space_defs.insert(29, Box::new(StreetSpace::new("Indiana Avenue", SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
+    space_defs.insert(30, Box::new(StreetSpace::new("Illinois Avenue", SpaceGroupEnum::Red, 240, 150, [20, 100, 300, 750, 925, 1100])));
+    space_defs.insert(31, Box::new(StreetSpace::new("Michigan Avenue", SpaceGroupEnum::Red, 240, 150, [20, 100, 300, 750, 925, 1100])));
+    space_defs.insert(32, Box::new(BusTicketSpace::new("Bus Ticket")));
+    space_defs.insert(33, Box::new(BasicSpace::new("B & O Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(34, Box::new(StreetSpace::new("Atlantic Avenue", SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(35, Box::new(StreetSpace::new("Ventnor Avenue", SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(36, Box::new(BasicSpace::new("Waterworks", SpaceGroupEnum::Utilities)));
+    space_defs.insert(37, Box::new(StreetSpace::new("Marvin Gardens", SpaceGroupEnum::Yellow, 280, 150, [24, 120, 360, 850, 1025, 1200])));
+    space_defs.insert(38, Box::new(StreetSpace::new("California Avenue", SpaceGroupEnum::Yellow, 280, 150, [24, 120, 360, 850, 1025, 1200])));
+    space_defs.insert(39, Box::new(GoToJailSpace::new()));
+    space_defs.insert(40, Box::new(StreetSpace::new("Pacific Avenue", SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(41, Box::new(StreetSpace::new("South Carolina Avenue", SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(42, Box::new(StreetSpace::new("North Carolina Avenue", SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(43, Box::new(CommunityChestSpace::new(43)));
+    space_defs.insert(44, Box::new(StreetSpace::new("Pennsylvania Avenue", SpaceGroupEnum::Green, 320, 200, [28, 150, 450, 1000, 1200, 1400])));
+    space_defs.insert(45, Box::new(BasicSpace::new("Short Line Railroad", SpaceGroupEnum::Railroads)));
+    space_defs.insert(46, Box::new(ChanceSpace::new(46)));
+    //Birthday Gift offers $100 or a bus ticket, simulated players always take the ticket
+    space_defs.insert(47, Box::new(BusTicketSpace::new("Birthday Gift")));
+    space_defs.insert(48, Box::new(StreetSpace::new("Florida Avenue", SpaceGroupEnum::DarkBlue, 350, 200, [35, 175, 500, 1100, 1300, 1500])));
+    space_defs.insert(49, Box::new(StreetSpace::new("Park Place", SpaceGroupEnum::DarkBlue, 350, 200, [35, 175, 500, 1100, 1300, 1500])));
+    space_defs.insert(50, Box::new(TaxSpace::new("Luxury Tax", 100)));
+    space_defs.insert(51, Box::new(StreetSpace::new("Boardwalk", SpaceGroupEnum::DarkBlue, 400, 200, [50, 200, 600, 1400, 1700, 2000])));
+
+    space_defs
+}
+
+
+
+//The space names that differ between the 40 space editions outside the US, in board order.  Everything else (prices, rents,
+//taxes, where the corners, cards and stations sit) is the same as the classic board
+struct InternationalBoardNames {
+    streets: [&'static str; 22],
+    stations: [&'static str; 4],
+    utilities: [&'static str; 2],
+    taxes: [&'static str; 2]
+}
+
+const UK_BOARD_NAMES: InternationalBoardNames = InternationalBoardNames {
+    streets: [
+        "Old Kent Road", "Whitechapel Road",
+        "The Angel Islington", "Euston Road", "Pentonville Road",
+        "Pall Mall", "Whitehall", "Northumberland Avenue",
+        "Bow Street", "Marlborough Street", "Vine Street",
+        "Strand", "Fleet Street", "Trafalgar Square",
+        "Leicester Square", "Coventry Street", "Piccadilly",
+        "Regent Street", "Oxford Street", "Bond Street",
+        "Park Lane", "Mayfair"
+    ],
+    stations: ["King's Cross Station", "Marylebone Station", "Fenchurch St. Station", "Liverpool Street Station"],
+    utilities: ["Electric Company", "Water Works"],
+    taxes: ["Income Tax", "Super Tax"]
+};
+
+const FRENCH_BOARD_NAMES: InternationalBoardNames = InternationalBoardNames {
+    streets: [
+        "Boulevard de Belleville", "Rue Lecourbe",
+        "Rue de Vaugirard", "Rue de Courcelles", "Avenue de la République",
+        "Boulevard de la Villette", "Avenue de Neuilly", "Rue de Paradis",
+        "Avenue Mozart", "Boulevard Saint-Michel", "Place Pigalle",
+        "Avenue Matignon", "Boulevard Malesherbes", "Avenue Henri-Martin",
+        "Faubourg Saint-Honoré", "Place de la Bourse", "Rue La Fayette",
+        "Avenue de Breteuil", "Avenue Foch", "Boulevard des Capucines",
+        "Avenue des Champs-Élysées", "Rue de la Paix"
+    ],
+    stations: ["Gare Montparnasse", "Gare de Lyon", "Gare du Nord", "Gare Saint-Lazare"],
+    utilities: ["Compagnie de distribution d'électricité", "Compagnie de distribution des eaux"],
+    taxes: ["Impôts sur le revenu", "Taxe de luxe"]
+};
+
+const GERMAN_BOARD_NAMES: InternationalBoardNames = InternationalBoardNames {
+    streets: [
+        "Badstraße", "Turmstraße",
+        "Chausseestraße", "Elisenstraße", "Poststraße",
+        "Seestraße", "Hafenstraße", "Neue Straße",
+        "Münchner Straße", "Wiener Straße", "Berliner Straße",
+        "Theaterstraße", "Museumstraße", "Opernplatz",
+        "Lessingstraße", "Schillerstraße", "Goethestraße",
+        "Rathausplatz", "Hauptstraße", "Bahnhofstraße",
+        "Parkstraße", "Schlossallee"
+    ],
+    stations: ["Südbahnhof", "Westbahnhof", "Nordbahnhof", "Hauptbahnhof"],
+    utilities: ["Elektrizitätswerk", "Wasserwerk"],
+    taxes: ["Einkommensteuer", "Zusatzsteuer"]
+};
+
+//The first color group is purple on the classic US board and brown everywhere else
+const INTERNATIONAL_BROWN: (u8, u8, u8) = (150, 75, 0);
+
+
+//London board of the UK edition
+pub fn initialize_uk_board() -> Vec::<Box<dyn BoardSpace>> {
+    initialize_international_board(&UK_BOARD_NAMES)
+}
+
+//Paris board of the French edition
+pub fn initialize_french_board() -> Vec::<Box<dyn BoardSpace>> {
+    initialize_international_board(&FRENCH_BOARD_NAMES)
+}
+
+//Board of the German edition, Badstraße to Schlossallee
+pub fn initialize_german_board() -> Vec::<Box<dyn BoardSpace>> {
+    initialize_international_board(&GERMAN_BOARD_NAMES)
+}
+
+
+fn initialize_international_board(names: &InternationalBoardNames) -> Vec::<Box<dyn BoardSpace>> {
+    let [brown1, brown2, light_blue1, light_blue2, light_blue3, pink1, pink2, pink3, orange1, orange2, orange3, red1, red2, red3, yellow1, yellow2, yellow3, green1, green2, green3, dark_blue1, dark_blue2] = names.streets;
+    let [station1, station2, station3, station4] = names.stations;
+    let [electric, water] = names.utilities;
+    let [income_tax, luxury_tax] = names.taxes;
+
+    let mut space_defs = Vec::<Box<dyn BoardSpace>>::with_capacity(40);
+
+
+    space_defs.insert(0, Box::new(GoSpace::new()));
+    space_defs.insert(1, Box::new(StreetSpace::new(brown1, SpaceGroupEnum::Brown, 60, 50, [2, 10, 30, 90, 160, 250]).in_brown()));
+    space_defs.insert(2, Box::new(CommunityChestSpace::new(2)));
+    space_defs.insert(3, Box::new(StreetSpace::new(brown2, SpaceGroupEnum::Brown, 60, 50, [4, 20, 60, 180, 320, 450]).in_brown()));
+    space_defs.insert(4, Box::new(TaxSpace::new(income_tax, 200)));
+    space_defs.insert(5, Box::new(BasicSpace::new(station1, SpaceGroupEnum::Railroads)));
+    space_defs.insert(6, Box::new(StreetSpace::new(light_blue1, SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(7, Box::new(ChanceSpace::new(7)));
+    space_defs.insert(8, Box::new(StreetSpace::new(light_blue2, SpaceGroupEnum::LightBlue, 100, 50, [6, 30, 90, 270, 400, 550])));
+    space_defs.insert(9, Box::new(StreetSpace::new(light_blue3, SpaceGroupEnum::LightBlue, 120, 50, [8, 40, 100, 300, 450, 600])));
+    space_defs.insert(10, Box::new(JailSpace::new()));
+    space_defs.insert(11, Box::new(StreetSpace::new(pink1, SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(12, Box::new(BasicSpace::new(electric, SpaceGroupEnum::Utilities)));
+    space_defs.insert(13, Box::new(StreetSpace::new(pink2, SpaceGroupEnum::Pink, 140, 100, [10, 50, 150, 450, 625, 750])));
+    space_defs.insert(14, Box::new(StreetSpace::new(pink3, SpaceGroupEnum::Pink, 160, 100, [12, 60, 180, 500, 700, 900])));
+    space_defs.insert(15, Box::new(BasicSpace::new(station2, SpaceGroupEnum::Railroads)));
+    space_defs.insert(16, Box::new(StreetSpace::new(orange1, SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
+    space_defs.insert(17, Box::new(CommunityChestSpace::new(17)));
+    space_defs.insert(18, Box::new(StreetSpace::new(orange2, SpaceGroupEnum::Orange, 180, 100, [14, 70, 200, 550, 750, 950])));
+    space_defs.insert(19, Box::new(StreetSpace::new(orange3, SpaceGroupEnum::Orange, 200, 100, [16, 80, 220, 600, 800, 1000])));
+    space_defs.insert(20, Box::new(FreeParkingSpace::new()));
+    space_defs.insert(21, Box::new(StreetSpace::new(red1, SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
+    space_defs.insert(22, Box::new(ChanceSpace::new(22)));
+    space_defs.insert(23, Box::new(StreetSpace::new(red2, SpaceGroupEnum::Red, 220, 150, [18, 90, 250, 700, 875, 1050])));
+    space_defs.insert(24, Box::new(StreetSpace::new(red3, SpaceGroupEnum::Red, 240, 150, [20, 100, 300, 750, 925, 1100])));
+    space_defs.insert(25, Box::new(BasicSpace::new(station3, SpaceGroupEnum::Railroads)));
+    space_defs.insert(26, Box::new(StreetSpace::new(yellow1, SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(27, Box::new(StreetSpace::new(yellow2, SpaceGroupEnum::Yellow, 260, 150, [22, 110, 330, 800, 975, 1150])));
+    space_defs.insert(28, Box::new(BasicSpace::new(water, SpaceGroupEnum::Utilities)));
+    space_defs.insert(29, Box::new(StreetSpace::new(yellow3, SpaceGroupEnum::Yellow, 280, 150, [24, 120, 360, 850, 1025, 1200])));
+    space_defs.insert(30, Box::new(GoToJailSpace::new()));
+    space_defs.insert(31, Box::new(StreetSpace::new(green1, SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(32, Box::new(StreetSpace::new(green2, SpaceGroupEnum::Green, 300, 200, [26, 130, 390, 900, 1100, 1275])));
+    space_defs.insert(33, Box::new(CommunityChestSpace::new(33)));
+    space_defs.insert(34, Box::new(StreetSpace::new(green3, SpaceGroupEnum::Green, 320, 200, [28, 150, 450, 1000, 1200, 1400])));
+    space_defs.insert(35, Box::new(BasicSpace::new(station4, SpaceGroupEnum::Railroads)));
+    space_defs.insert(36, Box::new(ChanceSpace::new(36)));
+    space_defs.insert(37, Box::new(StreetSpace::new(dark_blue1, SpaceGroupEnum::DarkBlue, 350, 200, [35, 175, 500, 1100, 1300, 1500])));
+    space_defs.insert(38, Box::new(TaxSpace::new(luxury_tax, 100)));
+    space_defs.insert(39, Box::new(StreetSpace::new(dark_blue2, SpaceGroupEnum::DarkBlue, 400, 200, [50, 200, 600, 1400, 1700, 2000])));
+
+    space_defs
+}
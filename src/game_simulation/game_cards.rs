@@ -1,23 +1,109 @@
 use std::collections::VecDeque;
 
-use rand::{prelude::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng};
+
+use crate::game_simulation::game_board::{BoardSpace, CardDeckEnum, SpaceActionEnum, SpaceGroupEnum};
+
+//Where a drawn card sends the player.  Cards name what they do rather than where that is, and get resolved against whichever board
+//they're played on, so the same deck works on the classic, Mega and custom boards
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CardMoveEnum {
+    StayPut,
+    //Advance to the space with this name.  Swapped for AdvanceToSpace when the deck is set up for a board
+    AdvanceTo(&'static str),
+    //Back to the space with this name, without passing Go on the way.  Swapped for GoBackToSpace when the deck is set up for a board
+    GoBackTo(&'static str),
+    AdvanceToSpace(u8),
+    GoBackToSpace(u8),
+    AdvanceToGo,
+    //The next space in this group going forward, wrapping round past Go if need be
+    AdvanceToNearest(SpaceGroupEnum),
+    //Forward this many spaces, or back if negative, wrapping round the board either way
+    MoveBy(i8),
+    GoToJail
+}
+impl CardMoveEnum {
+    //The same move with any space name swapped for where that space is on this board, so nothing compares names once a game is
+    //under way.  A name the board doesn't have is an error rather than a card that quietly never moves anyone
+    pub fn for_board(&self, board: &[Box<dyn BoardSpace>]) -> Result<Self, String> {
+        let find_space = |space_name: &str| {
+            board.iter()
+                .position(|space| &**space.get_space_name() == space_name)
+                .map(|space| space as u8)
+                .ok_or(format!("The board has no space called '{}'", space_name))
+        };
+
+        match self {
+            CardMoveEnum::AdvanceTo(space_name) => Ok(CardMoveEnum::AdvanceToSpace(find_space(space_name)?)),
+            CardMoveEnum::GoBackTo(space_name) => Ok(CardMoveEnum::GoBackToSpace(find_space(space_name)?)),
+            _ => Ok(*self)
+        }
+    }
+
+
+    //The space a player who drew this card on from_space moves to, None if they stay put.  Cards still naming a space are looked up
+    //on the spot, and stay put if the board doesn't have it, so set decks up with CardDecks::for_board before playing
+    pub fn resolve(&self, board: &[Box<dyn BoardSpace>], from_space: u8) -> Option<u8> {
+        let board_len = board.len() as i64;
+        let find_forward = |is_target: &dyn Fn(&dyn BoardSpace) -> bool| {
+            (1..=board.len())
+                .map(|distance| (from_space as usize + distance) % board.len())
+                .find(|space| is_target(board[*space].as_ref()))
+                .map(|space| space as u8)
+        };
+
+        match self {
+            CardMoveEnum::StayPut => None,
+            CardMoveEnum::AdvanceTo(_) | CardMoveEnum::GoBackTo(_) => self.for_board(board).ok()?.resolve(board, from_space),
+            CardMoveEnum::AdvanceToSpace(space) | CardMoveEnum::GoBackToSpace(space) => Some(*space),
+            CardMoveEnum::AdvanceToGo => find_forward(&|space| matches!(space.get_space_action(), SpaceActionEnum::LandOnGo)),
+            CardMoveEnum::AdvanceToNearest(group) => find_forward(&|space| space.get_space_group() == *group),
+            CardMoveEnum::MoveBy(distance) => Some((from_space as i64 + *distance as i64).rem_euclid(board_len) as u8),
+            CardMoveEnum::GoToJail => find_forward(&|space| space.is_jail())
+        }
+    }
+
+
+    //Whether moving from from_space to to_space on this card goes round past Go.  Only cards that send the player forward can,
+    //going back or straight to jail never does
+    pub fn passes_go(&self, from_space: u8, to_space: u8) -> bool {
+        match self {
+            CardMoveEnum::AdvanceTo(_) | CardMoveEnum::AdvanceToSpace(_) | CardMoveEnum::AdvanceToGo | CardMoveEnum::AdvanceToNearest(_) => to_space <= from_space,
+            CardMoveEnum::MoveBy(distance) => *distance > 0 && to_space < from_space,
+            CardMoveEnum::StayPut | CardMoveEnum::GoBackTo(_) | CardMoveEnum::GoBackToSpace(_) | CardMoveEnum::GoToJail => false
+        }
+    }
+}
+
 
-//Represents the move action to be taken when a Chance or community chest card is drawn
-type DrawnCardAction = fn(u8) -> Option<u8>;
 
-#[derive(PartialEq)]
 pub struct GameActionCard
 {
     text: String,
-    move_action: DrawnCardAction
+    card_move: CardMoveEnum,
+    //Amount paid to the bank (doctor's fees, fines...).  Only matters for house rules like the Free Parking jackpot
+    fee: u32
 }
 impl GameActionCard {
+    pub fn new(text: &str, card_move: CardMoveEnum) -> Self {
+        GameActionCard { text: String::from(text), card_move, fee: 0 }
+    }
+
+    pub fn with_fee(mut self, fee: u32) -> Self {
+        self.fee = fee;
+        self
+    }
+
     pub fn get_card_text(&self) -> &str {
         &self.text
     }
 
-    pub fn get_move_action(&self) -> &DrawnCardAction {
-        &self.move_action
+    pub fn get_card_move(&self) -> CardMoveEnum {
+        self.card_move
+    }
+
+    pub fn get_fee(&self) -> u32 {
+        self.fee
     }
 }
 
@@ -35,6 +121,64 @@ impl CardDecks {
         }
     }
 
+    //The US decks as printed before the 2008 update: only one nearest Railroad card, Grand Opera Night instead of the birthday
+    //card, and a few different amounts
+    pub fn pre_2008() -> Self {
+        CardDecks {
+            chance_deck: init_pre_2008_chance(),
+            community_chest_deck: init_pre_2008_community_chest()
+        }
+    }
+
+    //The UK decks: no nearest Railroad or Utility cards, a trip to Marylebone Station instead of Reading Railroad, and
+    //Community Chest can send players back to Old Kent Road
+    pub fn uk() -> Self {
+        CardDecks {
+            chance_deck: init_uk_chance(),
+            community_chest_deck: init_uk_community_chest()
+        }
+    }
+
+    //Same cards as the UK decks, translated for the French board
+    pub fn french() -> Self {
+        CardDecks {
+            chance_deck: init_french_chance(),
+            community_chest_deck: init_french_community_chest()
+        }
+    }
+
+    //Same cards as the UK decks, translated for the German board
+    pub fn german() -> Self {
+        CardDecks {
+            chance_deck: init_german_chance(),
+            community_chest_deck: init_german_community_chest()
+        }
+    }
+
+    //Swaps in custom decks (a different edition, or a stacked deck for testing)
+    pub fn from_decks(chance_deck: GameActionCardDeck, community_chest_deck: GameActionCardDeck) -> Self {
+        CardDecks { chance_deck, community_chest_deck }
+    }
+
+    //Points every card naming a space at where it is on this board.  Fails if a card names a space the board doesn't have, unless
+    //the board never draws from that card's deck
+    pub fn for_board(&mut self, board: &[Box<dyn BoardSpace>]) -> Result<(), String> {
+        for deck in [CardDeckEnum::Chance, CardDeckEnum::CommunityChest] {
+            if board.iter().any(|space| matches!(space.get_space_action(), SpaceActionEnum::DrawCard(space_deck) if space_deck == deck)) {
+                self.get_deck(deck).for_board(board)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_deck(&mut self, deck: CardDeckEnum) -> &mut GameActionCardDeck {
+        match deck {
+            CardDeckEnum::Chance => &mut self.chance_deck,
+            CardDeckEnum::CommunityChest => &mut self.community_chest_deck
+        }
+    }
+
     pub fn get_chance_deck(&mut self) -> &mut GameActionCardDeck {
         &mut self.chance_deck
     }
@@ -44,44 +188,69 @@ impl CardDecks {
     }
 }
 
+impl Default for CardDecks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 
 
 
 pub struct GameActionCardDeck
 {
-    deck: VecDeque<GameActionCard>,
-    dealt: Vec<GameActionCard>
+    cards: Vec<GameActionCard>,
+    //Both hold indexes into cards, which never changes order.  That keeps each card's index stable for logs and replays
+    deck: VecDeque<usize>,
+    dealt: Vec<usize>
 }
 
 impl GameActionCardDeck {
-    fn new(cards: Vec::<GameActionCard>) -> Self {
+    pub fn new(cards: Vec::<GameActionCard>) -> Self {
         //Initialize cards into the Dealt vec, deck will shuffle on first use
-        GameActionCardDeck { deck: VecDeque::<GameActionCard>::with_capacity(16), dealt: cards }
+        let dealt = (0..cards.len()).collect();
+
+        GameActionCardDeck { cards, deck: VecDeque::<usize>::with_capacity(16), dealt }
     }
 
 
-    pub fn draw_card(&mut self, rng: &mut ThreadRng) -> Option<&GameActionCard> {
+    //Draws the next card, returning its index in the deck as it was originally defined
+    pub fn draw_card(&mut self, rng: &mut StdRng) -> Option<usize> {
         //Shuffle if needed
-        if self.deck.len() == 0 {
-            //let mut rng = thread_rng();
-
-            while self.dealt.len() > 0 {
+        if self.deck.is_empty() {
+            while !self.dealt.is_empty() {
                 self.deck.push_back(self.dealt.swap_remove(rng.gen_range(0..self.dealt.len())));
             }
         }
 
         //Draw
-        let next_card = self.deck.pop_front();
-        
-        if next_card == None {
-            return None;
+        let next_card = self.deck.pop_front()?;
+
+        self.dealt.push(next_card);
+
+        Some(next_card)
+    }
+
+    //Puts these cards on top of the deck, to be drawn in this order.  The rest get shuffled in underneath once they run out
+    pub fn stack(&mut self, card_order: &[usize]) {
+        self.deck = card_order.iter().copied().collect();
+        self.dealt = (0..self.cards.len()).filter(|card_index| !card_order.contains(card_index)).collect();
+    }
+
+    pub fn get_card(&self, card_index: usize) -> Option<&GameActionCard> {
+        self.cards.get(card_index)
+    }
+
+    pub fn for_board(&mut self, board: &[Box<dyn BoardSpace>]) -> Result<(), String> {
+        for card in self.cards.iter_mut() {
+            card.card_move = card.card_move.for_board(board).map_err(|e| format!("The card '{}' can't be played: {}", card.text, e))?;
         }
 
+        Ok(())
+    }
 
-        self.dealt.push(next_card.unwrap());
-    
-        //This should return the card we just pushed.  (assuming single-threaded)
-        self.dealt.last()
+    pub fn get_card_count(&self) -> usize {
+        self.cards.len()
     }
 }
 
@@ -89,72 +258,252 @@ impl GameActionCardDeck {
 
 
 fn init_community_chest() -> GameActionCardDeck {
-    let mut comm_chest_cards: Vec<_> = Vec::<GameActionCard>::with_capacity(16);
-    comm_chest_cards.push(GameActionCard { text: String::from("Advance to GO"), move_action: |_| Some(0) });
-    comm_chest_cards.push(GameActionCard { text: String::from("Bank error in your favor. Collect $200"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Doctor’s fee. Pay $50"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("From sale of stock you get $50"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Get Out of Jail Free"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Go to Jail. Go directly to jail, do not pass Go, do not collect $200"), move_action: |_| Some(10) });
-    comm_chest_cards.push(GameActionCard { text: String::from("Holiday fund matures. Receive $100"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Income tax refund. Collect $20"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("It is your birthday. Collect $10 from every player"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Life insurance matures. Collect $100"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Pay hospital fees of $100"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Pay school fees of $50"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("Receive $25 consultancy fee"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("You are assessed for street repair. $40 per house. $115 per hotel"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("You have won second prize in a beauty contest. Collect $10"), move_action: |_| None });
-    comm_chest_cards.push(GameActionCard { text: String::from("You inherit $100"), move_action: |_| None });
+    let comm_chest_cards = vec![
+        GameActionCard::new("Advance to GO", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Bank error in your favor. Collect $200", CardMoveEnum::StayPut),
+        GameActionCard::new("Doctor’s fee. Pay $50", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("From sale of stock you get $50", CardMoveEnum::StayPut),
+        GameActionCard::new("Get Out of Jail Free", CardMoveEnum::StayPut),
+        GameActionCard::new("Go to Jail. Go directly to jail, do not pass Go, do not collect $200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Holiday fund matures. Receive $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Income tax refund. Collect $20", CardMoveEnum::StayPut),
+        GameActionCard::new("It is your birthday. Collect $10 from every player", CardMoveEnum::StayPut),
+        GameActionCard::new("Life insurance matures. Collect $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Pay hospital fees of $100", CardMoveEnum::StayPut).with_fee(100),
+        GameActionCard::new("Pay school fees of $50", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Receive $25 consultancy fee", CardMoveEnum::StayPut),
+        GameActionCard::new("You are assessed for street repair. $40 per house. $115 per hotel", CardMoveEnum::StayPut),
+        GameActionCard::new("You have won second prize in a beauty contest. Collect $10", CardMoveEnum::StayPut),
+        GameActionCard::new("You inherit $100", CardMoveEnum::StayPut),
+    ];
 
     GameActionCardDeck::new(comm_chest_cards)
 }
 
 
 
+//CARDS FROM: https://www.monopolyland.com/list-monopoly-chance-community-chest-cards/
 fn init_chance() -> GameActionCardDeck {
-    let mut chance_cards: Vec<_> = Vec::<GameActionCard>::with_capacity(16);
-
-
-    //CARDS FROM: https://www.monopolyland.com/list-monopoly-chance-community-chest-cards/
-
-        //Initialize cards into the Dealt vec, then shuffle on use
-        chance_cards.push(GameActionCard { text: String::from("Advance to Boardwalk"), move_action: |_| Some(39) });
-        chance_cards.push(GameActionCard { text: String::from("Advance to Go (Collect $200)"), move_action: |_| Some(0) });
-        chance_cards.push(GameActionCard { text: String::from("Advance to Illinois Avenue. If you pass Go, collect $200"), move_action: |_| Some(24) });
-        chance_cards.push(GameActionCard { text: String::from("Advance to St. Charles Place. If you pass Go, collect $200"), move_action: |_| Some(11) });
-        chance_cards.push(GameActionCard { text: String::from("Advance to the nearest Railroad. If unowned, you may buy it from the Bank. If owned, pay owner twice the rental to which they are otherwise entitled"), move_action: |landed| {
-            match landed {
-                7 => Some(15),  //Pennsylvania Railroad
-                22 => Some(25),  //B&O Railroad
-                36 => Some(5),    //Reading Railroad
-                _ => None
-            }
-        }});
-        chance_cards.push(GameActionCard { text: String::from("Advance to the nearest Railroad. If unowned, you may buy it from the Bank. If owned, pay owner twice the rental to which they are otherwise entitled"), move_action: |landed| {
-            match landed {
-                7 => Some(15),  //Pennsylvania Railroad
-                22 => Some(25),  //B&O Railroad
-                36 => Some(5),    //Reading Railroad
-                _ => None
-            }
-        }});
-        chance_cards.push(GameActionCard { text: String::from("Advance token to nearest Utility. If unowned, you may buy it from the Bank. If owned, throw dice and pay owner a total ten times amount thrown."), move_action: |landed| {
-            match landed {
-                7 | 36 => Some(12),  //Electric Company
-                22 => Some(28),  //Water Works
-                _ => None
-            }
-        }});
-        chance_cards.push(GameActionCard { text: String::from("Bank pays you dividend of $50"), move_action: |_| None });
-        chance_cards.push(GameActionCard { text: String::from("Get Out of Jail Free"), move_action: |_| None });
-        chance_cards.push(GameActionCard { text: String::from("Go Back 3 Spaces"), move_action: |landed| Some(landed - 3) });
-        chance_cards.push(GameActionCard { text: String::from("Go to Jail. Go directly to jail, do not pass Go, do not collect $200"), move_action: |_| Some(10) });
-        chance_cards.push(GameActionCard { text: String::from("Make general repairs on all your property. For each house pay $25. For each hotel pay $100"), move_action: |_| None });
-        chance_cards.push(GameActionCard { text: String::from("Speeding fine $15"), move_action: |_| None });
-        chance_cards.push(GameActionCard { text: String::from("Take a trip to Reading Railroad. If you pass Go, collect $200"), move_action: |_| Some(5) });
-        chance_cards.push(GameActionCard { text: String::from("You have been elected Chairman of the Board. Pay each player $50"), move_action: |_| None });
-        chance_cards.push(GameActionCard { text: String::from("Your building loan matures. Collect $150"), move_action: |_| None });
-
-        GameActionCardDeck::new(chance_cards)
-}
\ No newline at end of file
+    let chance_cards = vec![
+        GameActionCard::new("Advance to Boardwalk", CardMoveEnum::AdvanceTo("Boardwalk")),
+        GameActionCard::new("Advance to Go (Collect $200)", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Advance to Illinois Avenue. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("Illinois Avenue")),
+        GameActionCard::new("Advance to St. Charles Place. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("St. Charles Place")),
+        GameActionCard::new("Advance to the nearest Railroad. If unowned, you may buy it from the Bank. If owned, pay owner twice the rental to which they are otherwise entitled", CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Railroads)),
+        GameActionCard::new("Advance to the nearest Railroad. If unowned, you may buy it from the Bank. If owned, pay owner twice the rental to which they are otherwise entitled", CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Railroads)),
+        GameActionCard::new("Advance token to nearest Utility. If unowned, you may buy it from the Bank. If owned, throw dice and pay owner a total ten times amount thrown.", CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Utilities)),
+        GameActionCard::new("Bank pays you dividend of $50", CardMoveEnum::StayPut),
+        GameActionCard::new("Get Out of Jail Free", CardMoveEnum::StayPut),
+        GameActionCard::new("Go Back 3 Spaces", CardMoveEnum::MoveBy(-3)),
+        GameActionCard::new("Go to Jail. Go directly to jail, do not pass Go, do not collect $200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Make general repairs on all your property. For each house pay $25. For each hotel pay $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Speeding fine $15", CardMoveEnum::StayPut).with_fee(15),
+        GameActionCard::new("Take a trip to Reading Railroad. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("Reading Railroad")),
+        GameActionCard::new("You have been elected Chairman of the Board. Pay each player $50", CardMoveEnum::StayPut),
+        GameActionCard::new("Your building loan matures. Collect $150", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(chance_cards)
+}
+
+
+
+fn init_pre_2008_community_chest() -> GameActionCardDeck {
+    let comm_chest_cards = vec![
+        GameActionCard::new("Advance to Go (Collect $200)", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Bank error in your favor. Collect $200", CardMoveEnum::StayPut),
+        GameActionCard::new("Doctor's fee. Pay $50", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("From sale of stock you get $45", CardMoveEnum::StayPut),
+        GameActionCard::new("Get Out of Jail Free", CardMoveEnum::StayPut),
+        GameActionCard::new("Go to Jail. Go directly to jail. Do not pass Go, do not collect $200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Grand Opera Night. Collect $50 from every player for opening night seats", CardMoveEnum::StayPut),
+        GameActionCard::new("Xmas fund matures. Collect $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Income tax refund. Collect $20", CardMoveEnum::StayPut),
+        GameActionCard::new("Life insurance matures. Collect $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Pay hospital $100", CardMoveEnum::StayPut).with_fee(100),
+        GameActionCard::new("Pay school tax of $150", CardMoveEnum::StayPut).with_fee(150),
+        GameActionCard::new("Receive for services $25", CardMoveEnum::StayPut),
+        GameActionCard::new("You are assessed for street repairs. $40 per house, $115 per hotel", CardMoveEnum::StayPut),
+        GameActionCard::new("You have won second prize in a beauty contest. Collect $10", CardMoveEnum::StayPut),
+        GameActionCard::new("You inherit $100", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(comm_chest_cards)
+}
+
+
+
+fn init_pre_2008_chance() -> GameActionCardDeck {
+    let chance_cards = vec![
+        GameActionCard::new("Advance to Go (Collect $200)", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Advance to Illinois Ave. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("Illinois Avenue")),
+        GameActionCard::new("Advance to St. Charles Place. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("St. Charles Place")),
+        GameActionCard::new("Advance token to nearest Utility. If unowned, you may buy it from the Bank. If owned, throw dice and pay owner a total ten times the amount thrown", CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Utilities)),
+        GameActionCard::new("Advance token to the nearest Railroad and pay owner twice the rental to which he is otherwise entitled. If Railroad is unowned, you may buy it from the Bank", CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Railroads)),
+        GameActionCard::new("Bank pays you dividend of $50", CardMoveEnum::StayPut),
+        GameActionCard::new("Get Out of Jail Free", CardMoveEnum::StayPut),
+        GameActionCard::new("Go Back 3 Spaces", CardMoveEnum::MoveBy(-3)),
+        GameActionCard::new("Go to Jail. Go directly to Jail. Do not pass Go, do not collect $200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Make general repairs on all your property. For each house pay $25. For each hotel $100", CardMoveEnum::StayPut),
+        GameActionCard::new("Pay poor tax of $15", CardMoveEnum::StayPut).with_fee(15),
+        GameActionCard::new("Take a ride on the Reading. If you pass Go, collect $200", CardMoveEnum::AdvanceTo("Reading Railroad")),
+        GameActionCard::new("Take a walk on the Boardwalk. Advance token to Boardwalk", CardMoveEnum::AdvanceTo("Boardwalk")),
+        GameActionCard::new("You have been elected Chairman of the Board. Pay each player $50", CardMoveEnum::StayPut),
+        GameActionCard::new("Your building and loan matures. Collect $150", CardMoveEnum::StayPut),
+        GameActionCard::new("You have won a crossword competition. Collect $100", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(chance_cards)
+}
+
+
+fn init_uk_community_chest() -> GameActionCardDeck {
+    let comm_chest_cards = vec![
+        GameActionCard::new("Advance to Go", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Go back to Old Kent Road", CardMoveEnum::GoBackTo("Old Kent Road")),
+        GameActionCard::new("Go to Jail. Move directly to Jail. Do not pass Go. Do not collect £200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Pay hospital £100", CardMoveEnum::StayPut).with_fee(100),
+        GameActionCard::new("Doctor's fee. Pay £50", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Pay your insurance premium £50", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Bank error in your favour. Collect £200", CardMoveEnum::StayPut),
+        GameActionCard::new("Annuity matures. Collect £100", CardMoveEnum::StayPut),
+        GameActionCard::new("You inherit £100", CardMoveEnum::StayPut),
+        GameActionCard::new("From sale of stock you get £50", CardMoveEnum::StayPut),
+        GameActionCard::new("Receive interest on 7% preference shares £25", CardMoveEnum::StayPut),
+        GameActionCard::new("Income tax refund. Collect £20", CardMoveEnum::StayPut),
+        GameActionCard::new("You have won second prize in a beauty contest. Collect £10", CardMoveEnum::StayPut),
+        GameActionCard::new("It is your birthday. Collect £10 from each player", CardMoveEnum::StayPut),
+        GameActionCard::new("Get out of jail free", CardMoveEnum::StayPut),
+        //Simulated players always pay rather than draw
+        GameActionCard::new("Pay a £10 fine or take a Chance", CardMoveEnum::StayPut).with_fee(10),
+    ];
+
+    GameActionCardDeck::new(comm_chest_cards)
+}
+
+
+
+fn init_uk_chance() -> GameActionCardDeck {
+    let chance_cards = vec![
+        GameActionCard::new("Advance to Go", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Advance to Trafalgar Square. If you pass Go, collect £200", CardMoveEnum::AdvanceTo("Trafalgar Square")),
+        GameActionCard::new("Advance to Mayfair", CardMoveEnum::AdvanceTo("Mayfair")),
+        GameActionCard::new("Advance to Pall Mall. If you pass Go, collect £200", CardMoveEnum::AdvanceTo("Pall Mall")),
+        GameActionCard::new("Take a trip to Marylebone Station. If you pass Go, collect £200", CardMoveEnum::AdvanceTo("Marylebone Station")),
+        GameActionCard::new("Go to Jail. Move directly to Jail. Do not pass Go. Do not collect £200", CardMoveEnum::GoToJail),
+        GameActionCard::new("Go back three spaces", CardMoveEnum::MoveBy(-3)),
+        GameActionCard::new("Make general repairs on all of your houses. For each house pay £25. For each hotel pay £100", CardMoveEnum::StayPut),
+        GameActionCard::new("You are assessed for street repairs. £40 per house, £115 per hotel", CardMoveEnum::StayPut),
+        GameActionCard::new("Pay school fees of £150", CardMoveEnum::StayPut).with_fee(150),
+        GameActionCard::new("\"Drunk in charge\" fine £20", CardMoveEnum::StayPut).with_fee(20),
+        GameActionCard::new("Speeding fine £15", CardMoveEnum::StayPut).with_fee(15),
+        GameActionCard::new("Your building loan matures. Receive £150", CardMoveEnum::StayPut),
+        GameActionCard::new("You have won a crossword competition. Collect £100", CardMoveEnum::StayPut),
+        GameActionCard::new("Bank pays you dividend of £50", CardMoveEnum::StayPut),
+        GameActionCard::new("Get out of jail free", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(chance_cards)
+}
+
+
+
+fn init_french_community_chest() -> GameActionCardDeck {
+    let comm_chest_cards = vec![
+        GameActionCard::new("Placez-vous sur la case Départ", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Retournez à Belleville", CardMoveEnum::GoBackTo("Boulevard de Belleville")),
+        GameActionCard::new("Allez en prison. Avancez tout droit en prison. Ne passez pas par la case Départ. Ne recevez pas 200 €", CardMoveEnum::GoToJail),
+        GameActionCard::new("Payez à l'hôpital 100 €", CardMoveEnum::StayPut).with_fee(100),
+        GameActionCard::new("Payez la note du médecin 50 €", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Payez votre police d'assurance 50 €", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Erreur de la banque en votre faveur. Recevez 200 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Recevez votre revenu annuel 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous héritez 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("La vente de votre stock vous rapporte 50 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Recevez votre intérêt sur l'emprunt à 7 % : 25 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Les contributions vous remboursent la somme de 20 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous avez gagné le deuxième prix de beauté. Recevez 10 €", CardMoveEnum::StayPut),
+        GameActionCard::new("C'est votre anniversaire : chaque joueur doit vous donner 10 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous êtes libéré de prison", CardMoveEnum::StayPut),
+        //Simulated players always pay rather than draw
+        GameActionCard::new("Payez une amende de 10 € ou bien tirez une carte Chance", CardMoveEnum::StayPut).with_fee(10),
+    ];
+
+    GameActionCardDeck::new(comm_chest_cards)
+}
+
+
+
+fn init_french_chance() -> GameActionCardDeck {
+    let chance_cards = vec![
+        GameActionCard::new("Avancez jusqu'à la case Départ", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Rendez-vous à l'Avenue Henri-Martin. Si vous passez par la case Départ, recevez 200 €", CardMoveEnum::AdvanceTo("Avenue Henri-Martin")),
+        GameActionCard::new("Rendez-vous Rue de la Paix", CardMoveEnum::AdvanceTo("Rue de la Paix")),
+        GameActionCard::new("Avancez au Boulevard de la Villette. Si vous passez par la case Départ, recevez 200 €", CardMoveEnum::AdvanceTo("Boulevard de la Villette")),
+        GameActionCard::new("Allez à la Gare de Lyon. Si vous passez par la case Départ, recevez 200 €", CardMoveEnum::AdvanceTo("Gare de Lyon")),
+        GameActionCard::new("Allez en prison. Avancez tout droit en prison. Ne passez pas par la case Départ. Ne recevez pas 200 €", CardMoveEnum::GoToJail),
+        GameActionCard::new("Reculez de trois cases", CardMoveEnum::MoveBy(-3)),
+        GameActionCard::new("Faites des réparations dans toutes vos maisons. Versez pour chaque maison 25 €, pour chaque hôtel 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous êtes imposé pour les réparations de voirie à raison de 40 € par maison et 115 € par hôtel", CardMoveEnum::StayPut),
+        GameActionCard::new("Payez pour frais de scolarité 150 €", CardMoveEnum::StayPut).with_fee(150),
+        GameActionCard::new("Amende pour ivresse 20 €", CardMoveEnum::StayPut).with_fee(20),
+        GameActionCard::new("Amende pour excès de vitesse 15 €", CardMoveEnum::StayPut).with_fee(15),
+        GameActionCard::new("Votre immeuble et votre prêt rapportent. Vous devez toucher 150 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous avez gagné le prix de mots croisés. Recevez 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("La banque vous verse un dividende de 50 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Vous êtes libéré de prison", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(chance_cards)
+}
+
+
+
+fn init_german_community_chest() -> GameActionCardDeck {
+    let comm_chest_cards = vec![
+        GameActionCard::new("Rücken Sie vor bis auf Los", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Gehen Sie zurück zur Badstraße", CardMoveEnum::GoBackTo("Badstraße")),
+        GameActionCard::new("Gehen Sie in das Gefängnis. Begeben Sie sich direkt dorthin. Gehen Sie nicht über Los. Ziehen Sie nicht 200 € ein", CardMoveEnum::GoToJail),
+        GameActionCard::new("Zahlen Sie an das Krankenhaus 100 €", CardMoveEnum::StayPut).with_fee(100),
+        GameActionCard::new("Arzt-Kosten. Zahlen Sie 50 €", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Zahlen Sie Ihre Versicherungssumme 50 €", CardMoveEnum::StayPut).with_fee(50),
+        GameActionCard::new("Bank-Irrtum zu Ihren Gunsten. Ziehen Sie 200 € ein", CardMoveEnum::StayPut),
+        GameActionCard::new("Die Jahresrente wird fällig. Sie erhalten 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie erben 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Aus Lagerverkäufen erhalten Sie 50 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie erhalten auf Vorzugs-Aktien 7 % Dividende: 25 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Einkommensteuer-Rückzahlung. Ziehen Sie 20 € ein", CardMoveEnum::StayPut),
+        GameActionCard::new("Zweiter Preis im Schönheitswettbewerb. Sie erhalten 10 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Es ist Ihr Geburtstag. Ziehen Sie von jedem Spieler 10 € ein", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie kommen aus dem Gefängnis frei", CardMoveEnum::StayPut),
+        //Simulated players always pay rather than draw
+        GameActionCard::new("Zahlen Sie eine Strafe von 10 € oder nehmen Sie eine Ereigniskarte", CardMoveEnum::StayPut).with_fee(10),
+    ];
+
+    GameActionCardDeck::new(comm_chest_cards)
+}
+
+
+
+fn init_german_chance() -> GameActionCardDeck {
+    let chance_cards = vec![
+        GameActionCard::new("Rücken Sie vor bis auf Los", CardMoveEnum::AdvanceToGo),
+        GameActionCard::new("Rücken Sie vor bis zum Opernplatz. Wenn Sie über Los kommen, ziehen Sie 200 € ein", CardMoveEnum::AdvanceTo("Opernplatz")),
+        GameActionCard::new("Rücken Sie vor bis zur Schlossallee", CardMoveEnum::AdvanceTo("Schlossallee")),
+        GameActionCard::new("Rücken Sie vor bis zur Seestraße. Wenn Sie über Los kommen, ziehen Sie 200 € ein", CardMoveEnum::AdvanceTo("Seestraße")),
+        GameActionCard::new("Machen Sie einen Ausflug zum Westbahnhof. Wenn Sie über Los kommen, ziehen Sie 200 € ein", CardMoveEnum::AdvanceTo("Westbahnhof")),
+        GameActionCard::new("Gehen Sie in das Gefängnis. Begeben Sie sich direkt dorthin. Gehen Sie nicht über Los. Ziehen Sie nicht 200 € ein", CardMoveEnum::GoToJail),
+        GameActionCard::new("Gehen Sie 3 Felder zurück", CardMoveEnum::MoveBy(-3)),
+        GameActionCard::new("Lassen Sie alle Ihre Häuser renovieren. Zahlen Sie an die Bank für jedes Haus 25 €, für jedes Hotel 100 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie werden zu Straßenausbesserungsarbeiten herangezogen. Zahlen Sie 40 € je Haus und 115 € je Hotel", CardMoveEnum::StayPut),
+        GameActionCard::new("Zahlen Sie Schulgeld 150 €", CardMoveEnum::StayPut).with_fee(150),
+        GameActionCard::new("Betrunken im Dienst. Strafe 20 €", CardMoveEnum::StayPut).with_fee(20),
+        GameActionCard::new("Strafe für zu schnelles Fahren 15 €", CardMoveEnum::StayPut).with_fee(15),
+        GameActionCard::new("Ihr Bausparvertrag wird fällig. Sie erhalten 150 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie haben in einem Kreuzworträtsel-Wettbewerb gewonnen. Ziehen Sie 100 € ein", CardMoveEnum::StayPut),
+        GameActionCard::new("Die Bank zahlt Ihnen eine Dividende von 50 €", CardMoveEnum::StayPut),
+        GameActionCard::new("Sie kommen aus dem Gefängnis frei", CardMoveEnum::StayPut),
+    ];
+
+    GameActionCardDeck::new(chance_cards)
+}
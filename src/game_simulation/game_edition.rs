@@ -0,0 +1,72 @@
+use crate::game_simulation::game_board::{self, BoardSpace};
+use crate::game_simulation::game_cards::CardDecks;
+use crate::game_simulation::rule_set::RuleSet;
+
+
+//A published version of the game: its board, its card decks and any rules that come with it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameEditionEnum {
+    Classic,
+    //The classic board with the US decks from before the 2008 update
+    ClassicPre2008,
+    Mega,
+    Uk,
+    France,
+    Germany
+}
+
+impl GameEditionEnum {
+    pub const ALL: [GameEditionEnum; 6] = [
+        GameEditionEnum::Classic,
+        GameEditionEnum::ClassicPre2008,
+        GameEditionEnum::Mega,
+        GameEditionEnum::Uk,
+        GameEditionEnum::France,
+        GameEditionEnum::Germany
+    ];
+
+    //Short name used on the command line and in replay logs
+    pub fn get_edition_name(&self) -> &'static str {
+        match self {
+            GameEditionEnum::Classic => "classic",
+            GameEditionEnum::ClassicPre2008 => "classic-pre-2008",
+            GameEditionEnum::Mega => "mega",
+            GameEditionEnum::Uk => "uk",
+            GameEditionEnum::France => "france",
+            GameEditionEnum::Germany => "germany"
+        }
+    }
+
+    pub fn from_edition_name(name: &str) -> Option<Self> {
+        GameEditionEnum::ALL.into_iter().find(|edition| edition.get_edition_name() == name)
+    }
+
+    pub fn create_board(&self) -> Vec<Box<dyn BoardSpace>> {
+        match self {
+            GameEditionEnum::Classic | GameEditionEnum::ClassicPre2008 => game_board::initialize_game_board(),
+            GameEditionEnum::Mega => game_board::initialize_mega_board(),
+            GameEditionEnum::Uk => game_board::initialize_uk_board(),
+            GameEditionEnum::France => game_board::initialize_french_board(),
+            GameEditionEnum::Germany => game_board::initialize_german_board()
+        }
+    }
+
+    pub fn create_card_decks(&self) -> CardDecks {
+        match self {
+            //Cards resolve their moves against the board, so Mega plays with the classic decks
+            GameEditionEnum::Classic | GameEditionEnum::Mega => CardDecks::new(),
+            GameEditionEnum::ClassicPre2008 => CardDecks::pre_2008(),
+            GameEditionEnum::Uk => CardDecks::uk(),
+            GameEditionEnum::France => CardDecks::french(),
+            GameEditionEnum::Germany => CardDecks::german()
+        }
+    }
+
+    pub fn get_default_rules(&self) -> RuleSet {
+        match self {
+            //The printed rules don't change from country to country, only the currency does
+            GameEditionEnum::Classic | GameEditionEnum::ClassicPre2008 | GameEditionEnum::Uk | GameEditionEnum::France | GameEditionEnum::Germany => RuleSet::default(),
+            GameEditionEnum::Mega => RuleSet { speed_die: true, ..RuleSet::default() }
+        }
+    }
+}
@@ -0,0 +1,626 @@
+use crate::game_simulation::game_board::{self, BoardSpace, SpaceActionEnum};
+use crate::game_simulation::game_cards::{CardDecks, CardMoveEnum};
+use crate::game_simulation::passage_times::PassageTimes;
+use crate::game_simulation::rule_set::RuleSet;
+
+
+//Stop iterating once no state's probability moves by more than this
+const CONVERGENCE_TOLERANCE: f64 = 1e-13;
+const MAX_ITERATIONS: u32 = 100_000;
+
+//Cards moving players onto other card spaces can chain, but never this far
+const MAX_CARD_CHAIN: usize = 8;
+
+//With the doubles rule off a turn can go on rolling doubles forever.  Once less than this much probability is still rolling, the
+//rest of the turn is dropped
+const ROLLING_TOLERANCE: f64 = 1e-15;
+
+
+
+//Where a roll starts from, as a caller describes it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChainStateEnum {
+    //On a space (just visiting, for the jail) with this many doubles already rolled this turn, 0 at the start of a turn
+    OnSpace { space: u8, doubles_count: u8 },
+    //In jail after this many failed attempts to roll out
+    InJail { jail_turns: u8 }
+}
+
+
+
+//The exact long run behaviour of a single player, worked out as a Markov chain instead of simulated.  Each state is where a roll
+//starts from: a space plus doubles rolled so far this turn, or a turn spent in jail.  Two fair dice only, and card draws are
+//treated as independent (every card equally likely every time) rather than working through a shuffled deck.  Following rolls
+//through to the end of each turn gives the short run picture as well: where a player can be after a few turns, and how likely
+//they are to have landed somewhere by then
+pub struct MarkovChain {
+    space_count: usize,
+    jail_space: u8,
+    //Doubles already rolled this turn a roll can start from (max_doubles_before_jail, or 1 with the rule turned off)
+    doubles_states: usize,
+    //Failed attempts to roll out of jail a turn can start from
+    jail_states: usize,
+    //Sparse transition matrix, for each state the states the next roll can start from and how likely each one is
+    transitions: Vec<Vec<(usize, f64)>>,
+    //For each state, how many landings a roll starting there is expected to make on each space
+    landings: Vec<Vec<(usize, f64)>>,
+    //Every way landing on each space can play out
+    landing_paths: Vec<Vec<LandingPath>>,
+    max_doubles_before_jail: u8
+}
+
+impl MarkovChain {
+    pub fn new(board: &[Box<dyn BoardSpace>], card_decks: &mut CardDecks, rules: &RuleSet) -> Result<Self, String> {
+        if rules.speed_die {
+            return Err(String::from("The speed die depends on who owns what, which a Markov chain can't follow"));
+        }
+
+        if board.iter().any(|space| matches!(space.get_space_action(), SpaceActionEnum::TakeBusTicket)) {
+            return Err(String::from("Bus tickets depend on who owns what, which a Markov chain can't follow"));
+        }
+
+        let jail_space = game_board::get_jail_space(board)?;
+        card_decks.for_board(board)?;
+
+        //Where every space sends a player who lands on it, worked out once up front
+        let mut landing_paths = Vec::with_capacity(board.len());
+
+        for space in 0..board.len() as u8 {
+            let mut paths = Vec::new();
+            resolve_landing(board, card_decks, space, false, 1.0, Vec::new(), &mut paths)?;
+            landing_paths.push(paths);
+        }
+
+        let mut chain = MarkovChain {
+            space_count: board.len(),
+            jail_space,
+            doubles_states: rules.max_doubles_before_jail.max(1) as usize,
+            jail_states: rules.max_jail_turns as usize,
+            transitions: Vec::new(),
+            landings: Vec::new(),
+            landing_paths,
+            max_doubles_before_jail: rules.max_doubles_before_jail
+        };
+
+        for state in 0..chain.get_state_count() {
+            let mut transitions = vec![0.0; chain.get_state_count()];
+            let mut landings = vec![0.0; chain.space_count];
+
+            chain.for_each_roll_outcome(state, |outcome| {
+                transitions[outcome.next_state] += outcome.probability;
+
+                for space in outcome.visited.iter() {
+                    landings[*space as usize] += outcome.probability;
+                }
+            });
+
+            chain.transitions.push(sparse(&transitions));
+            chain.landings.push(sparse(&landings));
+        }
+
+        Ok(chain)
+    }
+
+
+    pub fn get_state_count(&self) -> usize {
+        self.space_count * self.doubles_states + self.jail_states
+    }
+
+
+    //Long run share of rolls starting from each state
+    pub fn get_steady_state(&self) -> Vec<f64> {
+        let state_count = self.get_state_count();
+        let mut distribution = vec![1.0 / state_count as f64; state_count];
+
+        for _ in 0..MAX_ITERATIONS {
+            let next_distribution = self.step(&distribution);
+
+            let largest_change = distribution.iter().zip(next_distribution.iter())
+                .map(|(before, after)| (before - after).abs())
+                .fold(0.0, f64::max);
+
+            distribution = next_distribution;
+
+            if largest_change < CONVERGENCE_TOLERANCE {
+                break;
+            }
+        }
+
+        distribution
+    }
+
+
+    //Long run share of all landings made on each space, comparable to SimulationResults::get_landing_probability
+    pub fn get_landing_probabilities(&self) -> Vec<f64> {
+        let mut landing_probabilities = vec![0.0; self.space_count];
+
+        for (state, state_probability) in self.get_steady_state().iter().enumerate() {
+            for (space, expected_landings) in self.landings[state].iter() {
+                landing_probabilities[*space] += state_probability * expected_landings;
+            }
+        }
+
+        let total: f64 = landing_probabilities.iter().sum();
+
+        landing_probabilities.iter().map(|probability| probability / total).collect()
+    }
+
+
+    //Chance of being on each space at the end of exactly turn_count turns from the start state, rolling again after doubles,
+    //going to jail and following cards exactly as a turn does.  A player in jail counts as on the jail space.  With no turns at
+    //all it's just the start space
+    pub fn get_turn_distribution(&self, start: ChainStateEnum, turn_count: u32) -> Result<Vec<f64>, String> {
+        let mut distribution = self.get_start_distribution(start)?;
+
+        for _ in 0..turn_count {
+            distribution = self.play_turn(&distribution, None).0;
+        }
+
+        let mut space_distribution = vec![0.0; self.space_count];
+
+        for (state, state_probability) in distribution.iter().enumerate() {
+            space_distribution[self.get_state_details(state).0 as usize] += state_probability;
+        }
+
+        Ok(space_distribution)
+    }
+
+
+    //Chance of landing on at least one of the target spaces by the end of each turn from the start state, from 0 turns (always
+    //0, being there already doesn't count) up to turn_count.  Every landing counts, including the space a card was drawn on
+    //before it moved the player on and being sent to jail for rolling too many doubles
+    pub fn get_hitting_probabilities(&self, start: ChainStateEnum, target_spaces: &[u8], turn_count: u32) -> Result<Vec<f64>, String> {
+        let is_target = self.get_target_flags(target_spaces)?;
+
+        Ok(self.get_hitting_curve(self.get_start_distribution(start)?, &is_target, turn_count))
+    }
+
+
+    //Chance of landing on at least one of the target spaces within turn_count turns from the start state
+    pub fn get_hitting_probability(&self, start: ChainStateEnum, target_spaces: &[u8], turn_count: u32) -> Result<f64, String> {
+        Ok(*self.get_hitting_probabilities(start, target_spaces, turn_count)?.last().unwrap())
+    }
+
+
+    //Expected turns between every pair of spaces, and between landings on the same space, all worked out exactly.  Return time
+    //distributions go up to max_return_turns, anything longer is left off
+    pub fn get_passage_times(&self, max_return_turns: u32) -> PassageTimes {
+        //Where a turn can start from: on a space with no doubles rolled yet, or in jail.  Free states come first, so each space's
+        //turn start is at its own index
+        let turn_start_states: Vec<usize> = (0..self.space_count as u8).map(|space| self.get_free_state(space, 0))
+            .chain((0..self.jail_states).map(|jail_turns| self.get_jail_state(jail_turns)))
+            .collect();
+
+        let mut start_indices = vec![0; self.get_state_count()];
+
+        for (start_index, state) in turn_start_states.iter().enumerate() {
+            start_indices[*state] = start_index;
+        }
+
+        let mut first_passage_turns = vec![vec![f64::INFINITY; self.space_count]; self.space_count];
+        let mut return_time_distributions = Vec::with_capacity(self.space_count);
+        let mut mean_return_turns = Vec::with_capacity(self.space_count);
+        let mut turn_steady_state = None;
+
+        for target_space in 0..self.space_count {
+            let mut is_target = vec![false; self.space_count];
+            is_target[target_space] = true;
+
+            //One turn from each turn start to the next, split into turns that missed the target and turns that landed on it
+            let (missed_rows, hit_rows): (Vec<_>, Vec<_>) = turn_start_states.iter()
+                .map(|state| {
+                    let mut distribution = vec![0.0; self.get_state_count()];
+                    distribution[*state] = 1.0;
+
+                    let (missed, hit) = self.play_turn(&distribution, Some(&is_target));
+                    let to_start_indices = |row: Vec<f64>| -> Vec<(usize, f64)> { sparse(&row).into_iter().map(|(state, probability)| (start_indices[state], probability)).collect() };
+
+                    (to_start_indices(missed), to_start_indices(hit))
+                })
+                .unzip();
+
+            let expected_turns = get_expected_hitting_turns(&missed_rows, &hit_rows);
+
+            for (from_space, from_space_turns) in first_passage_turns.iter_mut().enumerate() {
+                from_space_turns[target_space] = expected_turns[from_space];
+            }
+
+            //Every turn either lands on the target or doesn't, so which one was used to split them makes no difference here
+            let turn_steady_state: &Vec<f64> = turn_steady_state.get_or_insert_with(|| get_turn_steady_state(&missed_rows, &hit_rows));
+
+            //Where turns that land on the space end up, and so where the wait for the next landing starts from.  Every turn that
+            //lands on it starts one, so the mean wait is one over the share of turns that do (Kac's lemma)
+            let hit_distribution = multiply(turn_steady_state, &hit_rows);
+            let hit_probability: f64 = hit_distribution.iter().sum();
+            let mut return_time_distribution = vec![0.0; max_return_turns as usize + 1];
+
+            if hit_probability > 0.0 {
+                let mut waiting: Vec<f64> = hit_distribution.iter().map(|probability| probability / hit_probability).collect();
+
+                for return_time_probability in return_time_distribution.iter_mut().skip(1) {
+                    *return_time_probability = multiply(&waiting, &hit_rows).iter().sum();
+                    waiting = multiply(&waiting, &missed_rows);
+                }
+            }
+
+            return_time_distributions.push(return_time_distribution);
+            mean_return_turns.push(1.0 / hit_probability);
+        }
+
+        PassageTimes { first_passage_turns, return_time_distributions, mean_return_turns }
+    }
+
+
+    //Where the next roll starts from, given the distribution of where this one does
+    fn step(&self, distribution: &[f64]) -> Vec<f64> {
+        multiply(distribution, &self.transitions)
+    }
+
+
+    //Cumulative chance of having landed on a target by the end of each turn, from 0 turns up to turn_count.  Only the probability
+    //of not having landed on one yet is carried from turn to turn
+    fn get_hitting_curve(&self, mut distribution: Vec<f64>, is_target: &[bool], turn_count: u32) -> Vec<f64> {
+        let mut hitting_probabilities = vec![0.0];
+
+        for _ in 0..turn_count {
+            let (missed, hit) = self.play_turn(&distribution, Some(is_target));
+
+            distribution = missed;
+            hitting_probabilities.push(hitting_probabilities.last().unwrap() + hit.iter().sum::<f64>());
+        }
+
+        hitting_probabilities
+    }
+
+
+    //Rolls until the turn is over from each state, giving the distribution of where the next turn starts from.  With target
+    //spaces that's split in two: turns that never landed on one, and turns that did at some point
+    fn play_turn(&self, distribution: &[f64], is_target: Option<&[bool]>) -> (Vec<f64>, Vec<f64>) {
+        let state_count = distribution.len();
+        //Probability still rolling, not yet landed on a target this turn and already landed on one
+        let mut rolling = [distribution.to_vec(), vec![0.0; state_count]];
+        let mut turn_over = [vec![0.0; state_count], vec![0.0; state_count]];
+
+        while rolling.iter().flatten().sum::<f64>() > ROLLING_TOLERANCE {
+            let mut rolling_again = [vec![0.0; state_count], vec![0.0; state_count]];
+
+            for (already_hit, rolling_states) in rolling.iter().enumerate() {
+                for (state, state_probability) in rolling_states.iter().enumerate().filter(|(_, state_probability)| **state_probability > 0.0) {
+                    self.for_each_roll_outcome(state, |outcome| {
+                        let probability = state_probability * outcome.probability;
+                        let hit = already_hit == 1 || is_target.is_some_and(|is_target| outcome.visited.iter().any(|space| is_target[*space as usize]));
+
+                        if outcome.rolls_again {
+                            rolling_again[hit as usize][outcome.next_state] += probability;
+                        }
+                        else {
+                            turn_over[hit as usize][outcome.next_state] += probability;
+                        }
+                    });
+                }
+            }
+
+            rolling = rolling_again;
+        }
+
+        let [missed, hit] = turn_over;
+
+        (missed, hit)
+    }
+
+
+    //Every way a roll from a state can play out: 36 dice rolls, each followed through every path its landing can take
+    fn for_each_roll_outcome(&self, state: usize, mut on_outcome: impl FnMut(RollOutcome)) {
+        let (from_space, doubles_count, jail_turns) = self.get_state_details(state);
+
+        for dice1 in 1..=6u8 {
+            for dice2 in 1..=6u8 {
+                let roll_probability = 1.0 / 36.0;
+                let doubles = dice1 == dice2;
+
+                //Still in jail, the roll either gets them out (doubles, or out of turns and paying up) or it doesn't
+                if let Some(jail_turns) = jail_turns {
+                    if !doubles && jail_turns + 1 < self.jail_states {
+                        on_outcome(RollOutcome { probability: roll_probability, next_state: self.get_jail_state(jail_turns + 1), rolls_again: false, visited: &[] });
+                        continue;
+                    }
+                }
+
+                //Too many doubles goes straight to jail without moving
+                if jail_turns.is_none() && doubles && self.max_doubles_before_jail > 0 && doubles_count + 1 == self.max_doubles_before_jail as usize {
+                    on_outcome(RollOutcome { probability: roll_probability, next_state: self.get_sent_to_jail_state(), rolls_again: false, visited: std::slice::from_ref(&self.jail_space) });
+                    continue;
+                }
+
+                let landed_space = (from_space as usize + dice1 as usize + dice2 as usize) % self.space_count;
+
+                for path in self.landing_paths[landed_space].iter() {
+                    //Rolling doubles out of jail doesn't earn another roll, and going to jail ends the turn
+                    let rolls_again = doubles && jail_turns.is_none() && !path.sent_to_jail;
+
+                    let next_state =
+                        if path.sent_to_jail {
+                            self.get_sent_to_jail_state()
+                        }
+                        else if rolls_again && self.max_doubles_before_jail > 0 {
+                            self.get_free_state(path.final_space, doubles_count + 1)
+                        }
+                        else {
+                            self.get_free_state(path.final_space, 0)
+                        };
+
+                    on_outcome(RollOutcome { probability: roll_probability * path.probability, next_state, rolls_again, visited: &path.visited });
+                }
+            }
+        }
+    }
+
+
+    fn get_target_flags(&self, target_spaces: &[u8]) -> Result<Vec<bool>, String> {
+        let mut is_target = vec![false; self.space_count];
+
+        for space in target_spaces.iter() {
+            *is_target.get_mut(*space as usize).ok_or(format!("Space {} isn't on the {} space board", space, self.space_count))? = true;
+        }
+
+        Ok(is_target)
+    }
+
+
+    fn get_start_distribution(&self, start: ChainStateEnum) -> Result<Vec<f64>, String> {
+        let start_state =
+            match start {
+                ChainStateEnum::OnSpace { space, doubles_count } if (space as usize) < self.space_count && (doubles_count as usize) < self.doubles_states =>
+                    self.get_free_state(space, doubles_count as usize),
+                ChainStateEnum::InJail { jail_turns } if (jail_turns as usize) < self.jail_states => self.get_jail_state(jail_turns as usize),
+                //Paying straight away, a player in jail makes a normal roll from the jail space
+                ChainStateEnum::InJail { jail_turns: 0 } if self.jail_states == 0 => self.get_sent_to_jail_state(),
+                _ => return Err(format!("{:?} can't happen on a {} space board with these rules", start, self.space_count))
+            };
+
+        let mut distribution = vec![0.0; self.get_state_count()];
+        distribution[start_state] = 1.0;
+
+        Ok(distribution)
+    }
+
+
+    fn get_free_state(&self, space: u8, doubles_count: usize) -> usize {
+        space as usize * self.doubles_states + doubles_count
+    }
+
+    fn get_jail_state(&self, jail_turns: usize) -> usize {
+        self.space_count * self.doubles_states + jail_turns
+    }
+
+    //Paying to leave jail straight away means the next roll is a perfectly normal one from the jail space
+    fn get_sent_to_jail_state(&self) -> usize {
+        if self.jail_states == 0 {
+            self.get_free_state(self.jail_space, 0)
+        }
+        else {
+            self.get_jail_state(0)
+        }
+    }
+
+    //The space a state's roll starts from, doubles rolled so far, and failed attempts to leave jail if it's a jail state
+    fn get_state_details(&self, state: usize) -> (u8, usize, Option<usize>) {
+        let free_state_count = self.space_count * self.doubles_states;
+
+        if state < free_state_count {
+            ((state / self.doubles_states) as u8, state % self.doubles_states, None)
+        }
+        else {
+            (self.jail_space, 0, Some(state - free_state_count))
+        }
+    }
+}
+
+
+
+//One way landing on a space can play out: every space landed on along the way (the space itself, then wherever cards moved
+//the player on to), where they came to rest, whether they were sent to jail to get there, and how likely it is
+struct LandingPath {
+    visited: Vec<u8>,
+    final_space: u8,
+    sent_to_jail: bool,
+    probability: f64
+}
+
+
+//One way a roll can play out, for a player in a particular state
+struct RollOutcome<'a> {
+    probability: f64,
+    next_state: usize,
+    //Doubles, so the turn carries on with another roll from next_state
+    rolls_again: bool,
+    //Every landing the roll made, nothing for a player who stayed in jail
+    visited: &'a [u8]
+}
+
+
+fn resolve_landing(board: &[Box<dyn BoardSpace>], card_decks: &mut CardDecks, space: u8, sent_to_jail: bool, probability: f64, mut visited: Vec<u8>, paths: &mut Vec<LandingPath>) -> Result<(), String> {
+    if visited.len() > MAX_CARD_CHAIN {
+        return Err(format!("Cards keep moving players on from space {} without ever stopping", space));
+    }
+
+    visited.push(space);
+
+    match board[space as usize].get_space_action() {
+        SpaceActionEnum::DrawCard(deck) => {
+            let deck = card_decks.get_deck(deck);
+            let card_moves: Vec<CardMoveEnum> = (0..deck.get_card_count())
+                .map(|card_index| deck.get_card(card_index).unwrap().get_card_move())
+                .collect();
+
+            let card_probability = probability / card_moves.len() as f64;
+
+            for card_move in card_moves {
+                match card_move.resolve(board, space) {
+                    Some(moved_space) => resolve_landing(board, card_decks, moved_space, card_move == CardMoveEnum::GoToJail, card_probability, visited.clone(), paths)?,
+                    None => add_path(paths, visited.clone(), space, sent_to_jail, card_probability)
+                }
+            }
+        },
+        SpaceActionEnum::SendToJail => {
+            let jail_space = board.iter().position(|space| space.is_jail()).unwrap() as u8;
+
+            resolve_landing(board, card_decks, jail_space, true, probability, visited, paths)?
+        },
+        _ => add_path(paths, visited, space, sent_to_jail, probability)
+    }
+
+    Ok(())
+}
+
+
+//Most cards leave the player where they are, so identical paths are merged rather than repeated
+fn add_path(paths: &mut Vec<LandingPath>, visited: Vec<u8>, final_space: u8, sent_to_jail: bool, probability: f64) {
+    match paths.iter_mut().find(|path| path.visited == visited && path.final_space == final_space && path.sent_to_jail == sent_to_jail) {
+        Some(path) => path.probability += probability,
+        None => paths.push(LandingPath { visited, final_space, sent_to_jail, probability })
+    }
+}
+
+
+//Long run share of turns starting from each turn start, given where a turn from each one ends up
+fn get_turn_steady_state(missed_rows: &[Vec<(usize, f64)>], hit_rows: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let mut distribution = vec![1.0 / missed_rows.len() as f64; missed_rows.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        let next_distribution: Vec<f64> = multiply(&distribution, missed_rows).iter()
+            .zip(multiply(&distribution, hit_rows).iter())
+            .map(|(missed, hit)| missed + hit)
+            .collect();
+
+        let largest_change = distribution.iter().zip(next_distribution.iter())
+            .map(|(before, after)| (before - after).abs())
+            .fold(0.0, f64::max);
+
+        distribution = next_distribution;
+
+        if largest_change < CONVERGENCE_TOLERANCE {
+            break;
+        }
+    }
+
+    distribution
+}
+
+
+//Expected turns until landing on a target from each turn start.  Solves E = 1 + K E over the turn starts a target is certain to be
+//landed on from, K being the chance of a turn going from one to the next without landing on it.  From anywhere else there's a
+//chance of never landing on it, so the wait is infinite
+fn get_expected_hitting_turns(missed_rows: &[Vec<(usize, f64)>], hit_rows: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let start_count = missed_rows.len();
+
+    //Turn starts that can get to the target at all, then the ones that can't get anywhere that can't
+    let mut can_hit: Vec<bool> = hit_rows.iter().map(|hit_row| !hit_row.is_empty()).collect();
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for start_index in 0..start_count {
+            if !can_hit[start_index] && missed_rows[start_index].iter().any(|(next_index, _)| can_hit[*next_index]) {
+                can_hit[start_index] = true;
+                changed = true;
+            }
+        }
+    }
+
+    let mut certain = can_hit;
+    changed = true;
+
+    while changed {
+        changed = false;
+
+        for start_index in 0..start_count {
+            if certain[start_index] && missed_rows[start_index].iter().any(|(next_index, _)| !certain[*next_index]) {
+                certain[start_index] = false;
+                changed = true;
+            }
+        }
+    }
+
+    let certain_indices: Vec<usize> = (0..start_count).filter(|start_index| certain[*start_index]).collect();
+    let mut row_indices = vec![0; start_count];
+
+    for (row_index, start_index) in certain_indices.iter().enumerate() {
+        row_indices[*start_index] = row_index;
+    }
+
+    let mut matrix = vec![vec![0.0; certain_indices.len()]; certain_indices.len()];
+
+    for (row_index, start_index) in certain_indices.iter().enumerate() {
+        matrix[row_index][row_index] = 1.0;
+
+        for (next_index, probability) in missed_rows[*start_index].iter() {
+            matrix[row_index][row_indices[*next_index]] -= probability;
+        }
+    }
+
+    let solution = solve_linear_system(matrix, vec![1.0; certain_indices.len()]);
+    let mut expected_turns = vec![f64::INFINITY; start_count];
+
+    for (row_index, start_index) in certain_indices.iter().enumerate() {
+        expected_turns[*start_index] = solution[row_index];
+    }
+
+    expected_turns
+}
+
+
+//Gaussian elimination with partial pivoting, for a square system that has a solution
+fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Vec<f64> {
+    let size = rhs.len();
+
+    for column in 0..size {
+        let pivot = (column..size).max_by(|a, b| matrix[*a][column].abs().total_cmp(&matrix[*b][column].abs())).unwrap();
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        let pivot_row = matrix[column].clone();
+
+        for row in column + 1..size {
+            let factor = matrix[row][column] / pivot_row[column];
+
+            if factor != 0.0 {
+                for (value, pivot_value) in matrix[row][column..].iter_mut().zip(pivot_row[column..].iter()) {
+                    *value -= factor * pivot_value;
+                }
+
+                rhs[row] -= factor * rhs[column];
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; size];
+
+    for row in (0..size).rev() {
+        let known: f64 = (row + 1..size).map(|idx| matrix[row][idx] * solution[idx]).sum();
+        solution[row] = (rhs[row] - known) / matrix[row][row];
+    }
+
+    solution
+}
+
+
+//Distribution over the rows' destinations, given the distribution over the rows
+fn multiply(distribution: &[f64], rows: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let mut next_distribution = vec![0.0; distribution.len()];
+
+    for (row, row_probability) in distribution.iter().enumerate() {
+        for (next, probability) in rows[row].iter() {
+            next_distribution[*next] += row_probability * probability;
+        }
+    }
+
+    next_distribution
+}
+
+
+//Drops the zeros from a row of the transition or landings matrix
+fn sparse(row: &[f64]) -> Vec<(usize, f64)> {
+    row.iter().enumerate().filter(|(_, value)| **value > 0.0).map(|(idx, value)| (idx, *value)).collect()
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use crate::game_simulation::game_board::SpaceGroupEnum;
+use crate::game_simulation::simulation_events::{SimulationEventEnum, SimulationObserver};
+
+
+//Return times shown as the chance of coming back within this many turns
+const RETURN_WITHIN_TURNS: [u32; 5] = [1, 5, 10, 20, 50];
+
+
+
+//How long, in a single player's turns, until spaces get landed on.  Worked out exactly by MarkovChain::get_passage_times or
+//measured from a game with a PassageTimeObserver, in the same shape so the two can be set side by side
+#[derive(Clone, Debug)]
+pub struct PassageTimes {
+    //Expected turns from ending a turn on one space until landing on another, first_passage_turns[from][to].  The turn that makes
+    //the landing counts, so it's never less than 1.  The jail's row starts from just visiting.  Infinite if it never happens
+    pub first_passage_turns: Vec<Vec<f64>>,
+    //For each space, the chance the next landing on it comes exactly k turns after a turn that landed on it,
+    //return_time_distributions[space][k].  k = 0 is always 0: landing twice in one turn isn't a return
+    pub return_time_distributions: Vec<Vec<f64>>,
+    //Expected turns between one turn landing on a space and the next, infinite for a space never landed on
+    pub mean_return_turns: Vec<f64>
+}
+
+impl PassageTimes {
+    //Chance the next landing on a space comes within turn_count turns of the last one
+    pub fn get_return_probability_within(&self, space_index: usize, turn_count: u32) -> f64 {
+        self.return_time_distributions[space_index].iter().take(turn_count as usize + 1).sum()
+    }
+
+
+    //Fewest turns within which a return to the space is at least as likely as probability (0.5 for the median).  None if the
+    //distribution doesn't go far enough to say
+    pub fn get_return_turns_quantile(&self, space_index: usize, probability: f64) -> Option<u32> {
+        let mut cumulative = 0.0;
+
+        for (turns, turn_probability) in self.return_time_distributions[space_index].iter().enumerate() {
+            cumulative += turn_probability;
+
+            if cumulative >= probability {
+                return Some(turns as u32);
+            }
+        }
+
+        None
+    }
+}
+
+
+
+//Passage times from the exact chain and a simulated game for one target space: how long until it's landed on from every space,
+//then how long between landings on it
+pub fn print_passage_comparison(target_space: usize, space_names: &[String], space_groups: &[SpaceGroupEnum], exact: &PassageTimes, simulated: &PassageTimes) {
+    let target_name = space_groups[target_space].colorize(&space_names[target_space]);
+
+    println!("--------------------PASSAGE TIMES--------------------");
+    println!("Expected turns from ending a turn on each space until landing on {}", target_name);
+    println!("Exact|Simulated|From");
+
+    for (space_index, (space_name, space_group)) in space_names.iter().zip(space_groups.iter()).enumerate() {
+        println!("{}|{}|{}",
+            format_turns(exact.first_passage_turns[space_index][target_space]),
+            format_turns(simulated.first_passage_turns[space_index][target_space]),
+            space_group.colorize(space_name));
+    }
+
+    println!("-----------------------------------------------------");
+    println!("Turns between landings on {}", target_name);
+    println!("Exact|Simulated|");
+    println!("{}|{}|Mean", format_turns(exact.mean_return_turns[target_space]), format_turns(simulated.mean_return_turns[target_space]));
+
+    let quantile = |passage_times: &PassageTimes| passage_times.get_return_turns_quantile(target_space, 0.5)
+        .map_or(String::from("-"), |turns| turns.to_string());
+    println!("{}|{}|Median", quantile(exact), quantile(simulated));
+
+    for turn_count in RETURN_WITHIN_TURNS {
+        println!("{:.2}%|{:.2}%|Back within {} turn{}",
+            exact.get_return_probability_within(target_space, turn_count) * 100.0,
+            simulated.get_return_probability_within(target_space, turn_count) * 100.0,
+            turn_count,
+            if turn_count == 1 { "" } else { "s" });
+    }
+
+    println!("-----------------------------------------------------");
+}
+
+
+//Waits that never end (or were never seen in a game, like a turn ending on Go To Jail) show as a dash
+fn format_turns(turns: f64) -> String {
+    if turns.is_finite() {
+        format!("{:.2}", turns)
+    }
+    else {
+        String::from("-")
+    }
+}
+
+
+
+//Measures passage times from the events of a game on the standard engine.  Every player's game counts separately, and a turn
+//only counts as a start once it's over, so a landing later in the same turn isn't a passage.  Turns ended in jail don't start
+//anything.  Passages still under way when the game ends are never finished, so short games come out a little quick
+pub struct PassageTimeObserver {
+    space_count: usize,
+    round: u32,
+    players: HashMap<String, PlayerPassages>,
+    //Turns added up across every finished passage, and how many there were, [from][to]
+    passage_turn_totals: Vec<Vec<u64>>,
+    passage_counts: Vec<Vec<u64>>,
+    //For each space, how many returns took each number of turns
+    return_time_counts: Vec<Vec<u64>>
+}
+
+
+//One player's passages still under way
+struct PlayerPassages {
+    in_jail: bool,
+    //Where the player came to rest this turn so far
+    turn_end: Option<u8>,
+    //For each target space, the turns ended since it was last landed on: how many ended on each space, and their turn numbers
+    //added up.  Enough to finish all of them at once when the target is landed on
+    pending_counts: Vec<Vec<u64>>,
+    pending_turn_totals: Vec<Vec<u64>>,
+    last_landed_turn: Vec<Option<u32>>
+}
+
+impl PlayerPassages {
+    fn get_player<'a>(players: &'a mut HashMap<String, PlayerPassages>, player_name: &str, space_count: usize) -> &'a mut PlayerPassages {
+        if !players.contains_key(player_name) {
+            players.insert(player_name.to_string(), PlayerPassages {
+                in_jail: false,
+                turn_end: None,
+                pending_counts: vec![vec![0; space_count]; space_count],
+                pending_turn_totals: vec![vec![0; space_count]; space_count],
+                last_landed_turn: vec![None; space_count]
+            });
+        }
+
+        players.get_mut(player_name).unwrap()
+    }
+}
+
+impl PassageTimeObserver {
+    pub fn new(space_count: usize) -> Self {
+        PassageTimeObserver {
+            space_count,
+            round: 0,
+            players: HashMap::new(),
+            passage_turn_totals: vec![vec![0; space_count]; space_count],
+            passage_counts: vec![vec![0; space_count]; space_count],
+            return_time_counts: vec![Vec::new(); space_count]
+        }
+    }
+
+
+    //Averages over the passages seen so far.  Pairs never seen are infinite, as is the mean return time of a space never returned to
+    pub fn get_passage_times(&self) -> PassageTimes {
+        let first_passage_turns = self.passage_turn_totals.iter()
+            .zip(self.passage_counts.iter())
+            .map(|(turn_totals, counts)| turn_totals.iter()
+                .zip(counts.iter())
+                .map(|(turn_total, count)| if *count > 0 { *turn_total as f64 / *count as f64 } else { f64::INFINITY })
+                .collect())
+            .collect();
+
+        let mut return_time_distributions = Vec::with_capacity(self.space_count);
+        let mut mean_return_turns = Vec::with_capacity(self.space_count);
+
+        for return_counts in self.return_time_counts.iter() {
+            let return_count: u64 = return_counts.iter().sum();
+            let turn_total: u64 = return_counts.iter().enumerate().map(|(turns, count)| turns as u64 * count).sum();
+
+            return_time_distributions.push(return_counts.iter().map(|count| *count as f64 / return_count.max(1) as f64).collect());
+            mean_return_turns.push(if return_count > 0 { turn_total as f64 / return_count as f64 } else { f64::INFINITY });
+        }
+
+        PassageTimes { first_passage_turns, return_time_distributions, mean_return_turns }
+    }
+
+
+    fn record_landing(&mut self, player_name: &str, space: usize) {
+        let round = self.round;
+        let player = PlayerPassages::get_player(&mut self.players, player_name, self.space_count);
+
+        for from_space in 0..self.space_count {
+            let pending_count = std::mem::take(&mut player.pending_counts[space][from_space]);
+            let pending_turn_total = std::mem::take(&mut player.pending_turn_totals[space][from_space]);
+
+            self.passage_counts[from_space][space] += pending_count;
+            self.passage_turn_totals[from_space][space] += pending_count * round as u64 - pending_turn_total;
+        }
+
+        if let Some(previous_turn) = player.last_landed_turn[space].replace(round).filter(|previous_turn| *previous_turn < round) {
+            let return_turns = (round - previous_turn) as usize;
+            let return_counts = &mut self.return_time_counts[space];
+
+            if return_counts.len() <= return_turns {
+                return_counts.resize(return_turns + 1, 0);
+            }
+
+            return_counts[return_turns] += 1;
+        }
+    }
+
+
+    //Every turn of the round just played starts a passage to every space
+    fn finish_round(&mut self) {
+        let round = self.round as u64;
+
+        for player in self.players.values_mut() {
+            if let Some(turn_end) = player.turn_end.take().filter(|_| !player.in_jail) {
+                for target_space in 0..self.space_count {
+                    player.pending_counts[target_space][turn_end as usize] += 1;
+                    player.pending_turn_totals[target_space][turn_end as usize] += round;
+                }
+            }
+        }
+    }
+}
+
+impl SimulationObserver for PassageTimeObserver {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        match event {
+            SimulationEventEnum::TurnStarted { turn_number } => {
+                self.finish_round();
+                self.round = *turn_number;
+            },
+            SimulationEventEnum::LandedOnSpace { player_name, space_index, .. } => self.record_landing(player_name, *space_index as usize),
+            SimulationEventEnum::RollFinished { player_name, final_space } =>
+                PlayerPassages::get_player(&mut self.players, player_name, self.space_count).turn_end = Some(*final_space),
+            SimulationEventEnum::SentToJail { player_name, .. } => PlayerPassages::get_player(&mut self.players, player_name, self.space_count).in_jail = true,
+            SimulationEventEnum::LeftJail { player_name, .. } => PlayerPassages::get_player(&mut self.players, player_name, self.space_count).in_jail = false,
+            _ => ()
+        }
+    }
+}
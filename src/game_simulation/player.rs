@@ -1,12 +1,19 @@
 pub struct Player
 {
     name: String,
-    current_space: u8
+    current_space: u8,
+    //Only true when sent to jail, a player sitting on the jail space otherwise is just visiting
+    in_jail: bool,
+    //Turns spent in jail so far trying to roll doubles
+    jail_turns: u8,
+    //Mega edition: the speed die only comes into play once a player has been round the board
+    passed_go: bool,
+    bus_tickets: u8
 }
 
 impl Player {
     pub fn new(name: String) -> Self {
-        Player { name, current_space: 0 }
+        Player { name, current_space: 0, in_jail: false, jail_turns: 0, passed_go: false, bus_tickets: 0 }
     }
 
     pub fn set_current_space (&mut self, space: u8) {
@@ -20,4 +27,46 @@ impl Player {
     pub fn get_player_name(&self) -> &str {
         &self.name
     }
-}
\ No newline at end of file
+
+    pub fn is_in_jail(&self) -> bool {
+        self.in_jail
+    }
+
+    pub fn get_jail_turns(&self) -> u8 {
+        self.jail_turns
+    }
+
+    pub fn send_to_jail(&mut self) {
+        self.in_jail = true;
+        self.jail_turns = 0;
+    }
+
+    pub fn add_jail_turn(&mut self) {
+        self.jail_turns += 1;
+    }
+
+    pub fn leave_jail(&mut self) {
+        self.in_jail = false;
+        self.jail_turns = 0;
+    }
+
+    pub fn has_passed_go(&self) -> bool {
+        self.passed_go
+    }
+
+    pub fn set_passed_go(&mut self) {
+        self.passed_go = true;
+    }
+
+    pub fn get_bus_tickets(&self) -> u8 {
+        self.bus_tickets
+    }
+
+    pub fn add_bus_ticket(&mut self) {
+        self.bus_tickets += 1;
+    }
+
+    pub fn use_bus_ticket(&mut self) {
+        self.bus_tickets -= 1;
+    }
+}
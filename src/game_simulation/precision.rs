@@ -0,0 +1,196 @@
+//Never trust an interval worked out from fewer batches than this, however tight it looks
+const MIN_BATCHES: u32 = 30;
+
+const DEFAULT_BATCH_TURNS: u32 = 1000;
+const DEFAULT_MAX_TURNS: u32 = 10_000_000;
+
+//Batches a fixed length run is cut into, comfortably over MIN_BATCHES
+const FIXED_TURNS_BATCHES: u32 = 50;
+
+//Two sided normal critical values for the confidence levels a target can ask for
+const Z_SCORES: [(f64, f64); 6] = [
+    (0.80, 1.2816),
+    (0.90, 1.6449),
+    (0.95, 1.9600),
+    (0.98, 2.3263),
+    (0.99, 2.5758),
+    (0.999, 3.2905)
+];
+
+
+
+//How precisely every space's landing probability has to be known before a run stops.  Half widths are a fraction of all
+//landings like the probabilities themselves, so 0.0005 is "within 0.05%"
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrecisionTarget {
+    half_width: f64,
+    confidence: f64,
+    z_score: f64,
+    batch_turns: u32,
+    max_turns: u32
+}
+
+impl PrecisionTarget {
+    //Confidence is the interval's confidence level, e.g. 0.95
+    pub fn new(half_width: f64, confidence: f64) -> Result<Self, String> {
+        if !(half_width > 0.0 && half_width < 1.0) {
+            return Err(format!("Target half width must be between 0 and 1, got {}", half_width));
+        }
+
+        let z_score = Z_SCORES.iter()
+            .find(|(level, _)| (level - confidence).abs() < 1e-9)
+            .map(|(_, z_score)| *z_score)
+            .ok_or(format!("Confidence must be one of 80%, 90%, 95%, 98%, 99% or 99.9%, got {}%", confidence * 100.0))?;
+
+        Ok(PrecisionTarget { half_width, confidence, z_score, batch_turns: DEFAULT_BATCH_TURNS, max_turns: DEFAULT_MAX_TURNS })
+    }
+
+    //No target to reach: plays exactly turn_count turns and measures how precise each space ended up along the way, for results
+    //that need intervals (see saved_results) from a run of a set length
+    pub fn fixed_turns(turn_count: u32, confidence: f64) -> Result<Self, String> {
+        let target = PrecisionTarget::new(0.5, confidence)?;
+
+        Ok(PrecisionTarget { half_width: 0.0, batch_turns: (turn_count / FIXED_TURNS_BATCHES).max(1), max_turns: turn_count, ..target })
+    }
+
+    //Turns per batch.  Each batch's landing shares are one sample towards the batch means estimate, so a batch wants to be long
+    //enough that one batch barely depends on the last
+    pub fn batch_turns(mut self, batch_turns: u32) -> Self {
+        self.batch_turns = batch_turns.max(1);
+        self
+    }
+
+    //Give up after this many turns even if the target still hasn't been reached
+    pub fn max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    //Zero for a fixed_turns run, which has no target
+    pub fn get_half_width(&self) -> f64 {
+        self.half_width
+    }
+
+    pub fn has_target(&self) -> bool {
+        self.half_width > 0.0
+    }
+
+    pub fn get_z_score(&self) -> f64 {
+        self.z_score
+    }
+
+    pub fn get_confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    pub fn get_batch_turns(&self) -> u32 {
+        self.batch_turns
+    }
+
+    pub fn get_max_turns(&self) -> u32 {
+        self.max_turns
+    }
+}
+
+
+
+//How precise a run stopped by a PrecisionTarget ended up
+#[derive(Clone, Debug)]
+pub struct PrecisionReport {
+    pub target: PrecisionTarget,
+    pub batch_count: u32,
+    pub target_reached: bool,
+    //Confidence interval half width on each space's landing probability, in board order
+    pub half_widths: Vec<f64>
+}
+
+impl PrecisionReport {
+    pub fn get_largest_half_width(&self) -> f64 {
+        self.half_widths.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+
+
+//Batch means: the run is cut into batches of target.batch_turns turns, each batch's share of landings on a space is one sample,
+//and the spread of those samples gives the interval.  Successive turns are far from independent but successive long batches
+//nearly are, which is what makes this honest where treating every landing as independent would not be
+#[derive(Clone)]
+pub(crate) struct PrecisionTracker {
+    target: PrecisionTarget,
+    //Everything below is saved in checkpoints, see checkpoint.rs
+    pub(super) previous_counts: Vec<u64>,
+    pub(super) share_sums: Vec<f64>,
+    pub(super) share_square_sums: Vec<f64>,
+    pub(super) batch_count: u32
+}
+
+impl PrecisionTracker {
+    pub fn new(target: PrecisionTarget, space_count: usize) -> Self {
+        PrecisionTracker {
+            target,
+            previous_counts: vec![0; space_count],
+            share_sums: vec![0.0; space_count],
+            share_square_sums: vec![0.0; space_count],
+            batch_count: 0
+        }
+    }
+
+    pub fn is_batch_end(&self, turns_played: u32) -> bool {
+        turns_played.is_multiple_of(self.target.batch_turns)
+    }
+
+    //Takes the running landed counts at the end of a batch, true once every space is precise enough
+    pub fn record_batch(&mut self, landed_counts: impl Iterator<Item = u64>) -> bool {
+        let batch_counts: Vec<u64> = landed_counts
+            .zip(self.previous_counts.iter_mut())
+            .map(|(landed_count, previous_count)| landed_count - std::mem::replace(previous_count, landed_count))
+            .collect();
+
+        let batch_total: u64 = batch_counts.iter().sum();
+
+        if batch_total > 0 {
+            for (space, batch_count) in batch_counts.iter().enumerate() {
+                let share = *batch_count as f64 / batch_total as f64;
+
+                self.share_sums[space] += share;
+                self.share_square_sums[space] += share * share;
+            }
+
+            self.batch_count += 1;
+        }
+
+        self.is_target_reached()
+    }
+
+    pub fn finish(self) -> PrecisionReport {
+        PrecisionReport {
+            target: self.target,
+            batch_count: self.batch_count,
+            target_reached: self.is_target_reached(),
+            half_widths: self.get_half_widths()
+        }
+    }
+
+
+    pub fn is_target_reached(&self) -> bool {
+        self.batch_count >= MIN_BATCHES && self.get_half_widths().iter().all(|half_width| *half_width < self.target.half_width)
+    }
+
+    fn get_half_widths(&self) -> Vec<f64> {
+        if self.batch_count < 2 {
+            return vec![f64::INFINITY; self.share_sums.len()];
+        }
+
+        let batches = self.batch_count as f64;
+
+        self.share_sums.iter()
+            .zip(self.share_square_sums.iter())
+            .map(|(sum, square_sum)| {
+                let variance = ((square_sum - sum * sum / batches) / (batches - 1.0)).max(0.0);
+
+                self.target.z_score * (variance / batches).sqrt()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+
+//Turns between progress updates and checks for a stop request.  Cheap enough not to show up even on the fast engine
+pub(crate) const PROGRESS_INTERVAL_TURNS: u32 = 10_000;
+
+//ConsoleProgress redraws at most this often
+const CONSOLE_REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+
+
+//How far a run has got.  Runs with a precision target count towards the target's turn limit, so they usually finish early
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    //Totals across every game for a sweep, which can run to more turns than a single game ever does
+    pub turns_played: u64,
+    pub turn_count: u64,
+    //Turns already played when this run started, from a checkpoint.  Only the turns since then count towards the rate
+    pub turns_at_start: u64,
+    pub elapsed: Duration
+}
+
+impl Progress {
+    pub fn get_fraction_done(&self) -> f64 {
+        if self.turn_count == 0 {
+            return 1.0;
+        }
+
+        self.turns_played as f64 / self.turn_count as f64
+    }
+
+    pub fn get_turns_per_second(&self) -> f64 {
+        self.turns_played.saturating_sub(self.turns_at_start) as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    //Time left at the rate so far, None until there's a rate to go on
+    pub fn get_eta(&self) -> Option<Duration> {
+        if self.turns_played <= self.turns_at_start {
+            return None;
+        }
+
+        let turns_left = self.turn_count.saturating_sub(self.turns_played) as f64;
+
+        Some(Duration::from_secs_f64(turns_left / self.get_turns_per_second()))
+    }
+}
+
+
+
+//Registered with Simulation::progress, handed a Progress every PROGRESS_INTERVAL_TURNS turns and once more at the end
+pub trait ProgressReporter {
+    fn on_progress(&mut self, progress: &Progress);
+}
+
+
+
+//Built-in reporter that keeps a single progress line up to date on stderr, so it never gets mixed into the results on stdout
+pub struct ConsoleProgress {
+    last_redraw: Option<Instant>
+}
+
+impl ConsoleProgress {
+    pub fn new() -> Self {
+        ConsoleProgress { last_redraw: None }
+    }
+
+    //Moves off the progress line, call once the run is over
+    pub fn finish(&self) {
+        if self.last_redraw.is_some() {
+            eprintln!();
+        }
+    }
+}
+
+impl Default for ConsoleProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for ConsoleProgress {
+    fn on_progress(&mut self, progress: &Progress) {
+        let finished = progress.turns_played >= progress.turn_count;
+
+        if !finished && self.last_redraw.is_some_and(|last_redraw| last_redraw.elapsed() < CONSOLE_REDRAW_INTERVAL) {
+            return;
+        }
+
+        self.last_redraw = Some(Instant::now());
+
+        let filled = ((progress.get_fraction_done() * PROGRESS_BAR_WIDTH as f64) as usize).min(PROGRESS_BAR_WIDTH);
+        let eta = match progress.get_eta() {
+            Some(eta) => format_duration(eta),
+            None => String::from("--:--")
+        };
+
+        eprint!("\r[{}{}] {:5.1}%  {}/{} turns  {:.0} turns/s  ETA {}   ",
+            "#".repeat(filled),
+            ".".repeat(PROGRESS_BAR_WIDTH - filled),
+            progress.get_fraction_done() * 100.0,
+            progress.turns_played,
+            progress.turn_count,
+            progress.get_turns_per_second(),
+            eta);
+
+        let _ = io::stderr().flush();
+    }
+}
+
+
+
+//h:mm:ss, or m:ss under an hour
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+
+
+//Reports progress and watches for a stop request on behalf of a running engine
+pub(crate) struct RunMonitor<'a> {
+    reporter: Option<&'a mut dyn ProgressReporter>,
+    stop_flag: Option<&'a AtomicBool>,
+    start: Instant,
+    turns_at_start: u32
+}
+
+impl<'a> RunMonitor<'a> {
+    //turns_at_start is non-zero when resuming, so the rate only covers the turns played since
+    pub fn new(reporter: Option<&'a mut dyn ProgressReporter>, stop_flag: Option<&'a AtomicBool>, turns_at_start: u32) -> Self {
+        RunMonitor { reporter, stop_flag, start: Instant::now(), turns_at_start }
+    }
+
+    pub fn is_check_due(&self, turns_played: u32) -> bool {
+        turns_played.is_multiple_of(PROGRESS_INTERVAL_TURNS)
+    }
+
+    //Reports progress, true if the run has been asked to stop
+    pub fn check(&mut self, turns_played: u32, turn_count: u32) -> bool {
+        self.report(turns_played, turn_count);
+
+        self.stop_flag.is_some_and(|stop_flag| stop_flag.load(Ordering::Relaxed))
+    }
+
+    pub fn report(&mut self, turns_played: u32, turn_count: u32) {
+        if let Some(reporter) = self.reporter.as_mut() {
+            reporter.on_progress(&Progress {
+                turns_played: turns_played as u64,
+                turn_count: turn_count as u64,
+                turns_at_start: self.turns_at_start as u64,
+                elapsed: self.start.elapsed()
+            });
+        }
+    }
+}
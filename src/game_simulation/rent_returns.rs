@@ -0,0 +1,162 @@
+use crate::game_simulation::game_board::StreetDetails;
+use crate::game_simulation::simulation_results::SimulationResults;
+
+
+//How far a street has been developed.  Monopoly is an unimproved street whose owner holds the whole color group (double rent)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DevelopmentLevelEnum {
+    Unimproved,
+    Monopoly,
+    OneHouse,
+    TwoHouses,
+    ThreeHouses,
+    FourHouses,
+    Hotel
+}
+impl DevelopmentLevelEnum {
+    pub const ALL: [DevelopmentLevelEnum; 7] = [
+        DevelopmentLevelEnum::Unimproved,
+        DevelopmentLevelEnum::Monopoly,
+        DevelopmentLevelEnum::OneHouse,
+        DevelopmentLevelEnum::TwoHouses,
+        DevelopmentLevelEnum::ThreeHouses,
+        DevelopmentLevelEnum::FourHouses,
+        DevelopmentLevelEnum::Hotel
+    ];
+
+    pub fn get_level_name(&self) -> &'static str {
+        match self {
+            DevelopmentLevelEnum::Unimproved => "Unimproved",
+            DevelopmentLevelEnum::Monopoly => "Monopoly",
+            DevelopmentLevelEnum::OneHouse => "1 House",
+            DevelopmentLevelEnum::TwoHouses => "2 Houses",
+            DevelopmentLevelEnum::ThreeHouses => "3 Houses",
+            DevelopmentLevelEnum::FourHouses => "4 Houses",
+            DevelopmentLevelEnum::Hotel => "Hotel"
+        }
+    }
+
+    //Number of houses that have to be bought to reach this level (a hotel costs a 5th house)
+    fn get_houses_bought(&self) -> u32 {
+        match self {
+            DevelopmentLevelEnum::Unimproved | DevelopmentLevelEnum::Monopoly => 0,
+            DevelopmentLevelEnum::OneHouse => 1,
+            DevelopmentLevelEnum::TwoHouses => 2,
+            DevelopmentLevelEnum::ThreeHouses => 3,
+            DevelopmentLevelEnum::FourHouses => 4,
+            DevelopmentLevelEnum::Hotel => 5
+        }
+    }
+}
+
+
+impl StreetDetails {
+    pub fn get_rent(&self, level: DevelopmentLevelEnum) -> u32 {
+        match level {
+            DevelopmentLevelEnum::Unimproved => self.rents[0],
+            DevelopmentLevelEnum::Monopoly => self.rents[0] * 2,
+            _ => self.rents[level.get_houses_bought() as usize]
+        }
+    }
+
+    //Purchase price of the street plus every house (or hotel) built on it
+    pub fn get_investment(&self, level: DevelopmentLevelEnum) -> u32 {
+        self.price + self.house_cost * level.get_houses_bought()
+    }
+}
+
+
+
+pub struct RentReturnRow {
+    //The street, or the whole color group when playing with the even build rule
+    pub space_name: String,
+    pub level: DevelopmentLevelEnum,
+    pub landing_probability: f64,
+    pub expected_rent: f64,
+    pub investment: u32,
+    pub break_even_turns: f64,
+    pub roi_percent: f64
+}
+
+
+
+//Combines each street's simulated landing rate with its rent schedule.  Rows come back ranked by ROI, best first.
+//Under the even build rule houses go up evenly across a whole color group, so rows are per group instead of per street
+pub fn calculate_rent_returns(results: &SimulationResults) -> Vec<RentReturnRow> {
+    let mut rows = Vec::<RentReturnRow>::new();
+    let total_player_turns = results.get_total_player_turns();
+
+    if total_player_turns == 0 {
+        return rows;
+    }
+
+    //An owner sitting in jail can't collect under the no rent in jail rule, scale everything by the time spent out of jail
+    let collecting_fraction = if results.rules.no_rent_in_jail { 1.0 - results.get_jail_time_fraction() } else { 1.0 };
+
+    //Each entry is a row name along with the streets that make it up
+    let mut holdings = Vec::<(String, Vec<(f64, &StreetDetails)>)>::new();
+
+    for space in results.spaces.iter() {
+        let details = match &space.street_details {
+            Some(details) => details,
+            None => continue
+        };
+
+        //Landings per opponent turn.  Can include more than one landing per turn thanks to doubles
+        let landing_probability = space.landed_count as f64 / total_player_turns as f64;
+
+        let holding_name = if results.rules.even_build { space.group.colorize(space.group.get_group_name()).to_string() } else { space.get_colored_name().to_string() };
+
+        match holdings.iter_mut().find(|(name, _)| *name == holding_name) {
+            Some((_, streets)) => streets.push((landing_probability, details)),
+            None => holdings.push((holding_name, vec![(landing_probability, details)]))
+        }
+    }
+
+    for (holding_name, streets) in holdings.iter() {
+        let landing_probability: f64 = streets.iter().map(|(probability, _)| probability).sum();
+
+        for level in DevelopmentLevelEnum::ALL {
+            let expected_rent: f64 = streets.iter()
+                .map(|(probability, details)| probability * details.get_rent(level) as f64)
+                .sum::<f64>() * collecting_fraction;
+
+            let investment: u32 = streets.iter().map(|(_, details)| details.get_investment(level)).sum();
+
+            rows.push(RentReturnRow {
+                space_name: holding_name.clone(),
+                level,
+                landing_probability,
+                expected_rent,
+                investment,
+                break_even_turns: if expected_rent > 0.0 { investment as f64 / expected_rent } else { f64::INFINITY },
+                roi_percent: expected_rent * 100.0 / investment as f64
+            });
+        }
+    }
+
+    rows.sort_by(|a, b| b.roi_percent.total_cmp(&a.roi_percent));
+
+    rows
+}
+
+
+
+pub fn print_rent_returns(rows: &[RentReturnRow]) {
+    println!("---------------------RENT RETURNS--------------------");
+    println!("Rank|ROI per turn|Break even (turns)|Expected rent per turn|Landings per turn|Investment|Level|Space");
+
+    for (rank, row) in rows.iter().enumerate() {
+        println!("{}|{:.4}%|{:.1}|${:.2}|{:.4}|${}|{}|{}",
+            rank + 1,
+            row.roi_percent,
+            row.break_even_turns,
+            row.expected_rent,
+            row.landing_probability,
+            row.investment,
+            row.level.get_level_name(),
+            row.space_name);
+    }
+
+    println!("-----------------------------------------------------");
+}
@@ -0,0 +1,392 @@
+use std::io::{self, BufRead, Write};
+
+use crate::game_simulation::Simulation;
+use crate::game_simulation::chance_source::ChanceSource;
+use crate::game_simulation::game_board::CardDeckEnum;
+use crate::game_simulation::game_cards::CardDecks;
+use crate::game_simulation::simulation_events::{SimulationEventEnum, SimulationObserver};
+use crate::game_simulation::simulation_results::SimulationResults;
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::speed_die::SpeedDieEnum;
+use crate::game_simulation::dice_model::{self, DiceRoll};
+
+
+//Bump whenever the log format changes, older logs are rejected rather than misread
+pub const REPLAY_LOG_VERSION: u32 = 1;
+
+const REPLAY_LOG_HEADER: &str = "MONOPOLY-REPLAY";
+
+
+
+//One roll of the dice, the cards it caused to be drawn, and where the player ended up once everything resolved
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ReplayRoll {
+    //None for a bus ticket move, which takes the place of a roll
+    pub roll: Option<DiceRoll>,
+    pub speed_die: Option<SpeedDieEnum>,
+    pub card_draws: Vec<(CardDeckEnum, usize)>,
+    pub final_space: u8
+}
+
+
+
+//Everything needed to play a game back through the rules engine and check it ends up in the same place
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ReplayLog {
+    pub player_count: u32,
+    pub turn_count: u32,
+    //Informational only, a replay never touches the RNG
+    pub seed: u64,
+    pub edition: GameEditionEnum,
+    //Informational only, like the seed
+    pub dice_model: String,
+    pub rules: RuleSet,
+    pub rolls: Vec<ReplayRoll>,
+    pub final_positions: Vec<u8>,
+    pub landed_counts: Vec<u64>
+}
+
+impl ReplayLog {
+    //Text format, one roll per line:  <dice joined with +> [s<speed die>] [ch<card>|cc<card>]... <final space>
+    //A bus ticket move replaces the dice with 'bus'
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "{} {}", REPLAY_LOG_HEADER, REPLAY_LOG_VERSION)?;
+        writeln!(writer, "players {}", self.player_count)?;
+        writeln!(writer, "turns {}", self.turn_count)?;
+        writeln!(writer, "seed {}", self.seed)?;
+        writeln!(writer, "edition {}", self.edition.get_edition_name())?;
+        writeln!(writer, "dice {}", self.dice_model)?;
+        writeln!(writer, "rules {}", self.rules.to_spec())?;
+
+        for roll in self.rolls.iter() {
+            match roll.roll {
+                Some(dice_roll) => write!(writer, "{}", dice_roll.to_spec())?,
+                None => write!(writer, "bus")?
+            }
+
+            match roll.speed_die {
+                Some(SpeedDieEnum::Number(number)) => write!(writer, " s{}", number)?,
+                Some(SpeedDieEnum::MrMonopoly) => write!(writer, " sM")?,
+                Some(SpeedDieEnum::Bus) => write!(writer, " sB")?,
+                None => {}
+            }
+
+            for (deck, card_index) in roll.card_draws.iter() {
+                match deck {
+                    CardDeckEnum::Chance => write!(writer, " ch{}", card_index)?,
+                    CardDeckEnum::CommunityChest => write!(writer, " cc{}", card_index)?
+                }
+            }
+
+            writeln!(writer, " {}", roll.final_space)?;
+        }
+
+        writeln!(writer, "final {}", join_numbers(&self.final_positions))?;
+        writeln!(writer, "landed {}", join_numbers(&self.landed_counts))?;
+
+        Ok(())
+    }
+
+
+    pub fn read_from(reader: impl BufRead) -> Result<Self, String> {
+        let mut lines = reader.lines().enumerate();
+
+        let mut next_line = || -> Result<(usize, String), String> {
+            match lines.next() {
+                Some((line_idx, Ok(line))) => Ok((line_idx + 1, line)),
+                Some((line_idx, Err(e))) => Err(format!("Error reading line {}: {}", line_idx + 1, e)),
+                None => Err(String::from("Replay log ended early"))
+            }
+        };
+
+        let (_, header) = next_line()?;
+        let expected_header = format!("{} {}", REPLAY_LOG_HEADER, REPLAY_LOG_VERSION);
+
+        if header.trim() != expected_header {
+            return Err(format!("Not a version {} replay log (header was '{}')", REPLAY_LOG_VERSION, header.trim()));
+        }
+
+        let player_count = parse_keyed_value(next_line()?, "players")?;
+        let turn_count = parse_keyed_value(next_line()?, "turns")?;
+        let seed = parse_keyed_value(next_line()?, "seed")?;
+        let edition_name: String = parse_keyed_value(next_line()?, "edition")?;
+        let edition = GameEditionEnum::from_edition_name(&edition_name).ok_or(format!("Unknown edition '{}'", edition_name))?;
+        let dice_model: String = parse_keyed_value(next_line()?, "dice")?;
+        dice_model::from_spec(&dice_model)?;
+        let rules_spec: String = parse_keyed_value(next_line()?, "rules")?;
+        let rules = RuleSet::from_spec(&rules_spec)?;
+
+        let mut rolls = Vec::<ReplayRoll>::new();
+
+        loop {
+            let (line_num, line) = next_line()?;
+
+            if let Some(positions) = line.strip_prefix("final") {
+                let final_positions = parse_numbers(positions, line_num)?;
+                let landed_counts = parse_keyed_numbers(next_line()?, "landed")?;
+
+                return Ok(ReplayLog { player_count, turn_count, seed, edition, dice_model, rules, rolls, final_positions, landed_counts });
+            }
+
+            rolls.push(parse_roll(&line, line_num)?);
+        }
+    }
+}
+
+
+
+//Observer that writes down every roll as it happens.  Hand it to finish() along with the results once the simulation is done
+#[derive(Default)]
+pub struct ReplayRecorder {
+    rolls: Vec<ReplayRoll>
+}
+
+impl ReplayRecorder {
+    pub fn new() -> Self {
+        ReplayRecorder { rolls: Vec::new() }
+    }
+
+    pub fn finish(self, results: &SimulationResults) -> ReplayLog {
+        ReplayLog {
+            player_count: results.player_count,
+            turn_count: results.turn_count,
+            seed: results.seed,
+            edition: results.edition,
+            dice_model: results.dice_model.clone(),
+            rules: results.rules.clone(),
+            rolls: self.rolls,
+            final_positions: results.final_positions.clone(),
+            landed_counts: results.spaces.iter().map(|space| space.landed_count).collect()
+        }
+    }
+}
+
+impl SimulationObserver for ReplayRecorder {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        match event {
+            SimulationEventEnum::DiceRolled { roll, .. } => {
+                self.rolls.push(ReplayRoll { roll: Some(*roll), speed_die: None, card_draws: Vec::new(), final_space: 0 });
+            },
+            SimulationEventEnum::BusTicketUsed { .. } => {
+                self.rolls.push(ReplayRoll { roll: None, speed_die: None, card_draws: Vec::new(), final_space: 0 });
+            },
+            SimulationEventEnum::SpeedDieRolled { face, .. } => {
+                if let Some(roll) = self.rolls.last_mut() {
+                    roll.speed_die = Some(*face);
+                }
+            },
+            SimulationEventEnum::CardDrawn { deck, card_index, .. } => {
+                if let Some(roll) = self.rolls.last_mut() {
+                    roll.card_draws.push((*deck, *card_index));
+                }
+            },
+            SimulationEventEnum::RollFinished { final_space, .. } => {
+                if let Some(roll) = self.rolls.last_mut() {
+                    roll.final_space = *final_space;
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+
+
+//Feeds the recorded dice and cards back into the rules engine in place of the RNG
+pub(crate) struct ReplayChanceSource<'a> {
+    log: &'a ReplayLog,
+    next_roll: usize,
+    next_card: usize
+}
+
+impl<'a> ReplayChanceSource<'a> {
+    pub fn new(log: &'a ReplayLog) -> Self {
+        ReplayChanceSource { log, next_roll: 0, next_card: 0 }
+    }
+
+    //Moves on to the next line of the log, cards are drawn from the line most recently moved on to
+    fn next_log_roll(&mut self) -> Option<&'a ReplayRoll> {
+        let roll = self.log.rolls.get(self.next_roll);
+
+        self.next_roll += 1;
+        self.next_card = 0;
+
+        roll
+    }
+}
+
+impl ChanceSource for ReplayChanceSource<'_> {
+    fn roll_dice(&mut self) -> DiceRoll {
+        let roll = self.next_log_roll().and_then(|roll| roll.roll);
+
+        //Running off the end of the log means the replay has already diverged.  Keep going with a harmless non-double roll,
+        //the comparison afterwards reports where things went wrong
+        roll.unwrap_or(DiceRoll::new(&[1, 2]))
+    }
+
+    fn roll_speed_die(&mut self) -> SpeedDieEnum {
+        let speed_die = self.next_roll.checked_sub(1)
+            .and_then(|roll_idx| self.log.rolls.get(roll_idx))
+            .and_then(|roll| roll.speed_die);
+
+        speed_die.unwrap_or(SpeedDieEnum::Number(1))
+    }
+
+    fn use_bus_ticket(&mut self) {
+        self.next_log_roll();
+    }
+
+    fn draw_card(&mut self, deck: CardDeckEnum, _card_decks: &mut CardDecks) -> usize {
+        let card_draw = self.next_roll.checked_sub(1)
+            .and_then(|roll_idx| self.log.rolls.get(roll_idx))
+            .and_then(|roll| roll.card_draws.get(self.next_card));
+
+        self.next_card += 1;
+
+        match card_draw {
+            Some((logged_deck, card_index)) if *logged_deck == deck => *card_index,
+            _ => 0
+        }
+    }
+}
+
+
+
+//Plays a log back through the rules engine and checks every roll, the final positions and the landed counts all match
+pub fn verify_replay(log: &ReplayLog) -> Result<SimulationResults, String> {
+    validate_log(log)?;
+
+    let mut recorder = ReplayRecorder::new();
+
+    let results = Simulation::new()
+        .replaying(log)
+        .add_observer(&mut recorder)
+        .run();
+
+    let replayed = recorder.finish(&results);
+
+    for (roll_idx, (logged_roll, replayed_roll)) in log.rolls.iter().zip(replayed.rolls.iter()).enumerate() {
+        if logged_roll != replayed_roll {
+            return Err(format!("Roll {} diverged.  Log: {:?}  Replay: {:?}", roll_idx + 1, logged_roll, replayed_roll));
+        }
+    }
+
+    if log.rolls.len() != replayed.rolls.len() {
+        return Err(format!("Log has {} rolls but the replay took {}", log.rolls.len(), replayed.rolls.len()));
+    }
+
+    if log.final_positions != replayed.final_positions {
+        return Err(format!("Final positions differ.  Log: {:?}  Replay: {:?}", log.final_positions, replayed.final_positions));
+    }
+
+    if log.landed_counts != replayed.landed_counts {
+        return Err(format!("Landed counts differ.  Log: {:?}  Replay: {:?}", log.landed_counts, replayed.landed_counts));
+    }
+
+    Ok(results)
+}
+
+
+
+//Catch anything that would make the rules engine panic before handing the log to it
+fn validate_log(log: &ReplayLog) -> Result<(), String> {
+    let mut card_decks = log.edition.create_card_decks();
+
+    for (roll_idx, roll) in log.rolls.iter().enumerate() {
+        if let Some(speed_die @ SpeedDieEnum::Number(number)) = roll.speed_die {
+            if !(1..=3).contains(&number) {
+                return Err(format!("Roll {} has an impossible speed die: {:?}", roll_idx + 1, speed_die));
+            }
+        }
+
+        for (deck, card_index) in roll.card_draws.iter() {
+            if *card_index >= card_decks.get_deck(*deck).get_card_count() {
+                return Err(format!("Roll {} draws card {} which isn't in the {:?} deck", roll_idx + 1, card_index, deck));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+
+fn parse_roll(line: &str, line_num: usize) -> Result<ReplayRoll, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    if tokens.len() < 2 {
+        return Err(format!("Line {}: expected '<dice> [speed die] [cards] <final space>', got '{}'", line_num, line));
+    }
+
+    fn parse_token<T: std::str::FromStr<Err = std::num::ParseIntError>>(token: &str, line_num: usize) -> Result<T, String> {
+        token.parse().map_err(|e| format!("Line {}: invalid number '{}': {}", line_num, token, e))
+    }
+
+    let roll =
+        match tokens[0] {
+            "bus" => None,
+            dice => Some(DiceRoll::from_spec(dice).map_err(|e| format!("Line {}: {}", line_num, e))?)
+        };
+
+    let mut speed_die = None;
+    let mut card_draws = Vec::<(CardDeckEnum, usize)>::new();
+
+    for token in tokens[1..tokens.len() - 1].iter() {
+        if let Some(face) = token.strip_prefix('s') {
+            speed_die = match face {
+                "M" => Some(SpeedDieEnum::MrMonopoly),
+                "B" => Some(SpeedDieEnum::Bus),
+                _ => match parse_token(face, line_num)? {
+                    number @ 1..=3 => Some(SpeedDieEnum::Number(number)),
+                    number => return Err(format!("Line {}: speed die faces are 1, 2, 3, M or B, got {}", line_num, number))
+                }
+            };
+
+            continue;
+        }
+
+        let card_draw = match (token.strip_prefix("ch"), token.strip_prefix("cc")) {
+            (Some(card_index), _) => (CardDeckEnum::Chance, parse_token(card_index, line_num)?),
+            (_, Some(card_index)) => (CardDeckEnum::CommunityChest, parse_token(card_index, line_num)?),
+            _ => return Err(format!("Line {}: invalid card draw '{}'", line_num, token))
+        };
+
+        card_draws.push(card_draw);
+    }
+
+    Ok(ReplayRoll {
+        roll,
+        speed_die,
+        card_draws,
+        final_space: parse_token(tokens[tokens.len() - 1], line_num)?
+    })
+}
+
+
+pub(super) fn parse_keyed_value<T: std::str::FromStr>((line_num, line): (usize, String), key: &str) -> Result<T, String> {
+    match line.split_once(' ') {
+        Some((line_key, value)) if line_key == key => value.trim().parse().map_err(|_| format!("Line {}: invalid {} '{}'", line_num, key, value.trim())),
+        _ => Err(format!("Line {}: expected '{} <value>', got '{}'", line_num, key, line))
+    }
+}
+
+
+pub(super) fn parse_keyed_numbers<T: std::str::FromStr>((line_num, line): (usize, String), key: &str) -> Result<Vec<T>, String> {
+    match line.strip_prefix(key) {
+        Some(values) => parse_numbers(values, line_num),
+        None => Err(format!("Line {}: expected '{} <values>', got '{}'", line_num, key, line))
+    }
+}
+
+
+pub(super) fn parse_numbers<T: std::str::FromStr>(values: &str, line_num: usize) -> Result<Vec<T>, String> {
+    values.split_whitespace()
+        .map(|value| value.parse().map_err(|_| format!("Line {}: invalid number '{}'", line_num, value)))
+        .collect()
+}
+
+
+pub(super) fn join_numbers<T: ToString>(values: &[T]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(" ")
+}
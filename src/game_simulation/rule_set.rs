@@ -0,0 +1,104 @@
+//Rule switches for a simulation, covering the official rules and the common house rules.  The default matches how the
+//simulator has always played: three doubles send you to jail, and you pay the fine to leave jail on your very next turn
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RuleSet {
+    //Rolling this many doubles in a row sends you to jail.  0 turns the rule off
+    pub max_doubles_before_jail: u8,
+    //Turns spent trying to roll doubles out of jail before being forced to pay the fine.  0 pays the fine straight away
+    pub max_jail_turns: u8,
+    pub jail_fine: u32,
+    pub go_salary: u32,
+    //Taxes, fees and fines go into a pot in the middle of the board, landing on Free Parking collects it
+    pub free_parking_jackpot: bool,
+    //Landing exactly on Go pays double salary
+    pub double_salary_on_go: bool,
+    //Owners in jail can't collect rent.  Nobody pays rent during a game, so this only scales the rent return table by the time
+    //spent out of jail and leaves every landing the same
+    pub no_rent_in_jail: bool,
+    //Houses have to be built evenly across a color group.  Nothing gets built during a game either, so this only works the rent
+    //return table out per group instead of per street
+    pub even_build: bool,
+    //Mega edition speed die (1-3, Mr. Monopoly, bus), rolled alongside the white dice once a player has been round the board
+    pub speed_die: bool
+}
+
+impl RuleSet {
+    //The rules as printed in the box, including up to three turns in jail trying to roll doubles
+    pub fn official() -> Self {
+        RuleSet { max_jail_turns: 3, ..RuleSet::default() }
+    }
+
+
+    //Parses a comma separated list of rule settings, e.g. "official,max_doubles=2,free_parking_jackpot".  Settings are
+    //applied in order on top of the default rules.  A bare switch name turns it on
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        RuleSet::default().with_spec(spec)
+    }
+
+
+    //Same as from_spec, but applies the settings on top of these rules (an edition's defaults, say)
+    pub fn with_spec(self, spec: &str) -> Result<Self, String> {
+        let mut rules = self;
+
+        for setting in spec.split(',').map(|setting| setting.trim()).filter(|setting| !setting.is_empty()) {
+            let (key, value) = match setting.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (setting, "true")
+            };
+
+            match key {
+                "default" => rules = RuleSet::default(),
+                "official" => rules = RuleSet::official(),
+                "max_doubles" => rules.max_doubles_before_jail = parse_rule_value(key, value)?,
+                "max_jail_turns" => rules.max_jail_turns = parse_rule_value(key, value)?,
+                "jail_fine" => rules.jail_fine = parse_rule_value(key, value)?,
+                "go_salary" => rules.go_salary = parse_rule_value(key, value)?,
+                "free_parking_jackpot" => rules.free_parking_jackpot = parse_rule_value(key, value)?,
+                "double_salary_on_go" => rules.double_salary_on_go = parse_rule_value(key, value)?,
+                "no_rent_in_jail" => rules.no_rent_in_jail = parse_rule_value(key, value)?,
+                "even_build" => rules.even_build = parse_rule_value(key, value)?,
+                "speed_die" => rules.speed_die = parse_rule_value(key, value)?,
+                _ => return Err(format!("Unknown rule '{}'", key))
+            }
+        }
+
+        Ok(rules)
+    }
+
+
+    //Round trips through from_spec
+    pub fn to_spec(&self) -> String {
+        format!("max_doubles={},max_jail_turns={},jail_fine={},go_salary={},free_parking_jackpot={},double_salary_on_go={},no_rent_in_jail={},even_build={},speed_die={}",
+            self.max_doubles_before_jail,
+            self.max_jail_turns,
+            self.jail_fine,
+            self.go_salary,
+            self.free_parking_jackpot,
+            self.double_salary_on_go,
+            self.no_rent_in_jail,
+            self.even_build,
+            self.speed_die)
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            max_doubles_before_jail: 3,
+            max_jail_turns: 0,
+            jail_fine: 50,
+            go_salary: 200,
+            free_parking_jackpot: false,
+            double_salary_on_go: false,
+            no_rent_in_jail: false,
+            even_build: false,
+            speed_die: false
+        }
+    }
+}
+
+
+
+fn parse_rule_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value '{}' for rule '{}'", value, key))
+}
@@ -0,0 +1,181 @@
+use std::io::{self, BufRead, Write};
+
+use crate::game_simulation::game_board::SpaceGroupEnum;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::replay_log::{parse_keyed_value, parse_numbers};
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::simulation_results::SimulationResults;
+
+
+//Bump whenever the file format changes, older files are rejected rather than misread
+pub const SAVED_RESULTS_VERSION: u32 = 1;
+
+const SAVED_RESULTS_HEADER: &str = "MONOPOLY-RESULTS";
+
+
+
+//One space's figures in a saved results file
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedSpace {
+    pub name: String,
+    pub group: SpaceGroupEnum,
+    pub landed_count: u64,
+    //Confidence interval half width on the landing probability, at the file's confidence level
+    pub half_width: f64
+}
+
+
+
+//A finished run's landing counts and confidence intervals along with the settings that produced it, saved so two runs can be
+//compared later (see comparison).  Only runs with a precision report can be saved, the intervals are what make a comparison
+//more than eyeballing two sets of numbers
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedResults {
+    pub player_count: u32,
+    pub turn_count: u32,
+    pub seed: u64,
+    pub edition: GameEditionEnum,
+    pub dice_model: String,
+    pub rules: RuleSet,
+    pub confidence: f64,
+    pub batch_count: u32,
+    pub batch_turns: u32,
+    pub spaces: Vec<SavedSpace>
+}
+
+impl SavedResults {
+    pub fn from_results(results: &SimulationResults) -> Result<Self, String> {
+        let precision = results.precision.as_ref()
+            .ok_or("Results without confidence intervals can't be saved, run with a precision target or PrecisionTarget::fixed_turns")?;
+
+        let spaces = results.spaces.iter()
+            .zip(precision.half_widths.iter())
+            .map(|(space, half_width)| SavedSpace { name: space.name.clone(), group: space.group, landed_count: space.landed_count, half_width: *half_width })
+            .collect();
+
+        Ok(SavedResults {
+            player_count: results.player_count,
+            turn_count: results.turn_count,
+            seed: results.seed,
+            edition: results.edition,
+            dice_model: results.dice_model.clone(),
+            rules: results.rules.clone(),
+            confidence: precision.target.get_confidence(),
+            batch_count: precision.batch_count,
+            batch_turns: precision.target.get_batch_turns(),
+            spaces
+        })
+    }
+
+
+    pub fn get_total_landed(&self) -> u64 {
+        self.spaces.iter().map(|space| space.landed_count).sum()
+    }
+
+    pub fn get_landing_probability(&self, space_index: usize) -> f64 {
+        match (self.spaces.get(space_index), self.get_total_landed()) {
+            (Some(space), total_landed) if total_landed > 0 => space.landed_count as f64 / total_landed as f64,
+            _ => 0.0
+        }
+    }
+
+    //Edition, players, turns, dice and rules on one line
+    pub fn get_settings_summary(&self) -> String {
+        format!("{}, {} players, {} turns, seed {}, dice {}, rules {}",
+            self.edition.get_edition_name(),
+            self.player_count,
+            self.turn_count,
+            self.seed,
+            self.dice_model,
+            self.rules.to_spec())
+    }
+
+
+    //Text format, one space per line after the settings:  space <landed count> <half width> <group>|<name>
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "{} {}", SAVED_RESULTS_HEADER, SAVED_RESULTS_VERSION)?;
+        writeln!(writer, "players {}", self.player_count)?;
+        writeln!(writer, "turns {}", self.turn_count)?;
+        writeln!(writer, "seed {}", self.seed)?;
+        writeln!(writer, "edition {}", self.edition.get_edition_name())?;
+        writeln!(writer, "dice {}", self.dice_model)?;
+        writeln!(writer, "rules {}", self.rules.to_spec())?;
+        writeln!(writer, "confidence {}", self.confidence)?;
+        writeln!(writer, "batches {} {}", self.batch_count, self.batch_turns)?;
+
+        for space in self.spaces.iter() {
+            writeln!(writer, "space {} {} {}|{}", space.landed_count, space.half_width, space.group.get_group_name(), space.name)?;
+        }
+
+        Ok(())
+    }
+
+
+    pub fn read_from(reader: impl BufRead) -> Result<Self, String> {
+        let mut lines = reader.lines().enumerate();
+
+        let mut next_line = || -> Result<(usize, String), String> {
+            match lines.next() {
+                Some((line_idx, Ok(line))) => Ok((line_idx + 1, line)),
+                Some((line_idx, Err(e))) => Err(format!("Error reading line {}: {}", line_idx + 1, e)),
+                None => Err(String::from("Results file ended early"))
+            }
+        };
+
+        let (_, header) = next_line()?;
+        let expected_header = format!("{} {}", SAVED_RESULTS_HEADER, SAVED_RESULTS_VERSION);
+
+        if header.trim() != expected_header {
+            return Err(format!("Not a version {} results file (header was '{}')", SAVED_RESULTS_VERSION, header.trim()));
+        }
+
+        let player_count = parse_keyed_value(next_line()?, "players")?;
+        let turn_count = parse_keyed_value(next_line()?, "turns")?;
+        let seed = parse_keyed_value(next_line()?, "seed")?;
+        let edition_name: String = parse_keyed_value(next_line()?, "edition")?;
+        let edition = GameEditionEnum::from_edition_name(&edition_name).ok_or(format!("Unknown edition '{}'", edition_name))?;
+        let dice_model = parse_keyed_value(next_line()?, "dice")?;
+        let rules_spec: String = parse_keyed_value(next_line()?, "rules")?;
+        let rules = RuleSet::from_spec(&rules_spec)?;
+        let confidence = parse_keyed_value(next_line()?, "confidence")?;
+
+        let (line_num, batches_line) = next_line()?;
+        let (batch_count, batch_turns) = match batches_line.strip_prefix("batches").map(|values| parse_numbers::<u32>(values, line_num)) {
+            Some(Ok(values)) if values.len() == 2 => (values[0], values[1]),
+            _ => return Err(format!("Line {}: expected 'batches <count> <turns>', got '{}'", line_num, batches_line))
+        };
+
+        let mut spaces = Vec::new();
+
+        for (line_idx, line) in lines {
+            let line = line.map_err(|e| format!("Error reading line {}: {}", line_idx + 1, e))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            spaces.push(parse_space(&line, line_idx + 1)?);
+        }
+
+        if spaces.is_empty() {
+            return Err(String::from("Results file has no spaces"));
+        }
+
+        Ok(SavedResults { player_count, turn_count, seed, edition, dice_model, rules, confidence, batch_count, batch_turns, spaces })
+    }
+}
+
+
+
+fn parse_space(line: &str, line_num: usize) -> Result<SavedSpace, String> {
+    let invalid = || format!("Line {}: expected 'space <landed count> <half width> <group>|<name>', got '{}'", line_num, line);
+
+    let mut parts = line.strip_prefix("space ").ok_or_else(invalid)?.splitn(3, ' ');
+
+    let landed_count = parts.next().and_then(|value| value.parse().ok()).ok_or_else(invalid)?;
+    let half_width = parts.next().and_then(|value| value.parse().ok()).ok_or_else(invalid)?;
+    let (group_name, name) = parts.next().and_then(|rest| rest.split_once('|')).ok_or_else(invalid)?;
+    let group = SpaceGroupEnum::from_group_name(group_name).ok_or(format!("Line {}: unknown group '{}'", line_num, group_name))?;
+
+    Ok(SavedSpace { name: name.to_string(), group, landed_count, half_width })
+}
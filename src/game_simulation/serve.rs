@@ -0,0 +1,612 @@
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Map, Value};
+
+use crate::game_simulation::Simulation;
+use crate::game_simulation::dice_model::{self, DiceModel, StandardDice};
+use crate::game_simulation::game_board::SpaceGroupEnum;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::simulation_results::{RuleStatistics, SimulationResults};
+
+
+//Request bodies bigger than this are turned away, a job's parameters are a few hundred bytes
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+//Most games a single job may ask for
+const MAX_GAMES: u32 = 100_000;
+
+const DEFAULT_PLAYERS: u32 = 4;
+const DEFAULT_TURNS: u32 = 1000;
+const DEFAULT_GAMES: u32 = 1;
+
+//Slow or stalled clients are dropped rather than tying up a connection thread
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+//Connections are answered this many at a time.  Past that they wait for a free thread, and once this many more are waiting the
+//rest are left with the OS until the queue has room
+const CONNECTION_THREADS: usize = 8;
+const MAX_WAITING_CONNECTIONS: usize = 64;
+
+//Finished jobs kept for their status and results.  The oldest go first once there are more than this
+pub const MAX_FINISHED_JOBS: usize = 1000;
+
+
+
+//Settings for the serve command
+pub struct ServeOptions {
+    pub port: u16,
+    pub worker_count: usize
+}
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum JobStatusEnum {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled
+}
+
+impl JobStatusEnum {
+    fn get_status_name(&self) -> &'static str {
+        match self {
+            JobStatusEnum::Queued => "queued",
+            JobStatusEnum::Running => "running",
+            JobStatusEnum::Done => "done",
+            JobStatusEnum::Failed => "failed",
+            JobStatusEnum::Cancelled => "cancelled"
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self, JobStatusEnum::Done | JobStatusEnum::Failed | JobStatusEnum::Cancelled)
+    }
+}
+
+
+
+//What a job was asked to play.  Game g plays with seed + g, so a job with a seed always gives the same results
+#[derive(Clone)]
+pub struct JobRequest {
+    player_count: u32,
+    turn_count: u32,
+    game_count: u32,
+    seed: u64,
+    edition: GameEditionEnum,
+    rules: RuleSet,
+    dice_spec: String
+}
+
+impl JobRequest {
+    //Takes a JSON object with any of players, turns, games, seed, board (an edition name), rules and dice.  Anything left out
+    //gets the same default as the command line
+    pub fn from_json(body: &Value) -> Result<Self, String> {
+        let fields = body.as_object().ok_or("Expected a JSON object of job parameters")?;
+
+        let mut request = JobRequest {
+            player_count: DEFAULT_PLAYERS,
+            turn_count: DEFAULT_TURNS,
+            game_count: DEFAULT_GAMES,
+            //Kept to 32 bits so it reads back exactly in clients that hold every JSON number as a double
+            seed: rand::random::<u32>() as u64,
+            edition: GameEditionEnum::Classic,
+            rules: RuleSet::default(),
+            dice_spec: StandardDice.get_model_spec()
+        };
+        let mut rules_spec = "";
+
+        if let Some(edition_name) = fields.get("board") {
+            let edition_name = edition_name.as_str().ok_or("board must be an edition name, e.g. \"classic\"")?;
+            request.edition = GameEditionEnum::from_edition_name(edition_name).ok_or(format!("Unknown board '{}'", edition_name))?;
+        }
+
+        for (key, value) in fields {
+            match key.as_str() {
+                "players" => request.player_count = match value.as_u64() {
+                    Some(count) if (2..=8).contains(&count) => count as u32,
+                    _ => return Err(String::from("players must be between 2 and 8"))
+                },
+                "turns" => request.turn_count = get_count(key, value, u32::MAX)?,
+                "games" => request.game_count = get_count(key, value, MAX_GAMES)?,
+                "seed" => request.seed = value.as_u64().ok_or("seed must be a whole number")?,
+                "rules" => rules_spec = value.as_str().ok_or("rules must be a string, e.g. \"official,free_parking_jackpot\"")?,
+                "dice" => {
+                    let dice_spec = value.as_str().ok_or("dice must be a string, e.g. \"2d6\"")?;
+                    request.dice_spec = dice_model::from_spec(dice_spec)?.get_model_spec();
+                },
+                "board" => (),
+                _ => return Err(format!("Unknown job parameter '{}'", key))
+            }
+        }
+
+        //Rules go on top of the edition's defaults, the same as --rules
+        request.rules = request.edition.get_default_rules().with_spec(rules_spec)?;
+
+        Ok(request)
+    }
+
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "players": self.player_count,
+            "turns": self.turn_count,
+            "games": self.game_count,
+            "seed": self.seed,
+            "board": self.edition.get_edition_name(),
+            "rules": self.rules.to_spec(),
+            "dice": self.dice_spec
+        })
+    }
+}
+
+
+fn get_count(key: &str, value: &Value, max: u32) -> Result<u32, String> {
+    match value.as_u64() {
+        Some(count) if count > 0 && count <= max as u64 => Ok(count as u32),
+        _ => Err(format!("{} must be a whole number between 1 and {}", key, max))
+    }
+}
+
+
+
+struct Job {
+    request: JobRequest,
+    status: JobStatusEnum,
+    games_played: u32,
+    results: Option<Value>,
+    error: Option<String>,
+    //Set by DELETE, stops the game under way within a few thousand turns
+    cancel: Arc<AtomicBool>
+}
+
+impl Job {
+    fn get_status_json(&self, job_id: u64) -> Value {
+        let mut status = json!({
+            "id": job_id,
+            "status": self.status.get_status_name(),
+            "games_played": self.games_played,
+            "request": self.request.to_json()
+        });
+
+        if let Some(error) = &self.error {
+            status["error"] = json!(error);
+        }
+
+        status
+    }
+}
+
+
+
+//Every job still running or waiting, the most recently finished ones, and the queue the workers take jobs from in order
+pub struct JobBoard {
+    jobs: Mutex<JobList>,
+    job_queued: Condvar,
+    max_finished_jobs: usize
+}
+
+struct JobList {
+    next_job_id: u64,
+    jobs: BTreeMap<u64, Job>,
+    queue: VecDeque<u64>
+}
+
+impl JobBoard {
+    pub fn new(max_finished_jobs: usize) -> Self {
+        JobBoard {
+            jobs: Mutex::new(JobList { next_job_id: 1, jobs: BTreeMap::new(), queue: VecDeque::new() }),
+            job_queued: Condvar::new(),
+            max_finished_jobs
+        }
+    }
+
+
+    fn submit(&self, request: JobRequest) -> Value {
+        let mut job_list = self.jobs.lock().unwrap();
+
+        let job_id = job_list.next_job_id;
+        job_list.next_job_id += 1;
+
+        let job = Job { request, status: JobStatusEnum::Queued, games_played: 0, results: None, error: None, cancel: Arc::new(AtomicBool::new(false)) };
+        let status = job.get_status_json(job_id);
+
+        job_list.jobs.insert(job_id, job);
+        job_list.queue.push_back(job_id);
+        self.job_queued.notify_one();
+
+        //Job ids only go up, so the first finished jobs in the map are the oldest
+        let finished_jobs: Vec<u64> = job_list.jobs.iter().filter(|(_, job)| job.status.is_finished()).map(|(job_id, _)| *job_id).collect();
+        for job_id in finished_jobs.iter().take(finished_jobs.len().saturating_sub(self.max_finished_jobs)) {
+            job_list.jobs.remove(job_id);
+        }
+
+        status
+    }
+
+
+    //Blocks until there's a job to run, marks it as running and hands back what it needs
+    fn take_next_job(&self) -> (u64, JobRequest, Arc<AtomicBool>) {
+        let mut job_list = self.jobs.lock().unwrap();
+
+        loop {
+            while let Some(job_id) = job_list.queue.pop_front() {
+                if let Some(job) = job_list.jobs.get_mut(&job_id).filter(|job| job.status == JobStatusEnum::Queued) {
+                    job.status = JobStatusEnum::Running;
+                    return (job_id, job.request.clone(), Arc::clone(&job.cancel));
+                }
+            }
+
+            job_list = self.job_queued.wait(job_list).unwrap();
+        }
+    }
+
+
+    fn update_job(&self, job_id: u64, update: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.jobs.lock().unwrap().jobs.get_mut(&job_id) {
+            update(job);
+        }
+    }
+}
+
+
+
+//Landings and money added up across a job's games
+struct JobTotals {
+    landed_counts: Vec<u64>,
+    rule_statistics: RuleStatistics,
+    player_turns: u64,
+    used_fast_engine: bool
+}
+
+impl JobTotals {
+    fn add_game(&mut self, results: &SimulationResults) {
+        for (landed_count, space) in self.landed_counts.iter_mut().zip(results.spaces.iter()) {
+            *landed_count += space.landed_count;
+        }
+
+        let (totals, game) = (&mut self.rule_statistics, &results.rule_statistics);
+        totals.go_bonus_paid += game.go_bonus_paid;
+        totals.taxes_paid += game.taxes_paid;
+        totals.card_fees_paid += game.card_fees_paid;
+        totals.jail_fines_paid += game.jail_fines_paid;
+        totals.free_parking_paid_out += game.free_parking_paid_out;
+        totals.jail_turns_served += game.jail_turns_served;
+
+        self.player_turns += results.get_total_player_turns();
+    }
+
+
+    fn to_json(&self, request: &JobRequest) -> Value {
+        let board = request.edition.create_board();
+        let total_landed = self.landed_counts.iter().sum::<u64>().max(1) as f64;
+        let player_turns = self.player_turns.max(1) as f64;
+
+        let spaces: Vec<Value> = board.iter()
+            .zip(self.landed_counts.iter())
+            .enumerate()
+            .map(|(space_index, (space, landed_count))| {
+                let name: &str = space.get_space_name();
+
+                json!({
+                    "index": space_index,
+                    "name": name,
+                    "group": space.get_space_group().get_group_name(),
+                    "landed_count": landed_count,
+                    "probability": *landed_count as f64 / total_landed
+                })
+            })
+            .collect();
+
+        let groups: Map<String, Value> = SpaceGroupEnum::ALL.iter()
+            .map(|group| {
+                let group_landed: u64 = board.iter()
+                    .zip(self.landed_counts.iter())
+                    .filter(|(space, _)| space.get_space_group() == *group)
+                    .map(|(_, landed_count)| landed_count)
+                    .sum();
+
+                (group.get_group_name().to_string(), json!(group_landed as f64 / total_landed))
+            })
+            .collect();
+
+        let stats = &self.rule_statistics;
+
+        json!({
+            "engine": if self.used_fast_engine { "fast" } else { "standard" },
+            "total_landed": self.landed_counts.iter().sum::<u64>(),
+            "total_player_turns": self.player_turns,
+            "spaces": spaces,
+            "groups": groups,
+            "per_player_turn": {
+                "go_bonus_paid": stats.go_bonus_paid as f64 / player_turns,
+                "taxes_paid": stats.taxes_paid as f64 / player_turns,
+                "card_fees_paid": stats.card_fees_paid as f64 / player_turns,
+                "jail_fines_paid": stats.jail_fines_paid as f64 / player_turns,
+                "free_parking_paid_out": stats.free_parking_paid_out as f64 / player_turns,
+                "jail_turns_served": stats.jail_turns_served as f64 / player_turns
+            }
+        })
+    }
+}
+
+
+
+//Serves the job API on localhost until the process is killed.  Jobs run worker_count at a time, the rest wait their turn
+pub fn run_server(options: ServeOptions) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", options.port)).map_err(|e| format!("Couldn't listen on port {}: {}", options.port, e))?;
+
+    let job_board = Arc::new(JobBoard::new(MAX_FINISHED_JOBS));
+
+    for _ in 0..options.worker_count {
+        let job_board = Arc::clone(&job_board);
+        thread::spawn(move || run_jobs(&job_board));
+    }
+
+    //Connections get handed to a fixed set of threads the same way jobs go to the workers, so a flood of clients can't start
+    //a thread each
+    let (connection_sender, connection_receiver) = mpsc::sync_channel::<TcpStream>(MAX_WAITING_CONNECTIONS);
+    let connection_receiver = Arc::new(Mutex::new(connection_receiver));
+
+    for _ in 0..CONNECTION_THREADS {
+        let job_board = Arc::clone(&job_board);
+        let connection_receiver = Arc::clone(&connection_receiver);
+
+        thread::spawn(move || loop {
+            //Holding the lock only while waiting for the next connection, not while answering it
+            let stream = match connection_receiver.lock().unwrap().recv() {
+                Ok(stream) => stream,
+                Err(_) => return
+            };
+
+            if let Err(e) = handle_connection(stream, &job_board) {
+                eprintln!("Connection error: {}", e);
+            }
+        });
+    }
+
+    println!("Listening on http://127.0.0.1:{} with {} workers", options.port, options.worker_count);
+    println!("    POST   /jobs               Submit a job, e.g. {{\"players\": 4, \"turns\": 1000, \"games\": 100, \"rules\": \"official\"}}");
+    println!("    GET    /jobs               Every job's status");
+    println!("    GET    /jobs/<id>          One job's status");
+    println!("    GET    /jobs/<id>/results  A finished job's results");
+    println!("    DELETE /jobs/<id>          Cancel a job");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => connection_sender.send(stream).map_err(|_| String::from("Every connection thread has stopped"))?,
+            Err(e) => eprintln!("Connection error: {}", e)
+        }
+    }
+
+    Ok(())
+}
+
+
+
+fn run_jobs(job_board: &JobBoard) {
+    loop {
+        let (job_id, request, cancel) = job_board.take_next_job();
+
+        //A panic in the engine fails the job rather than taking the worker down with it and leaving the job running forever
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_job(job_id, &request, &cancel, job_board)))
+            .unwrap_or_else(|payload| Err(format!("The simulation crashed: {}", get_panic_message(payload.as_ref()))));
+
+        job_board.update_job(job_id, |job| match outcome {
+            Ok(Some(results)) => {
+                job.status = JobStatusEnum::Done;
+                job.results = Some(results);
+            },
+            Ok(None) => job.status = JobStatusEnum::Cancelled,
+            Err(e) => {
+                job.status = JobStatusEnum::Failed;
+                job.error = Some(e);
+            }
+        });
+    }
+}
+
+
+fn get_panic_message(payload: &(dyn Any + Send)) -> &str {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message,
+        (_, Some(message)) => message,
+        _ => "unknown panic"
+    }
+}
+
+
+//Plays the job's games one after another, None if it was cancelled part way
+fn run_job(job_id: u64, request: &JobRequest, cancel: &AtomicBool, job_board: &JobBoard) -> Result<Option<Value>, String> {
+    let mut totals = JobTotals {
+        landed_counts: vec![0; request.edition.create_board().len()],
+        rule_statistics: RuleStatistics::default(),
+        player_turns: 0,
+        used_fast_engine: true
+    };
+
+    for game in 0..request.game_count {
+        let simulation = || -> Result<Simulation, String> {
+            Ok(Simulation::new()
+                .edition(request.edition)
+                .rules(request.rules.clone())
+                .dice_model(dice_model::from_spec(&request.dice_spec)?)
+                .player_count(request.player_count)
+                .turn_count(request.turn_count)
+                .seed(request.seed.wrapping_add(game as u64))
+                .stop_when(cancel))
+        };
+
+        //Settings the fast engine can't play (the speed die, dice other than 2d6) fall back to the standard engine
+        let results = match simulation()?.run_fast() {
+            Ok(results) => results,
+            Err(_) => {
+                totals.used_fast_engine = false;
+                simulation()?.run()
+            }
+        };
+
+        if results.interrupted || cancel.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        totals.add_game(&results);
+        job_board.update_job(job_id, |job| job.games_played = game + 1);
+    }
+
+    Ok(Some(totals.to_json(request)))
+}
+
+
+
+//One request per connection, answered and closed
+fn handle_connection(stream: TcpStream, job_board: &JobBoard) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+
+    let (status_code, body) = match read_request(&mut reader) {
+        Ok((method, path, body)) => route_request(&method, &path, &body, job_board),
+        Err(e) => (400, json!({ "error": e }))
+    };
+
+    let body = body.to_string();
+    let mut stream = &stream;
+
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status_code,
+        get_reason_phrase(status_code),
+        body.len(),
+        body)?;
+
+    stream.flush()
+}
+
+
+//Method, path (without any query string) and body
+pub fn read_request(reader: &mut impl BufRead) -> Result<(String, String, Vec<u8>), String> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).map_err(|e| format!("Error reading request: {}", e))?;
+
+    let mut request_parts = request_line.split_whitespace();
+    let (method, target) = match (request_parts.next(), request_parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target),
+        _ => return Err(format!("Malformed request line '{}'", request_line.trim()))
+    };
+    let path = target.split('?').next().unwrap_or_default().trim_end_matches('/').to_string();
+
+    let mut content_length = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(|e| format!("Error reading request headers: {}", e))?;
+
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| format!("Invalid Content-Length '{}'", value.trim()))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(format!("Request body is over {} bytes", MAX_BODY_BYTES));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| format!("Error reading request body: {}", e))?;
+
+    Ok((method, path, body))
+}
+
+
+//Status code and JSON body to answer a request with
+pub fn route_request(method: &str, path: &str, body: &[u8], job_board: &JobBoard) -> (u16, Value) {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+    match (method, segments.as_slice()) {
+        ("POST", ["jobs"]) => {
+            let request = serde_json::from_slice(body)
+                .map_err(|e| format!("Request body isn't valid JSON: {}", e))
+                .and_then(|body| JobRequest::from_json(&body));
+
+            match request {
+                Ok(request) => (202, job_board.submit(request)),
+                Err(e) => (400, json!({ "error": e }))
+            }
+        },
+        ("GET", ["jobs"]) => {
+            let job_list = job_board.jobs.lock().unwrap();
+            let statuses: Vec<Value> = job_list.jobs.iter().map(|(job_id, job)| job.get_status_json(*job_id)).collect();
+
+            (200, json!({ "jobs": statuses }))
+        },
+        (_, ["jobs", job_id, rest @ ..]) => {
+            let job_id = match job_id.parse::<u64>() {
+                Ok(job_id) => job_id,
+                Err(_) => return (404, json!({ "error": format!("No job '{}'", job_id) }))
+            };
+
+            let mut job_list = job_board.jobs.lock().unwrap();
+            let job = match job_list.jobs.get_mut(&job_id) {
+                Some(job) => job,
+                None => return (404, json!({ "error": format!("No job {}", job_id) }))
+            };
+
+            match (method, rest) {
+                ("GET", []) => (200, job.get_status_json(job_id)),
+                ("GET", ["results"]) => match &job.results {
+                    Some(results) => {
+                        let mut results = results.clone();
+                        results["id"] = json!(job_id);
+                        results["request"] = job.request.to_json();
+                        (200, results)
+                    },
+                    None => (409, json!({ "error": format!("Job {} is {}, there are no results", job_id, job.status.get_status_name()) }))
+                },
+                ("DELETE", []) => {
+                    if !job.status.is_finished() {
+                        job.cancel.store(true, Ordering::Relaxed);
+
+                        //A job still waiting in the queue never starts, one that's running stops at its next check
+                        if job.status == JobStatusEnum::Queued {
+                            job.status = JobStatusEnum::Cancelled;
+                        }
+                    }
+
+                    (200, job.get_status_json(job_id))
+                },
+                _ => (405, json!({ "error": format!("Can't {} {}", method, path) }))
+            }
+        },
+        _ => (404, json!({ "error": format!("Nothing at {} {}", method, path) }))
+    }
+}
+
+
+fn get_reason_phrase(status_code: u16) -> &'static str {
+    match status_code {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Internal Server Error"
+    }
+}
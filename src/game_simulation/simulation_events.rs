@@ -0,0 +1,89 @@
+use colored::ColoredString;
+
+use crate::game_simulation::game_board::CardDeckEnum;
+use crate::game_simulation::speed_die::SpeedDieEnum;
+use crate::game_simulation::dice_model::DiceRoll;
+
+
+//Why a player ended up in jail
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JailReasonEnum {
+    TooManyDoubles,
+    GoToJailSpace,
+    Card
+}
+
+
+
+//Everything interesting that happens during a simulation.  Logging, statistics, visualization and tests all consume this same stream
+pub enum SimulationEventEnum<'a> {
+    //A new round is starting, every player takes one turn per round
+    TurnStarted { turn_number: u32 },
+    //doubles_count is how many doubles in a row this roll makes (0 when the dice didn't match).  Rolls to get out of jail never
+    //start a streak, so they're always 0
+    DiceRolled { player_name: &'a str, roll: DiceRoll, doubles_count: u8 },
+    //Mega edition only, rolled right after the white dice
+    SpeedDieRolled { player_name: &'a str, face: SpeedDieEnum },
+    //Mega edition only, a bus ticket was used to move instead of rolling
+    BusTicketUsed { player_name: &'a str, to_space: u8 },
+    LandedOnSpace { player_name: &'a str, space_index: u8, space_name: &'a ColoredString },
+    //card_index is the card's position in its deck as originally defined, not where it was in the shuffle
+    CardDrawn { player_name: &'a str, deck: CardDeckEnum, card_index: usize, card_text: &'a str },
+    //Moved by something other than the dice (a card, the Go To Jail space, or Mr. Monopoly)
+    PlayerMoved { player_name: &'a str, from_space: u8, to_space: u8 },
+    //Players buy every unowned property they land on.  Ownership only matters to the Mega edition's movement rules
+    PropertyBought { player_name: &'a str, space_index: u8 },
+    SentToJail { player_name: &'a str, reason: JailReasonEnum },
+    //Failed to roll doubles out of jail, jail_turns is how many turns have been spent trying so far
+    StayedInJail { player_name: &'a str, jail_turns: u8 },
+    LeftJail { player_name: &'a str, paid_fine: bool },
+    //Everything caused by a single roll (cards, Go To Jail) has been resolved and the player has come to rest
+    RollFinished { player_name: &'a str, final_space: u8 }
+}
+
+
+
+//Subscribers register with the simulation and are handed every event, in order, as it happens
+pub trait SimulationObserver {
+    fn on_event(&mut self, event: &SimulationEventEnum);
+}
+
+
+
+//Built-in observer that prints the play-by-play to the console
+pub struct ConsoleObserver;
+
+impl SimulationObserver for ConsoleObserver {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        match event {
+            SimulationEventEnum::DiceRolled { player_name, roll, doubles_count } => {
+                let dice = roll.get_dice().iter().map(|die| die.to_string()).collect::<Vec<_>>().join(" and ");
+
+                match doubles_count {
+                    0 => println!("{} Rolled {} totaling {}", player_name, dice, roll.get_total()),
+                    1 => println!("{} Rolled {} totaling {}.  Doubles Once.", player_name, dice, roll.get_total()),
+                    2 => println!("{} Rolled {} totaling {}.  Doubles Twice.", player_name, dice, roll.get_total()),
+                    3 => println!("{} Rolled {} totaling {}.  Doubles Thrice.", player_name, dice, roll.get_total()),
+                    _ => println!("{} Rolled {} totaling {}.  Doubles {} times in a row.", player_name, dice, roll.get_total(), doubles_count)
+                }
+            },
+            SimulationEventEnum::SpeedDieRolled { player_name, face } => println!("{} Rolled {} on the speed die", player_name, face.get_face_name()),
+            SimulationEventEnum::BusTicketUsed { player_name, .. } => println!("{} Uses a bus ticket", player_name),
+            SimulationEventEnum::SentToJail { player_name, .. } => println!("{} Goes directly to jail, do not pass go, do not collect $200", player_name),
+            SimulationEventEnum::StayedInJail { player_name, jail_turns } => println!("{} Stays in jail ({} turns so far)", player_name, jail_turns),
+            SimulationEventEnum::LeftJail { player_name, paid_fine: true } => println!("{} Pays the fine and leaves jail", player_name),
+            SimulationEventEnum::LeftJail { player_name, paid_fine: false } => println!("{} Rolled doubles and leaves jail", player_name),
+            SimulationEventEnum::LandedOnSpace { player_name, space_name, .. } => println!("{} is on {}", player_name, space_name),
+            SimulationEventEnum::CardDrawn { player_name, card_text, .. } => println!("{} Drew Card {}", player_name, card_text),
+            _ => {}
+        }
+    }
+}
+
+
+
+pub(crate) fn notify_observers(observers: &mut [&mut dyn SimulationObserver], event: SimulationEventEnum) {
+    for observer in observers.iter_mut() {
+        observer.on_event(&event);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::game_simulation::game_board::{BoardSpace, SpaceGroupEnum, StreetDetails};
+use crate::game_simulation::rule_set::RuleSet;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::precision::PrecisionReport;
+
+
+//Final landing count for a single space, in board order
+pub struct SpaceResult {
+    pub space_index: u8,
+    pub name: String,
+    pub group: SpaceGroupEnum,
+    pub street_details: Option<StreetDetails>,
+    pub landed_count: u64
+}
+impl SpaceResult {
+    pub fn from_space(space_index: u8, space: &dyn BoardSpace) -> Self {
+        //Strip the terminal colors, callers can re-apply them from the group
+        let plain_name: &str = space.get_space_name();
+
+        SpaceResult {
+            space_index,
+            name: plain_name.to_string(),
+            group: space.get_space_group(),
+            street_details: space.get_street_details().copied(),
+            landed_count: space.get_landed_count() as u64
+        }
+    }
+
+    pub fn get_colored_name(&self) -> colored::ColoredString {
+        self.group.colorize(&self.name)
+    }
+}
+
+
+
+//Money moved and jail time served under the rules in effect.  The simulator doesn't track player cash or bankruptcy, so these
+//totals are the way to compare how much a house rule pumps into (or drains out of) a game
+#[derive(Clone, Default, Debug)]
+pub struct RuleStatistics {
+    pub go_bonus_paid: u64,
+    pub taxes_paid: u64,
+    pub card_fees_paid: u64,
+    pub jail_fines_paid: u64,
+    pub free_parking_paid_out: u64,
+    pub jail_turns_served: u64
+}
+
+
+
+//Everything a finished simulation run produced, along with the settings needed to reproduce it
+pub struct SimulationResults {
+    pub player_count: u32,
+    pub turn_count: u32,
+    pub seed: u64,
+    pub edition: GameEditionEnum,
+    //Spec of the dice model used, see dice_model::from_spec
+    pub dice_model: String,
+    pub rules: RuleSet,
+    pub spaces: Vec<SpaceResult>,
+    //Where each player ended the game, in player order
+    pub final_positions: Vec<u8>,
+    //Whether each player ended the game in jail (rather than just visiting), in player order
+    pub final_in_jail: Vec<bool>,
+    pub rule_statistics: RuleStatistics,
+    //How precise each space's landing probability is, for runs stopped by a precision target
+    pub precision: Option<PrecisionReport>,
+    //Stopped early by Simulation::stop_when, so everything covers only the turns played before the stop
+    pub interrupted: bool
+}
+impl SimulationResults {
+
+    pub fn get_total_landed(&self) -> u64 {
+        self.spaces.iter().map(|space| space.landed_count).sum()
+    }
+
+    //Number of turns taken across every player (rolling doubles doesn't count as an extra turn)
+    pub fn get_total_player_turns(&self) -> u64 {
+        self.player_count as u64 * self.turn_count as u64
+    }
+
+    //Fraction of all player turns spent sitting in jail
+    pub fn get_jail_time_fraction(&self) -> f64 {
+        fraction_of(self.rule_statistics.jail_turns_served, self.get_total_player_turns())
+    }
+
+    //Share of all landings that happened on the given space
+    pub fn get_landing_probability(&self, space_index: u8) -> f64 {
+        match self.spaces.get(space_index as usize) {
+            Some(space) => fraction_of(space.landed_count, self.get_total_landed()),
+            None => 0.0
+        }
+    }
+
+    //Total landings per group, most landed group first
+    pub fn get_group_totals(&self) -> Vec<(SpaceGroupEnum, u64)> {
+        let mut group_totals: Vec<(SpaceGroupEnum, u64)> = SpaceGroupEnum::ALL.iter()
+            .map(|group| {
+                let group_landed = self.spaces.iter()
+                    .filter(|space| space.group == *group)
+                    .map(|space| space.landed_count)
+                    .sum();
+
+                (*group, group_landed)
+            })
+            .collect();
+
+        group_totals.sort_by_key(|group_total| std::cmp::Reverse(group_total.1));
+
+        group_totals
+    }
+
+
+    pub fn print_space_results(&self) {
+        println!("-----------------------RESULTS-----------------------");
+
+        //Sort the results by most landed to least landed
+        let mut sorted_spaces: Vec<&SpaceResult> = self.spaces.iter().collect();
+        sorted_spaces.sort_by_key(|space| std::cmp::Reverse(space.landed_count));
+
+        //Print game results (space landed counts)
+        for space in sorted_spaces {
+            println!("{}|{}", space.landed_count, space.get_colored_name())
+        }
+
+        println!("-----------------------------------------------------");
+    }
+
+
+    //Prints the landing probability of each color group, along with each space's share of all landings and of its own group
+    pub fn print_group_summary(&self) {
+        let total_landed = self.get_total_landed();
+
+        //Guard against dividing by zero, no landings means no probabilities to report
+        if total_landed == 0 {
+            return;
+        }
+
+        println!("--------------------GROUP RESULTS--------------------");
+
+        for (group, group_landed) in self.get_group_totals() {
+            println!("{:.3}%|{}", fraction_of(group_landed, total_landed) * 100.0, group.colorize(group.get_group_name()));
+
+            for space in self.spaces.iter().filter(|space| space.group == group) {
+                println!("    {:.3}%|{:.2}% of group|{}",
+                    fraction_of(space.landed_count, total_landed) * 100.0,
+                    fraction_of(space.landed_count, group_landed) * 100.0,
+                    space.get_colored_name());
+            }
+        }
+
+        println!("-----------------------------------------------------");
+    }
+
+
+    //Prints the rules in effect and the money and jail time they accounted for, per player turn
+    pub fn print_rule_summary(&self) {
+        let total_player_turns = self.get_total_player_turns() as f64;
+        let stats = &self.rule_statistics;
+
+        println!("---------------------RULE RESULTS--------------------");
+        println!("Edition: {}", self.edition.get_edition_name());
+        println!("Dice: {}", self.dice_model);
+        println!("Rules: {}", self.rules.to_spec());
+        println!("${:.2}|Go bonus salary per turn", stats.go_bonus_paid as f64 / total_player_turns);
+        println!("${:.2}|Taxes paid per turn", stats.taxes_paid as f64 / total_player_turns);
+        println!("${:.2}|Card fees paid per turn", stats.card_fees_paid as f64 / total_player_turns);
+        println!("${:.2}|Jail fines paid per turn", stats.jail_fines_paid as f64 / total_player_turns);
+        println!("${:.2}|Free Parking jackpot paid out per turn", stats.free_parking_paid_out as f64 / total_player_turns);
+        println!("{:.3}%|Turns spent in jail", self.get_jail_time_fraction() * 100.0);
+        println!("-----------------------------------------------------");
+    }
+
+
+    //Prints how long a precision target took to reach, and each space's landing probability with its confidence interval
+    pub fn print_precision_summary(&self) {
+        let precision = match &self.precision {
+            Some(precision) => precision,
+            None => return
+        };
+
+        let target = &precision.target;
+
+        println!("----------------------PRECISION----------------------");
+
+        if !target.has_target() {
+            println!("Intervals at {}% confidence from {} batches of {} turns", target.get_confidence() * 100.0, precision.batch_count, target.get_batch_turns());
+        }
+        else if precision.target_reached {
+            println!("Target: +/-{:.4}% at {}% confidence", target.get_half_width() * 100.0, target.get_confidence() * 100.0);
+            println!("Reached after {} turns ({} batches of {})", self.turn_count, precision.batch_count, target.get_batch_turns());
+        }
+        else {
+            println!("Target: +/-{:.4}% at {}% confidence", target.get_half_width() * 100.0, target.get_confidence() * 100.0);
+            println!("Not reached within {} turns, widest interval +/-{:.4}%", self.turn_count, precision.get_largest_half_width() * 100.0);
+        }
+
+        for (space, half_width) in self.spaces.iter().zip(precision.half_widths.iter()) {
+            println!("{:.3}%|+/-{:.4}%|{}", self.get_landing_probability(space.space_index) * 100.0, half_width * 100.0, space.get_colored_name());
+        }
+
+        println!("-----------------------------------------------------");
+    }
+}
+
+
+
+fn fraction_of(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    count as f64 / total as f64
+}
@@ -0,0 +1,92 @@
+use crate::game_simulation::GameState;
+use crate::game_simulation::dice_model::DiceRoll;
+
+
+//Bus tickets in a Mega edition box, shared by every player
+pub(super) const BUS_TICKET_COUNT: u8 = 16;
+
+
+//Faces of the Mega edition speed die.  Mr. Monopoly shows up on two of the six faces
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpeedDieEnum {
+    Number(u8),
+    MrMonopoly,
+    Bus
+}
+
+impl SpeedDieEnum {
+    //Maps a fair six sided roll (1-6) onto the speed die's faces
+    pub fn from_face(face: u8) -> Self {
+        match face {
+            1..=3 => SpeedDieEnum::Number(face),
+            4 | 5 => SpeedDieEnum::MrMonopoly,
+            _ => SpeedDieEnum::Bus
+        }
+    }
+
+    pub fn get_face_name(&self) -> String {
+        match self {
+            SpeedDieEnum::Number(number) => number.to_string(),
+            SpeedDieEnum::MrMonopoly => String::from("Mr. Monopoly"),
+            SpeedDieEnum::Bus => String::from("Bus")
+        }
+    }
+}
+
+
+
+//The simulator doesn't model player decisions beyond buying everything they land on, so every Mega edition choice is made
+//the same simple way: head for the nearest property nobody owns yet
+
+pub(super) fn next_unowned_property(state: &GameState, from_space: u8) -> Option<u8> {
+    spaces_ahead(state, from_space).find(|space| state.is_purchasable(*space) && state.get_owner(*space).is_none())
+}
+
+
+//Where Mr. Monopoly goes once everything has been bought: the next property owned by somebody else
+pub(super) fn next_opponent_property(state: &GameState, from_space: u8, player_index: usize) -> Option<u8> {
+    spaces_ahead(state, from_space).find(|space| matches!(state.get_owner(*space), Some(owner) if owner != player_index))
+}
+
+
+//The bus lets a player move by any one white die or their total.  Returns the distance to move
+pub(super) fn choose_bus_move(state: &GameState, from_space: u8, roll: &DiceRoll) -> u8 {
+    let mut distances: Vec<u8> = roll.get_dice().to_vec();
+
+    //Farthest first, so ties go to covering more ground
+    distances.sort_by_key(|distance| std::cmp::Reverse(*distance));
+    distances.insert(0, roll.get_total());
+
+    distances.into_iter()
+        .find(|distance| {
+            let destination = state.advance(from_space, *distance);
+            state.is_purchasable(destination) && state.get_owner(destination).is_none()
+        })
+        .unwrap_or(roll.get_total())
+}
+
+
+//Rolling triples lets a player move anywhere on the board
+pub(super) fn choose_triples_destination(state: &GameState, from_space: u8) -> u8 {
+    next_unowned_property(state, from_space).unwrap_or(0)
+}
+
+
+//A bus ticket moves a player to any space ahead on their side of the board.  Only worth using if it reaches an unowned property
+pub(super) fn choose_bus_ticket_destination(state: &GameState, from_space: u8) -> Option<u8> {
+    let side_length = (state.board.len() / 4).max(1);
+    let next_corner = (from_space as usize / side_length + 1) * side_length;
+
+    ((from_space as usize + 1)..next_corner.min(state.board.len()))
+        .map(|space| space as u8)
+        .find(|space| state.is_purchasable(*space) && state.get_owner(*space).is_none())
+}
+
+
+
+//Every other space on the board, in order, starting just past from_space
+fn spaces_ahead(state: &GameState, from_space: u8) -> impl Iterator<Item = u8> {
+    let board_size = state.board.len();
+
+    (1..board_size).map(move |offset| ((from_space as usize + offset) % board_size) as u8)
+}
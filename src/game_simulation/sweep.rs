@@ -0,0 +1,247 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::game_simulation::Simulation;
+use crate::game_simulation::game_board::SpaceGroupEnum;
+use crate::game_simulation::game_edition::GameEditionEnum;
+use crate::game_simulation::progress::{Progress, ProgressReporter};
+use crate::game_simulation::rule_set::RuleSet;
+
+
+
+//Every combination of player count, game length and rules to play.  Each cell plays game_count games of its length from the start,
+//so short horizons show the early game rather than a slice of a long one.  Game g of every cell is seeded with seed + g, so cells
+//differ only in their settings, not in their luck
+#[derive(Clone, Debug)]
+pub struct SweepGrid {
+    pub edition: GameEditionEnum,
+    pub player_counts: Vec<u32>,
+    pub turn_counts: Vec<u32>,
+    //Name to show for each variant, and its rules
+    pub rule_variants: Vec<(String, RuleSet)>,
+    pub game_count: u32,
+    pub seed: u64
+}
+
+impl SweepGrid {
+    pub fn get_cell_count(&self) -> usize {
+        self.player_counts.len() * self.turn_counts.len() * self.rule_variants.len()
+    }
+
+    //Turns across every game of every cell
+    pub fn get_total_turns(&self) -> u64 {
+        let turns_per_game: u64 = self.turn_counts.iter().map(|turn_count| *turn_count as u64).sum();
+
+        turns_per_game * self.game_count as u64 * self.player_counts.len() as u64 * self.rule_variants.len() as u64
+    }
+}
+
+
+
+//Landings added up across one cell's games, in board order
+#[derive(Clone, Debug)]
+pub struct SweepCell {
+    pub player_count: u32,
+    pub turn_count: u32,
+    pub rules_name: String,
+    pub landed_counts: Vec<u64>
+}
+
+impl SweepCell {
+    pub fn get_landing_probability(&self, space_index: usize) -> f64 {
+        let total_landed: u64 = self.landed_counts.iter().sum();
+
+        match total_landed {
+            0 => 0.0,
+            total_landed => self.landed_counts[space_index] as f64 / total_landed as f64
+        }
+    }
+
+    //Each space's place when the board is sorted most landed first, 1 being the most landed.  Ties share the better place
+    pub fn get_ranks(&self) -> Vec<usize> {
+        self.landed_counts.iter()
+            .map(|landed_count| 1 + self.landed_counts.iter().filter(|other| *other > landed_count).count())
+            .collect()
+    }
+
+    //e.g. "4p/100t/official"
+    pub fn get_cell_name(&self) -> String {
+        format!("{}p/{}t/{}", self.player_count, self.turn_count, self.rules_name)
+    }
+}
+
+
+
+//How far one space's rank moved across the grid, and where it was at its best and worst
+#[derive(Clone, Debug)]
+pub struct RankSpread {
+    pub space_index: usize,
+    pub best_rank: usize,
+    pub best_cell: usize,
+    pub worst_rank: usize,
+    pub worst_cell: usize
+}
+
+impl RankSpread {
+    pub fn get_spread(&self) -> usize {
+        self.worst_rank - self.best_rank
+    }
+}
+
+
+
+pub struct SweepResults {
+    pub space_names: Vec<String>,
+    pub space_groups: Vec<SpaceGroupEnum>,
+    //Grid order: player counts, then turn counts, then rule variants
+    pub cells: Vec<SweepCell>,
+    //Stopped early by a stop request, so only the cells finished before it are here
+    pub interrupted: bool
+}
+
+impl SweepResults {
+    //One row per cell and space, ready for a spreadsheet or a dataframe
+    pub fn write_table(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "players,turns,rules,space_index,space,group,landed_count,probability,rank")?;
+
+        for cell in self.cells.iter() {
+            let ranks = cell.get_ranks();
+
+            for (space_index, landed_count) in cell.landed_counts.iter().enumerate() {
+                writeln!(writer, "{},{},\"{}\",{},\"{}\",{},{},{:.6},{}",
+                    cell.player_count,
+                    cell.turn_count,
+                    cell.rules_name,
+                    space_index,
+                    self.space_names[space_index],
+                    self.space_groups[space_index].get_group_name(),
+                    landed_count,
+                    cell.get_landing_probability(space_index),
+                    ranks[space_index])?;
+            }
+        }
+
+        Ok(())
+    }
+
+
+    //Every space's best and worst rank across the grid, the spaces that moved furthest first
+    pub fn get_rank_spreads(&self) -> Vec<RankSpread> {
+        let cell_ranks: Vec<Vec<usize>> = self.cells.iter().map(|cell| cell.get_ranks()).collect();
+
+        if cell_ranks.is_empty() {
+            return Vec::new();
+        }
+
+        let mut rank_spreads: Vec<RankSpread> = (0..self.space_names.len())
+            .map(|space_index| {
+                let ranked_cells = || cell_ranks.iter().enumerate().map(|(cell_index, ranks)| (ranks[space_index], cell_index));
+                let (best_rank, best_cell) = ranked_cells().min().unwrap();
+                let (worst_rank, worst_cell) = ranked_cells().max().unwrap();
+
+                RankSpread { space_index, best_rank, best_cell, worst_rank, worst_cell }
+            })
+            .collect();
+
+        rank_spreads.sort_by_key(|rank_spread| (std::cmp::Reverse(rank_spread.get_spread()), rank_spread.best_rank));
+
+        rank_spreads
+    }
+
+
+    pub fn print_rank_summary(&self) {
+        println!("---------------------RANK CHANGES--------------------");
+        println!("{} cells, rank 1 is the most landed space", self.cells.len());
+
+        let rank_spreads = self.get_rank_spreads();
+        let (moved, steady): (Vec<&RankSpread>, Vec<&RankSpread>) = rank_spreads.iter().partition(|rank_spread| rank_spread.get_spread() > 0);
+
+        for rank_spread in moved {
+            println!("{}-{}|best {} at {}, worst {} at {}|{}",
+                rank_spread.best_rank,
+                rank_spread.worst_rank,
+                rank_spread.best_rank,
+                self.cells[rank_spread.best_cell].get_cell_name(),
+                rank_spread.worst_rank,
+                self.cells[rank_spread.worst_cell].get_cell_name(),
+                self.space_groups[rank_spread.space_index].colorize(&self.space_names[rank_spread.space_index]));
+        }
+
+        if !steady.is_empty() {
+            let steady_names: Vec<String> = steady.iter()
+                .map(|rank_spread| format!("{} ({})", self.space_names[rank_spread.space_index], rank_spread.best_rank))
+                .collect();
+
+            println!("Same rank in every cell: {}", steady_names.join(", "));
+        }
+
+        println!("-----------------------------------------------------");
+    }
+}
+
+
+
+//Plays every cell of the grid in turn on the fast engine, or the standard engine for settings the fast engine can't play.
+//Progress covers the whole sweep, and a stop request ends it after the game under way
+pub fn run_sweep(grid: &SweepGrid, mut progress_reporter: Option<&mut dyn ProgressReporter>, stop_flag: Option<&AtomicBool>) -> Result<SweepResults, String> {
+    if grid.get_cell_count() == 0 || grid.game_count == 0 {
+        return Err(String::from("The sweep grid needs at least one player count, turn count, rule variant and game"));
+    }
+
+    if let Some(player_count) = grid.player_counts.iter().find(|player_count| !(2..=8).contains(*player_count)) {
+        return Err(format!("Player counts must be between 2 and 8, got {}", player_count));
+    }
+
+    let board = grid.edition.create_board();
+    let space_names: Vec<String> = board.iter().map(|space| (**space.get_space_name()).to_string()).collect();
+    let space_groups = board.iter().map(|space| space.get_space_group()).collect();
+
+    let stop_requested = || stop_flag.is_some_and(|stop_flag| stop_flag.load(Ordering::Relaxed));
+    let start = Instant::now();
+    let turn_count_total = grid.get_total_turns();
+    let mut turns_played: u64 = 0;
+    let mut cells = Vec::with_capacity(grid.get_cell_count());
+    let mut interrupted = false;
+
+    'cells: for player_count in grid.player_counts.iter().copied() {
+        for turn_count in grid.turn_counts.iter().copied() {
+            for (rules_name, rules) in grid.rule_variants.iter() {
+                let mut cell = SweepCell { player_count, turn_count, rules_name: rules_name.clone(), landed_counts: vec![0; space_names.len()] };
+
+                for game in 0..grid.game_count {
+                    if stop_requested() {
+                        interrupted = true;
+                        break 'cells;
+                    }
+
+                    let simulation = || Simulation::new()
+                        .edition(grid.edition)
+                        .rules(rules.clone())
+                        .player_count(player_count)
+                        .turn_count(turn_count)
+                        .seed(grid.seed.wrapping_add(game as u64));
+
+                    let results = match simulation().run_fast() {
+                        Ok(results) => results,
+                        Err(_) => simulation().run()
+                    };
+
+                    for (landed_count, space) in cell.landed_counts.iter_mut().zip(results.spaces.iter()) {
+                        *landed_count += space.landed_count;
+                    }
+
+                    turns_played += turn_count as u64;
+
+                    if let Some(reporter) = progress_reporter.as_mut() {
+                        reporter.on_progress(&Progress { turns_played, turn_count: turn_count_total, turns_at_start: 0, elapsed: start.elapsed() });
+                    }
+                }
+
+                cells.push(cell);
+            }
+        }
+    }
+
+    Ok(SweepResults { space_names, space_groups, cells, interrupted })
+}
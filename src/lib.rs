@@ -0,0 +1,9 @@
+//Monopoly space landing simulator.  The CLI in main.rs is a thin layer over this library, other tools can embed it directly:
+//
+//    let results = Simulation::new().player_count(4).turn_count(500).seed(42).run();
+//    results.print_space_results();
+
+pub mod game_simulation;
+
+pub use game_simulation::Simulation;
+pub use game_simulation::simulation_results::{SimulationResults, SpaceResult};
@@ -1,23 +1,583 @@
-use std::{io, process};
+use std::{env, fs, io, process};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::io::*;
 
-use futures::executor::block_on;
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::rent_returns;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::dice_model::{self, DiceModel, StandardDice};
+use monopoly_space_calc::game_simulation::simulation_events::ConsoleObserver;
+use monopoly_space_calc::game_simulation::replay_log::{self, ReplayLog, ReplayRecorder};
+use monopoly_space_calc::game_simulation::benchmark::{self, EngineEnum};
+use monopoly_space_calc::game_simulation::precision::PrecisionTarget;
+use monopoly_space_calc::game_simulation::progress::ConsoleProgress;
+use monopoly_space_calc::game_simulation::saved_results::SavedResults;
+use monopoly_space_calc::game_simulation::comparison;
+use monopoly_space_calc::game_simulation::sweep::{self, SweepGrid};
+use monopoly_space_calc::game_simulation::markov_chain::MarkovChain;
+use monopoly_space_calc::game_simulation::passage_times::{self, PassageTimeObserver};
+use monopoly_space_calc::game_simulation::serve::{self, ServeOptions};
+
+mod dashboard;
+use dashboard::DashboardOptions;
+
+
+const USAGE: &str = "Usage:
+    monopoly-space-calc [options]          Prompt for players and turns, then simulate
+    monopoly-space-calc replay <file>      Play a replay log back and check it reaches the same final state
+    monopoly-space-calc bench [options]    Time both engines at 2, 4, 6 and 8 players, and each phase of a turn
+    monopoly-space-calc dashboard [options]
+                                           Full screen live view of a simulation that keeps playing until you quit.  Takes
+                                           --edition, --dice, --rules, --players (default 4) and --seed
+    monopoly-space-calc serve [options]    Run simulation jobs submitted over a local HTTP/JSON API
+    monopoly-space-calc compare <a> <b> [--significance <pct>]
+                                           Compare two runs saved with --save-results, flagging the spaces and groups whose
+                                           landing probability really changed (default significance 5%)
+    monopoly-space-calc sweep [options]    Play every combination of player count, game length and rules, then show how
+                                           each space's rank changes across them
+    monopoly-space-calc passage [options]  How many turns until a space gets landed on from every other space, and between
+                                           landings on it, worked out exactly and from a simulated game side by side
+
+Options:
+    --record <file>     Write a replay log of the game to <file>
+    --save-results <file>  Write the results and each space's confidence interval to <file>, for compare
+    --edition <name>    Board, cards and default rules to play with: classic (default), classic-pre-2008, mega, uk, france or germany
+    --dice <model>      Dice to roll: 2d6 (default), 1d6, 3d6, weighted:<dice>:<face>=<weight>,... or scripted:<roll>,...
+                        e.g. weighted:2:1=1,2=1,3=1,4=1,5=1,6=1.5 or scripted:3+4,6+6,1+2
+    --precision <pct>   Instead of asking for a turn count, keep playing until every space's landing probability is known to
+                        within <pct> percent, e.g. 0.05.  Reports the turns it took and each space's confidence interval
+    --confidence <pct>  Confidence level for --precision and --save-results: 80, 90, 95 (default), 98, 99 or 99.9
+    --players <n>       Number of players (2-8) instead of asking
+    --turns <n>         Number of turns instead of asking.  Games over 500 turns skip the play by play
+    --seed <n>          Seed the RNG, runs with the same seed and settings give the same results
+    --checkpoint <file> Run on the fast engine, saving progress to <file> as it goes.  Run the same command again after an
+                        interruption to carry on from the checkpoint.  Needs --turns or --precision
+    --checkpoint-every <n>  Turns between checkpoint saves (default 1000000)
+    --rules <rules>     Comma separated rule settings applied on top of the edition's, e.g. official,free_parking_jackpot,max_doubles=2
+                        Rules: default, official, max_doubles=N, max_jail_turns=N, jail_fine=N, go_salary=N,
+                               free_parking_jackpot, double_salary_on_go, speed_die
+                               Rent return table only: no_rent_in_jail, even_build
+
+Bench options:
+    --games <n>         Games played per engine and player count (default 200)
+    --turns <n>         Turns per game (default 1000)
+    --save <file>       Write the turns per second figures to <file> as a baseline
+    --baseline <file>   Compare against a saved baseline, exiting with an error if anything is over 10% slower
+
+Sweep options:
+    --edition <name>    Board and cards to play with (default classic)
+    --players <list>    Player counts, e.g. 2-8 (default) or 2,4,6
+    --turns <list>      Game lengths in turns, e.g. 25,50,100 (default 10,25,50,100,250,1000)
+    --rules <variants>  Rule variants separated by ';', each as for --rules, e.g. 'default;official' (default the edition's rules)
+    --games <n>         Games played per combination (default 1000)
+    --seed <n>          Seed the RNG, sweeps with the same seed and settings give the same results
+    --out <file>        Write the full table (one row per combination and space) to <file> as CSV instead of printing it
+
+Passage options:
+    --space <n>         Board index of the space to wait for (default the last space, e.g. 39 for Boardwalk)
+    --edition <name>    Board and cards to play with (default classic).  Editions with the speed die or bus tickets can't be worked out exactly
+    --rules <rules>     Rule settings applied on top of the edition's, as for a normal run
+    --players <n>       Number of players (2-8) in the simulated game (default 4)
+    --turns <n>         Turns in the simulated game (default 100000)
+    --seed <n>          Seed the RNG, runs with the same seed and settings give the same results
+
+Serve options:
+    --port <n>          Port to listen on at 127.0.0.1 (default 7878)
+    --workers <n>       Jobs run at the same time (default one per CPU core), the rest queue up";
+
+const DEFAULT_CONFIDENCE_PERCENT: f64 = 95.0;
+
+const DEFAULT_CHECKPOINT_TURNS: u32 = 1_000_000;
+
+//Set by the first Ctrl-C once a run is under way
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+//Longest game the play by play is printed for, the most the turns prompt allows
+const MAX_PLAY_BY_PLAY_TURNS: u32 = 500;
+
+const DEFAULT_BENCH_GAMES: u32 = 200;
+const DEFAULT_BENCH_TURNS: u32 = 1000;
+
+const DEFAULT_DASHBOARD_PLAYERS: u32 = 4;
+
+const DEFAULT_SERVE_PORT: u16 = 7878;
+
+const DEFAULT_SWEEP_TURNS: [u32; 6] = [10, 25, 50, 100, 250, 1000];
+const DEFAULT_SWEEP_GAMES: u32 = 1000;
+
+const DEFAULT_PASSAGE_PLAYERS: u32 = 4;
+const DEFAULT_PASSAGE_TURNS: u32 = 100_000;
+//Exact return time distributions are worked out this many turns ahead, far enough for the median of any space
+const MAX_RETURN_TURNS: u32 = 500;
+
+//Operations timed per phase, and the game played to count how often each phase happens per turn
+const PHASE_ITERATIONS: u32 = 2_000_000;
+const PHASE_RATE_PLAYERS: u32 = 4;
+const PHASE_RATE_TURNS: u32 = 50_000;
+
+
+
+//Settings picked on the command line for an interactive run
+struct CliOptions {
+    record_path: Option<String>,
+    results_path: Option<String>,
+    edition: GameEditionEnum,
+    dice_model: Box<dyn DiceModel>,
+    rules: RuleSet,
+    precision: Option<PrecisionTarget>,
+    confidence: f64,
+    player_count: Option<u32>,
+    turn_count: Option<u32>,
+    seed: Option<u64>,
+    checkpoint_path: Option<String>,
+    checkpoint_turns: u32
+}
+
+
+
+//--edition, --rules, --players and --seed, which mean the same thing to every command that takes them
+struct SharedOptions {
+    edition: GameEditionEnum,
+    rules_spec: String,
+    player_count: Option<u32>,
+    seed: Option<u64>
+}
+
+impl SharedOptions {
+    fn new() -> Self {
+        SharedOptions { edition: GameEditionEnum::Classic, rules_spec: String::new(), player_count: None, seed: None }
+    }
+
+    //Takes arg if it's one of the shared options, false if it's up to the command
+    fn parse_arg<'a>(&mut self, arg: &str, next_value: &mut dyn FnMut() -> std::result::Result<&'a String, String>) -> std::result::Result<bool, String> {
+        match arg {
+            "--edition" => {
+                let edition_name = next_value()?;
+                self.edition = GameEditionEnum::from_edition_name(edition_name).ok_or(format!("Unknown edition '{}'", edition_name))?;
+            },
+            "--rules" => self.rules_spec = next_value()?.clone(),
+            "--players" => self.player_count = Some(check_player_count(parse_number(arg, next_value()?)?)?),
+            "--seed" => self.seed = Some(next_value()?.parse().map_err(|_| String::from("--seed must be a whole number"))?),
+            _ => return Ok(false)
+        }
+
+        Ok(true)
+    }
+
+    //Rules go on top of the edition's defaults whichever order they were given in
+    fn get_rules(&self) -> std::result::Result<RuleSet, String> {
+        self.edition.get_default_rules().with_spec(&self.rules_spec)
+    }
+}
+
+
+fn check_player_count(player_count: u32) -> std::result::Result<u32, String> {
+    match player_count {
+        2..=8 => Ok(player_count),
+        _ => Err(format!("--players must be between 2 and 8, got {}", player_count))
+    }
+}
+
+
+
+//Settings for the sweep command
+struct SweepOptions {
+    grid: SweepGrid,
+    out_path: Option<String>
+}
+
+
+
+//Settings for the passage command
+struct PassageOptions {
+    edition: GameEditionEnum,
+    rules: RuleSet,
+    player_count: u32,
+    turn_count: u32,
+    seed: Option<u64>,
+    target_space: Option<u8>
+}
+
+
+
+//Settings for the bench command
+struct BenchOptions {
+    game_count: u32,
+    turn_count: u32,
+    save_path: Option<String>,
+    baseline_path: Option<String>
+}
 
-mod game_simulation;
 
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let outcome = match args.first().map(|arg| arg.as_str()) {
+        Some("replay") if args.len() == 2 => {
+            run_replay(&args[1]);
+            Ok(())
+        },
+        Some("bench") => parse_bench_options(&args[1..]).and_then(run_bench),
+        Some("dashboard") => parse_dashboard_options(&args[1..]).and_then(dashboard::run_dashboard),
+        Some("serve") => parse_serve_options(&args[1..]).and_then(serve::run_server),
+        Some("compare") => run_compare(&args[1..]),
+        Some("sweep") => parse_sweep_options(&args[1..]).and_then(run_sweep),
+        Some("passage") => parse_passage_options(&args[1..]).and_then(run_passage),
+        _ => parse_options(&args).map(run_interactive)
+    };
+
+    if let Err(e) = outcome {
+        println!("{}", e);
+        println!();
+        println!("{}", USAGE);
+        process::exit(1);
+    }
+}
+
+
+
+fn parse_options(args: &[String]) -> std::result::Result<CliOptions, String> {
+    let mut shared = SharedOptions::new();
+    let mut record_path = None;
+    let mut results_path = None;
+    let mut dice_model: Box<dyn DiceModel> = Box::new(StandardDice);
+    let mut precision_percent = None;
+    let mut confidence_percent = DEFAULT_CONFIDENCE_PERCENT;
+    let mut turn_count = None;
+    let mut checkpoint_path = None;
+    let mut checkpoint_turns = DEFAULT_CHECKPOINT_TURNS;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--record" => record_path = Some(next_value()?.clone()),
+            "--save-results" => results_path = Some(next_value()?.clone()),
+            "--dice" => dice_model = dice_model::from_spec(next_value()?)?,
+            "--precision" => precision_percent = Some(parse_percent(arg, next_value()?)?),
+            "--confidence" => confidence_percent = parse_percent(arg, next_value()?)?,
+            "--turns" => turn_count = Some(parse_number(arg, next_value()?)?),
+            "--checkpoint" => checkpoint_path = Some(next_value()?.clone()),
+            "--checkpoint-every" => checkpoint_turns = parse_number(arg, next_value()?)?,
+            _ if shared.parse_arg(arg, &mut next_value)? => {},
+            _ => return Err(format!("Unknown argument '{}'", arg))
+        }
+    }
+
+    let rules = shared.get_rules()?;
+
+    let confidence = confidence_percent / 100.0;
+    let precision = match precision_percent {
+        Some(precision_percent) => Some(PrecisionTarget::new(precision_percent / 100.0, confidence)?),
+        None => None
+    };
+
+    //Saving results measures intervals over the whole run, check the confidence level before the run rather than after
+    if results_path.is_some() {
+        PrecisionTarget::fixed_turns(1, confidence)?;
+    }
+
+    if checkpoint_path.is_some() && record_path.is_some() {
+        return Err(String::from("--checkpoint runs on the fast engine, which can't --record a replay log"));
+    }
+
+    if checkpoint_path.is_some() && turn_count.is_none() && precision.is_none() {
+        return Err(String::from("--checkpoint needs --turns or --precision"));
+    }
+
+    Ok(CliOptions {
+        record_path,
+        results_path,
+        edition: shared.edition,
+        dice_model,
+        rules,
+        precision,
+        confidence,
+        player_count: shared.player_count,
+        turn_count,
+        seed: shared.seed,
+        checkpoint_path,
+        checkpoint_turns
+    })
+}
+
+
+fn parse_number(arg: &str, value: &str) -> std::result::Result<u32, String> {
+    match value.replace('_', "").parse() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("{} must be a whole number above 0, got '{}'", arg, value))
+    }
+}
+
+
+fn parse_percent(arg: &str, value: &str) -> std::result::Result<f64, String> {
+    value.trim_end_matches('%').parse().map_err(|_| format!("{} needs a percentage, got '{}'", arg, value))
+}
+
+
+
+fn parse_bench_options(args: &[String]) -> std::result::Result<BenchOptions, String> {
+    let mut options = BenchOptions { game_count: DEFAULT_BENCH_GAMES, turn_count: DEFAULT_BENCH_TURNS, save_path: None, baseline_path: None };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+        let parse_count = |value: &String| match value.parse() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(format!("{} must be a whole number above 0, got '{}'", arg, value))
+        };
+
+        match arg.as_str() {
+            "--games" => options.game_count = parse_count(next_value()?)?,
+            "--turns" => options.turn_count = parse_count(next_value()?)?,
+            "--save" => options.save_path = Some(next_value()?.clone()),
+            "--baseline" => options.baseline_path = Some(next_value()?.clone()),
+            _ => return Err(format!("Unknown bench argument '{}'", arg))
+        }
+    }
+
+    Ok(options)
+}
+
+
+//Same options as an interactive run, less the ones that only make sense for a run with an end
+fn parse_dashboard_options(args: &[String]) -> std::result::Result<DashboardOptions, String> {
+    let mut shared = SharedOptions::new();
+    let mut dice_spec = StandardDice.get_model_spec();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--dice" => dice_spec = dice_model::from_spec(next_value()?)?.get_model_spec(),
+            _ if shared.parse_arg(arg, &mut next_value)? => {},
+            _ => return Err(format!("Unknown dashboard argument '{}'", arg))
+        }
+    }
+
+    Ok(DashboardOptions {
+        edition: shared.edition,
+        dice_spec,
+        rules: shared.get_rules()?,
+        player_count: shared.player_count.unwrap_or(DEFAULT_DASHBOARD_PLAYERS),
+        seed: shared.seed.unwrap_or_else(rand::random),
+        rules_spec: shared.rules_spec
+    })
+}
+
+
+fn parse_sweep_options(args: &[String]) -> std::result::Result<SweepOptions, String> {
+    let mut shared = SharedOptions::new();
+    let mut player_counts: Vec<u32> = (2..=8).collect();
+    let mut turn_counts = DEFAULT_SWEEP_TURNS.to_vec();
+    let mut rules_specs = vec![String::new()];
+    let mut game_count = DEFAULT_SWEEP_GAMES;
+    let mut out_path = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        //Sweeps take a list of player counts and of rule variants, the rest is shared
+        match arg.as_str() {
+            "--players" => player_counts = parse_number_list(arg, next_value()?)?,
+            "--turns" => turn_counts = parse_number_list(arg, next_value()?)?,
+            "--rules" => rules_specs = next_value()?.split(';').map(|spec| spec.trim().to_string()).collect(),
+            "--games" => game_count = parse_number(arg, next_value()?)?,
+            "--out" => out_path = Some(next_value()?.clone()),
+            _ if shared.parse_arg(arg, &mut next_value)? => {},
+            _ => return Err(format!("Unknown sweep argument '{}'", arg))
+        }
+    }
+
+    for player_count in &player_counts {
+        check_player_count(*player_count)?;
+    }
+
+    let edition = shared.edition;
+
+    //Each variant goes on top of the edition's defaults, like --rules.  An empty one is just the edition's rules
+    let mut rule_variants = Vec::new();
+    for rules_spec in rules_specs {
+        let rules = edition.get_default_rules().with_spec(&rules_spec)?;
+        let rules_name = if rules_spec.is_empty() { String::from(edition.get_edition_name()) } else { rules_spec };
+
+        rule_variants.push((rules_name, rules));
+    }
+
+    let grid = SweepGrid { edition, player_counts, turn_counts, rule_variants, game_count, seed: shared.seed.unwrap_or_else(rand::random) };
+
+    Ok(SweepOptions { grid, out_path })
+}
+
+
+//Comma separated numbers above 0, or a range like 2-8
+fn parse_number_list(arg: &str, value: &str) -> std::result::Result<Vec<u32>, String> {
+    let mut numbers = Vec::new();
+
+    for item in value.split(',').map(|item| item.trim()) {
+        match item.split_once('-') {
+            Some((first, last)) => numbers.extend(parse_number(arg, first)?..=parse_number(arg, last)?),
+            None => numbers.push(parse_number(arg, item)?)
+        }
+    }
+
+    numbers.dedup();
+
+    if numbers.is_empty() {
+        return Err(format!("{} needs at least one number", arg));
+    }
+
+    Ok(numbers)
+}
+
+
+fn parse_passage_options(args: &[String]) -> std::result::Result<PassageOptions, String> {
+    let mut shared = SharedOptions::new();
+    let mut turn_count = DEFAULT_PASSAGE_TURNS;
+    let mut target_space = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--space" => target_space = Some(next_value()?.parse().map_err(|_| String::from("--space must be a board index, 0 for Go"))?),
+            "--turns" => turn_count = parse_number(arg, next_value()?)?,
+            _ if shared.parse_arg(arg, &mut next_value)? => {},
+            _ => return Err(format!("Unknown passage argument '{}'", arg))
+        }
+    }
+
+    Ok(PassageOptions {
+        edition: shared.edition,
+        rules: shared.get_rules()?,
+        player_count: shared.player_count.unwrap_or(DEFAULT_PASSAGE_PLAYERS),
+        turn_count,
+        seed: shared.seed,
+        target_space
+    })
+}
+
+
+fn parse_serve_options(args: &[String]) -> std::result::Result<ServeOptions, String> {
+    let worker_count = std::thread::available_parallelism().map(|cores| cores.get()).unwrap_or(1);
+    let mut options = ServeOptions { port: DEFAULT_SERVE_PORT, worker_count };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut next_value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--port" => options.port = next_value()?.parse().map_err(|_| String::from("--port must be between 0 and 65535"))?,
+            "--workers" => options.worker_count = parse_number(arg, next_value()?)? as usize,
+            _ => return Err(format!("Unknown serve argument '{}'", arg))
+        }
+    }
+
+    Ok(options)
+}
+
+
+
+fn run_interactive(options: CliOptions) {
     println!("Monopoly space landing simulator.  Enter 'q' at any input to quit.");
-        println!("");
+        println!();
 
-        let (num_players, num_turns) = get_user_input();
+        let num_players = options.player_count.unwrap_or_else(get_player_count);
             
 
-        let work = game_simulation::run_simulation(num_players, num_turns);
-        block_on(work);
+        let mut console_observer = ConsoleObserver;
+        let mut replay_recorder = ReplayRecorder::new();
+        let mut console_progress = ConsoleProgress::new();
+        let mut play_by_play = false;
+
+        let mut simulation = Simulation::new()
+            .player_count(num_players)
+            .edition(options.edition)
+            .dice_model(options.dice_model)
+            .rules(options.rules);
+
+        if let Some(seed) = options.seed {
+            simulation = simulation.seed(seed);
+        }
+
+        //With a precision target there's no turn count to ask for.  Play by play is only for games short enough to read through
+        match options.precision {
+            Some(precision) => simulation = simulation.until_precision(precision),
+            None => {
+                let num_turns = options.turn_count.unwrap_or_else(get_turn_count);
+                simulation = simulation.turn_count(num_turns);
+
+                //Saved results need every space's interval, which a run of a set length doesn't otherwise measure
+                if options.results_path.is_some() {
+                    simulation = simulation.until_precision(PrecisionTarget::fixed_turns(num_turns, options.confidence).unwrap());
+                }
+
+                play_by_play = options.checkpoint_path.is_none() && num_turns <= MAX_PLAY_BY_PLAY_TURNS;
+            }
+        }
+
+        //The progress line would just get in the way of the play by play
+        if play_by_play {
+            simulation = simulation.add_observer(&mut console_observer);
+        }
+        else {
+            simulation = simulation.progress(&mut console_progress);
+        }
+
+        if options.record_path.is_some() {
+            simulation = simulation.add_observer(&mut replay_recorder);
+        }
+
+        stop_on_ctrl_c();
+        simulation = simulation.stop_when(&STOP_REQUESTED);
+
+        let results = match &options.checkpoint_path {
+            Some(checkpoint_path) => match simulation.run_fast_checkpointed(Path::new(checkpoint_path), options.checkpoint_turns) {
+                Ok(results) => results,
+                Err(e) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+            },
+            None => simulation.run()
+        };
+
+        console_progress.finish();
+
+        print_results(&results);
+
+        if let (true, Some(checkpoint_path)) = (results.interrupted, &options.checkpoint_path) {
+            println!("Progress saved to {}, run the same command again to carry on from there", checkpoint_path);
+        }
+
+        if let Some(record_path) = options.record_path {
+            let replay_log = replay_recorder.finish(&results);
+
+            match fs::File::create(&record_path).and_then(|mut file| replay_log.write_to(&mut file)) {
+                Ok(()) => println!("Replay log written to {}", record_path),
+                Err(e) => println!("Error writing replay log to {}: {}", record_path, e)
+            }
+        }
+
+        if let Some(results_path) = options.results_path {
+            let saved = SavedResults::from_results(&results)
+                .and_then(|saved_results| fs::File::create(&results_path).and_then(|mut file| saved_results.write_to(&mut file)).map_err(|e| e.to_string()));
+
+            match saved {
+                Ok(()) => println!("Results written to {}", results_path),
+                Err(e) => println!("Error writing results to {}: {}", results_path, e)
+            }
+        }
 
 
-        println!("");
+        println!();
         println!("Press Enter to quit");
 
         let mut buf: [u8; 1] = [0; 1];
@@ -27,21 +587,230 @@ fn main() {
 
 
 
-fn get_user_input() -> (u32, u32) {
-    fn check_quit_input(input: &str) {
-        if input == "q" || input == "Q" {
-            println!("Thanks for playing!  Goodbye!");
-            process::exit(0);
+//First Ctrl-C stops the run cleanly so the partial results still get printed, a second one quits on the spot
+fn stop_on_ctrl_c() {
+    let handler_result = ctrlc::set_handler(|| {
+        if STOP_REQUESTED.swap(true, Ordering::Relaxed) {
+            process::exit(130);
+        }
+
+        eprintln!();
+        eprintln!("Stopping, press Ctrl-C again to quit straight away");
+    });
+
+    if let Err(e) = handler_result {
+        println!("Ctrl-C will quit without results: {}", e);
+    }
+}
+
+
+
+fn run_replay(log_path: &str) {
+    let replay_log = match fs::File::open(log_path).map_err(|e| e.to_string()).and_then(|file| ReplayLog::read_from(BufReader::new(file))) {
+        Ok(replay_log) => replay_log,
+        Err(e) => {
+            println!("Error reading replay log {}: {}", log_path, e);
+            process::exit(1);
+        }
+    };
+
+    match replay_log::verify_replay(&replay_log) {
+        Ok(results) => {
+            print_results(&results);
+            println!("Replay of {} rolls matched the log", replay_log.rolls.len());
+        },
+        Err(e) => {
+            println!("Replay did not match the log: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+
+
+fn run_compare(args: &[String]) -> std::result::Result<(), String> {
+    let (paths, significance) = match args {
+        [path_a, path_b] => ([path_a, path_b], comparison::DEFAULT_SIGNIFICANCE),
+        [path_a, path_b, flag, value] if flag == "--significance" => ([path_a, path_b], parse_percent(flag, value)? / 100.0),
+        _ => return Err(String::from("compare needs two results files, and optionally --significance <pct>"))
+    };
+
+    let [results_a, results_b] = paths.map(|path| fs::File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| SavedResults::read_from(BufReader::new(file)))
+        .map_err(|e| format!("Error reading results {}: {}", path, e)));
+    let (results_a, results_b) = (results_a?, results_b?);
+
+    println!("A: {} ({})", paths[0], results_a.get_settings_summary());
+    println!("B: {} ({})", paths[1], results_b.get_settings_summary());
+
+    comparison::compare_results(&results_a, &results_b, significance)?.print_comparison();
+
+    Ok(())
+}
+
+
+
+fn run_sweep(options: SweepOptions) -> std::result::Result<(), String> {
+    let grid = &options.grid;
+
+    println!("Sweeping {} combinations of {} games each ({} turns in all), seed {}", grid.get_cell_count(), grid.game_count, grid.get_total_turns(), grid.seed);
+
+    let mut console_progress = ConsoleProgress::new();
+    stop_on_ctrl_c();
+
+    let results = sweep::run_sweep(grid, Some(&mut console_progress), Some(&STOP_REQUESTED))?;
+    console_progress.finish();
+
+    if results.interrupted {
+        println!("Stopped early, showing the {} combinations finished", results.cells.len());
+    }
+
+    match &options.out_path {
+        Some(out_path) => {
+            fs::File::create(out_path)
+                .map(io::BufWriter::new)
+                .and_then(|mut file| results.write_table(&mut file).and_then(|_| file.flush()))
+                .map_err(|e| format!("Error writing the table to {}: {}", out_path, e))?;
+
+            println!("Table written to {}", out_path);
+        },
+        None => results.write_table(&mut io::stdout().lock()).map_err(|e| e.to_string())?
+    }
+
+    if !results.cells.is_empty() {
+        results.print_rank_summary();
+    }
+
+    Ok(())
+}
+
+
+
+fn run_passage(options: PassageOptions) -> std::result::Result<(), String> {
+    let board = options.edition.create_board();
+    let target_space = options.target_space.map_or(board.len() - 1, |target_space| target_space as usize);
+
+    if target_space >= board.len() {
+        return Err(format!("--space must be below {}, the {} board's size", board.len(), options.edition.get_edition_name()));
+    }
+
+    let exact = MarkovChain::new(&board, &mut options.edition.create_card_decks(), &options.rules)?.get_passage_times(MAX_RETURN_TURNS);
+
+    let mut observer = PassageTimeObserver::new(board.len());
+    let mut console_progress = ConsoleProgress::new();
+    stop_on_ctrl_c();
+
+    let mut simulation = Simulation::new()
+        .edition(options.edition)
+        .rules(options.rules.clone())
+        .player_count(options.player_count)
+        .turn_count(options.turn_count)
+        .progress(&mut console_progress)
+        .stop_when(&STOP_REQUESTED)
+        .add_observer(&mut observer);
+
+    if let Some(seed) = options.seed {
+        simulation = simulation.seed(seed);
+    }
+
+    let results = simulation.run();
+    console_progress.finish();
+
+    println!("Simulated {} players for {} turns, seed {}{}", results.player_count, results.turn_count, results.seed, if results.interrupted { " (stopped early)" } else { "" });
+
+    let space_names: Vec<String> = results.spaces.iter().map(|space| space.name.clone()).collect();
+    let space_groups: Vec<_> = results.spaces.iter().map(|space| space.group).collect();
+
+    passage_times::print_passage_comparison(target_space, &space_names, &space_groups, &exact, &observer.get_passage_times());
+
+    Ok(())
+}
+
+
+
+fn run_bench(options: BenchOptions) -> std::result::Result<(), String> {
+    //Read the baseline first so a bad path fails before the long part
+    let baseline = match &options.baseline_path {
+        Some(baseline_path) => Some(fs::File::open(baseline_path)
+            .map_err(|e| format!("Error reading baseline {}: {}", baseline_path, e))
+            .and_then(|file| benchmark::read_baseline(BufReader::new(file)))?),
+        None => None
+    };
+
+    if cfg!(debug_assertions) {
+        println!("Warning: this is a debug build, build with --release for meaningful figures");
+        println!();
+    }
+
+    let results = benchmark::run_benchmarks(options.turn_count, options.game_count);
+    benchmark::print_benchmark_results(&results);
+
+    let phase_rates = benchmark::measure_phase_rates(PHASE_RATE_PLAYERS, PHASE_RATE_TURNS);
+
+    for engine in EngineEnum::ALL {
+        println!();
+        benchmark::print_phase_times(engine, &benchmark::time_phases(engine, PHASE_ITERATIONS), PHASE_ITERATIONS, &phase_rates);
+    }
+
+    if let Some(save_path) = options.save_path {
+        match fs::File::create(&save_path).and_then(|mut file| benchmark::write_baseline(&results, &mut file)) {
+            Ok(()) => println!("Baseline written to {}", save_path),
+            Err(e) => println!("Error writing baseline to {}: {}", save_path, e)
         }
     }
 
+    if let Some(baseline) = baseline {
+        println!();
+
+        if !benchmark::compare_to_baseline(&results, &baseline) {
+            println!("Performance regressed by more than {:.0}% against the baseline", benchmark::REGRESSION_THRESHOLD * 100.0);
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+
+
+fn print_results(results: &SimulationResults) {
+    if results.interrupted {
+        println!("*** Interrupted after {} turns, these are PARTIAL results ***", results.turn_count);
+    }
+
+    results.print_space_results();
+    results.print_group_summary();
+    results.print_rule_summary();
+    results.print_precision_summary();
+    rent_returns::print_rent_returns(&rent_returns::calculate_rent_returns(results));
+
+    println!("Seed: {}", results.seed);
+
+    if results.interrupted {
+        println!("*** PARTIAL results, interrupted after {} turns ***", results.turn_count);
+    }
+}
+
+
+
+fn check_quit_input(input: &str) {
+    if input == "q" || input == "Q" {
+        println!("Thanks for playing!  Goodbye!");
+        process::exit(0);
+    }
+}
+
+
+
+fn get_player_count() -> u32 {
     loop {
         let mut user_input = String::new();
         println!("Enter number of players (2-8): ");
 
         if let Err(e) = io::stdin().read_line(&mut user_input) {
             println!("Error reading input, please try again: {}", e);
-            println!("");
+            println!();
             continue;
         }
 
@@ -49,60 +818,54 @@ fn get_user_input() -> (u32, u32) {
 
         check_quit_input(input_num_players);
 
-        let num_players: u32 =
-            match input_num_players.trim().parse() {
-                Ok(num) if num < 2 => {
-                    println!("Number of players must be >= 2");
-                    println!("");
-                    continue;
-                },
-                Ok(num) if num > 8 => {
-                    println!("Number of players must be <= 8");
-                    println!("");
-                    continue;
-                },
-                Ok(num) => num,
-                Err(msg) => {
-                    println!("Invalid number of players: {}", msg);
-                    println!("");
-                    continue;
-                }
-            };
+        match input_num_players.trim().parse() {
+            Ok(num) if num < 2 => {
+                println!("Number of players must be >= 2");
+                println!();
+            },
+            Ok(num) if num > 8 => {
+                println!("Number of players must be <= 8");
+                println!();
+            },
+            Ok(num) => return num,
+            Err(msg) => {
+                println!("Invalid number of players: {}", msg);
+                println!();
+            }
+        };
+    };
+}
 
 
-        user_input.clear();
-        
+
+fn get_turn_count() -> u32 {
+    loop {
+        let mut user_input = String::new();
         println!("Enter number of turns (1-500): ");
 
         if let Err(e) = io::stdin().read_line(&mut user_input) {
             println!("Error reading input, please try again.  {}", e);
-            println!("");
+            println!();
             continue;
         }
         
         let input_num_turns = user_input.trim();
         check_quit_input(input_num_turns);
 
-        let num_turns: u32 =
-            match input_num_turns.trim().parse() {
-                Ok(num) if num < 1 => {
-                    println!("Number of turns must be >= 1");
-                    println!("");
-                    continue;
-                },
-                Ok(num) if num > 500 => {
-                    println!("Number of turns must be <= 500");
-                    println!("");
-                    continue;
-                },
-                Ok(num) => num,
-                Err(msg) => {
-                    println!("Invalid number of turns: {}", msg);
-                    println!("");
-                    continue;
-                }
-            };
-
-        return (num_players, num_turns);
+        match input_num_turns.trim().parse() {
+            Ok(num) if num < 1 => {
+                println!("Number of turns must be >= 1");
+                println!();
+            },
+            Ok(num) if num > 500 => {
+                println!("Number of turns must be <= 500");
+                println!();
+            },
+            Ok(num) => return num,
+            Err(msg) => {
+                println!("Invalid number of turns: {}", msg);
+                println!();
+            }
+        };
     };
 }
@@ -0,0 +1,129 @@
+mod common;
+
+use common::TinySpace;
+
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::dice_model::{DiceRoll, ScriptedDice};
+use monopoly_space_calc::game_simulation::game_board::{BoardSpace, CardDeckEnum, SpaceGroupEnum};
+use monopoly_space_calc::game_simulation::game_cards::{CardDecks, CardMoveEnum};
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::markov_chain::MarkovChain;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+
+
+const TINY_JAIL: u8 = 2;
+
+
+
+//Go, A, Jail, B, Go To Jail, C
+fn tiny_board() -> Vec<Box<dyn BoardSpace>> {
+    ["Go", "A", "Jail", "B", "Go To Jail", "C"].iter()
+        .map(|name| TinySpace::boxed(name, SpaceGroupEnum::Special))
+        .collect()
+}
+
+
+fn run_tiny_board(starting_space: u8, rolls: &[&str]) -> SimulationResults {
+    Simulation::new()
+        .player_count(1)
+        .turn_count(1)
+        .starting_positions(vec![starting_space])
+        .seed(0)
+        .board(tiny_board())
+        .dice_model(Box::new(ScriptedDice::new(rolls.iter().map(|roll| DiceRoll::from_spec(roll).unwrap()).collect()).unwrap()))
+        .rules(RuleSet::from_spec("max_doubles=0").unwrap())
+        .run()
+}
+
+
+
+//An 11 on a 6 space board goes all the way round and then some
+#[test]
+fn big_roll_wraps_more_than_once_on_a_tiny_board() {
+    let results = run_tiny_board(1, &["5+6"]);
+
+    assert_eq!(results.final_positions, vec![0]);
+    assert_eq!(results.spaces[0].landed_count, 1);
+    assert_eq!(results.get_total_landed(), 1);
+}
+
+
+#[test]
+fn go_to_jail_uses_the_boards_own_jail() {
+    let results = run_tiny_board(5, &["6+5"]);
+
+    assert_eq!(results.final_positions, vec![TINY_JAIL]);
+    assert_eq!(results.final_in_jail, vec![true]);
+    assert_eq!(results.spaces[4].landed_count, 1);
+    assert_eq!(results.spaces[TINY_JAIL as usize].landed_count, 1);
+}
+
+
+//Both engines and the exact chain agree on a board nothing like the classic one
+#[test]
+fn fast_engine_matches_markov_chain_on_a_tiny_board() {
+    let rules = RuleSet::official();
+    let expected = MarkovChain::new(&tiny_board(), &mut CardDecks::new(), &rules).unwrap().get_landing_probabilities();
+
+    let results = Simulation::new()
+        .player_count(4)
+        .turn_count(200_000)
+        .seed(7)
+        .board(tiny_board())
+        .rules(rules)
+        .run_fast()
+        .unwrap();
+
+    let total_landed = results.get_total_landed() as f64;
+
+    for (space, probability) in expected.iter().enumerate() {
+        let share = results.spaces[space].landed_count as f64 / total_landed;
+        assert!((share - probability).abs() < 0.005, "space {}: simulated {} against exact {}", space, share, probability);
+    }
+}
+
+
+#[test]
+fn board_without_a_jail_is_rejected() {
+    let board: Vec<Box<dyn BoardSpace>> = tiny_board().into_iter().filter(|space| !space.is_jail()).collect();
+
+    let error = Simulation::new().board(board).run_fast().err().unwrap();
+    assert!(error.contains("no jail"), "{}", error);
+}
+
+
+//A card naming a space its own board doesn't have would quietly never move anyone
+#[test]
+fn every_edition_card_names_a_space_on_its_board() {
+    for edition in GameEditionEnum::ALL {
+        let board = edition.create_board();
+        let mut card_decks = edition.create_card_decks();
+
+        card_decks.for_board(&board).unwrap_or_else(|e| panic!("{}: {}", edition.get_edition_name(), e));
+
+        for deck in [CardDeckEnum::Chance, CardDeckEnum::CommunityChest] {
+            let deck = card_decks.get_deck(deck);
+
+            for card_index in 0..deck.get_card_count() {
+                let card = deck.get_card(card_index).unwrap();
+
+                assert!(!matches!(card.get_card_move(), CardMoveEnum::AdvanceTo(_) | CardMoveEnum::GoBackTo(_)), "{}: '{}'", edition.get_edition_name(), card.get_card_text());
+            }
+        }
+    }
+}
+
+
+#[test]
+fn uk_cards_move_to_london_spaces() {
+    let board = GameEditionEnum::Uk.create_board();
+    let mut card_decks = GameEditionEnum::Uk.create_card_decks();
+
+    let card_moves: Vec<_> = (0..16)
+        .filter_map(|card_index| card_decks.get_community_chest_deck().get_card(card_index).unwrap().get_card_move().resolve(&board, 33))
+        .collect();
+
+    assert_eq!(card_moves, vec![0, 1, 10]);
+    assert_eq!(CardMoveEnum::AdvanceTo("Marylebone Station").resolve(&board, 36), Some(15));
+    assert_eq!(&**board[39].get_space_name(), "Mayfair");
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::precision::PrecisionTarget;
+use monopoly_space_calc::game_simulation::progress::{Progress, ProgressReporter};
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+
+
+const SEED: u64 = 99;
+
+
+
+//A fresh checkpoint path per test, so tests running in parallel never share one
+fn checkpoint_path(test_name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("monopoly-checkpoint-{}-{}", std::process::id(), test_name));
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+
+fn simulation<'a>() -> Simulation<'a> {
+    Simulation::new()
+        .player_count(4)
+        .turn_count(100_000)
+        .seed(SEED)
+        .rules(RuleSet { free_parking_jackpot: true, ..RuleSet::official() })
+}
+
+
+fn assert_same_results(results: &SimulationResults, expected: &SimulationResults) {
+    let landed_counts = |results: &SimulationResults| results.spaces.iter().map(|space| space.landed_count).collect::<Vec<_>>();
+
+    assert_eq!(results.turn_count, expected.turn_count);
+    assert_eq!(landed_counts(results), landed_counts(expected));
+    assert_eq!(results.final_positions, expected.final_positions);
+    assert_eq!(results.final_in_jail, expected.final_in_jail);
+    assert_eq!(results.rule_statistics.free_parking_paid_out, expected.rule_statistics.free_parking_paid_out);
+    assert_eq!(results.rule_statistics.jail_turns_served, expected.rule_statistics.jail_turns_served);
+}
+
+
+
+//Saving a checkpoint mustn't disturb the run, and a finished checkpoint picked back up gives the same results again
+#[test]
+fn checkpointed_run_matches_uninterrupted_run() {
+    let path = checkpoint_path("uninterrupted");
+
+    let expected = simulation().run_fast().unwrap();
+    let results = simulation().run_fast_checkpointed(&path, 7_000).unwrap();
+    let resumed = simulation().run_fast_checkpointed(&path, 7_000).unwrap();
+
+    assert_same_results(&results, &expected);
+    assert_same_results(&resumed, &expected);
+
+    fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+fn checkpointed_precision_run_matches_uninterrupted_run() {
+    let path = checkpoint_path("precision");
+    let target = PrecisionTarget::new(0.002, 0.95).unwrap().batch_turns(500);
+
+    let expected = simulation().until_precision(target).run_fast().unwrap();
+    let results = simulation().until_precision(target).run_fast_checkpointed(&path, 3_000).unwrap();
+    let resumed = simulation().until_precision(target).run_fast_checkpointed(&path, 3_000).unwrap();
+
+    for results in [&results, &resumed] {
+        assert_same_results(results, &expected);
+        assert_eq!(results.precision.as_ref().unwrap().half_widths, expected.precision.as_ref().unwrap().half_widths);
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+
+//Without a seed, resuming takes the checkpoint's rather than picking a new one
+#[test]
+fn resumed_run_takes_the_checkpoint_seed() {
+    let path = checkpoint_path("seed");
+
+    let results = simulation().run_fast_checkpointed(&path, 10_000).unwrap();
+    let resumed = Simulation::new()
+        .player_count(4)
+        .turn_count(100_000)
+        .rules(RuleSet { free_parking_jackpot: true, ..RuleSet::official() })
+        .run_fast_checkpointed(&path, 10_000)
+        .unwrap();
+
+    assert_eq!(resumed.seed, SEED);
+    assert_same_results(&resumed, &results);
+
+    fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+fn checkpoint_from_different_settings_is_rejected() {
+    let path = checkpoint_path("different");
+
+    simulation().run_fast_checkpointed(&path, 10_000).unwrap();
+
+    let error = simulation().player_count(3).run_fast_checkpointed(&path, 10_000).err().unwrap();
+    assert!(error.contains("players"), "{}", error);
+
+    let error = simulation().seed(SEED + 1).run_fast_checkpointed(&path, 10_000).err().unwrap();
+    assert!(error.contains("seed"), "{}", error);
+
+    fs::remove_file(&path).unwrap();
+}
+
+
+#[derive(Default)]
+struct ProgressLog {
+    updates: Vec<Progress>
+}
+
+impl ProgressReporter for ProgressLog {
+    fn on_progress(&mut self, progress: &Progress) {
+        self.updates.push(*progress);
+    }
+}
+
+
+//A run stopped part way through saves where it got to, and picking that checkpoint back up finishes with the same results as a
+//run that was never stopped
+#[test]
+fn stopped_run_resumes_from_its_checkpoint() {
+    let path = checkpoint_path("stopped");
+    let stop_flag = AtomicBool::new(true);
+
+    let expected = simulation().run_fast().unwrap();
+    let stopped = simulation().stop_when(&stop_flag).run_fast_checkpointed(&path, 50_000).unwrap();
+
+    assert!(stopped.interrupted);
+    assert!(stopped.turn_count < 100_000);
+
+    let resumed = simulation().run_fast_checkpointed(&path, 50_000).unwrap();
+
+    assert!(!resumed.interrupted);
+    assert_same_results(&resumed, &expected);
+
+    fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+fn stopped_run_reports_partial_results() {
+    let stop_flag = AtomicBool::new(true);
+    let mut progress_log = ProgressLog::default();
+
+    let results = simulation().stop_when(&stop_flag).progress(&mut progress_log).run();
+
+    assert!(results.interrupted);
+    assert!(results.turn_count > 0 && results.turn_count < 100_000);
+    assert_eq!(results.get_total_player_turns(), 4 * results.turn_count as u64);
+
+    let last_update = progress_log.updates.last().unwrap();
+    assert_eq!((last_update.turns_played, last_update.turn_count), (results.turn_count as u64, 100_000));
+}
+
+
+#[test]
+fn progress_is_reported_through_to_the_end() {
+    let mut progress_log = ProgressLog::default();
+    let results = simulation().progress(&mut progress_log).run_fast().unwrap();
+
+    assert!(!results.interrupted);
+    assert!(progress_log.updates.len() > 1);
+    assert!(progress_log.updates.windows(2).all(|updates| updates[0].turns_played <= updates[1].turns_played));
+    assert_eq!(progress_log.updates.last().unwrap().get_fraction_done(), 1.0);
+}
+
+
+//A resumed run only times the turns it plays itself, the ones from the checkpoint took no time at all
+#[test]
+fn resumed_run_rates_only_the_turns_since_resuming() {
+    let path = checkpoint_path("rate");
+    let stop_flag = AtomicBool::new(true);
+
+    let stopped = simulation().stop_when(&stop_flag).run_fast_checkpointed(&path, 50_000).unwrap();
+
+    let mut progress_log = ProgressLog::default();
+    simulation().progress(&mut progress_log).run_fast_checkpointed(&path, 50_000).unwrap();
+
+    assert!(progress_log.updates.iter().all(|update| update.turns_at_start == stopped.turn_count as u64));
+
+    let progress = Progress { turns_played: 60_000, turn_count: 100_000, turns_at_start: 50_000, elapsed: Duration::from_secs(10) };
+
+    assert_eq!(progress.get_turns_per_second(), 1_000.0);
+    assert_eq!(progress.get_eta(), Some(Duration::from_secs(40)));
+
+    fs::remove_file(&path).unwrap();
+}
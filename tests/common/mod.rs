@@ -0,0 +1,181 @@
+//Shared by the integration tests.  Each test file only uses some of it
+#![allow(dead_code)]
+
+use colored::{ColoredString, Colorize};
+
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::dice_model::{DiceRoll, ScriptedDice};
+use monopoly_space_calc::game_simulation::game_board::{self, BoardSpace, SpaceActionEnum, SpaceGroupEnum};
+use monopoly_space_calc::game_simulation::game_cards::CardDecks;
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::markov_chain::MarkovChain;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::speed_die::SpeedDieEnum;
+
+
+//Classic board space indices
+pub const GO: usize = 0;
+pub const MEDITERRANEAN_AVENUE: usize = 1;
+pub const READING_RAILROAD: usize = 5;
+pub const JAIL: usize = 10;
+pub const PENNSYLVANIA_RAILROAD: usize = 15;
+pub const ILLINOIS_AVENUE: usize = 24;
+pub const B_AND_O_RAILROAD: usize = 25;
+pub const GO_TO_JAIL: usize = 30;
+pub const SHORT_LINE: usize = 35;
+pub const BOARDWALK: usize = 39;
+
+
+
+//The exact chain for the classic board and decks
+pub fn classic_chain(rules: &RuleSet) -> MarkovChain {
+    MarkovChain::new(&game_board::initialize_game_board(), &mut CardDecks::new(), rules).unwrap()
+}
+
+
+
+//A fully scripted game: where everyone starts, every roll of the dice, and the order cards come off the top of each deck
+pub struct Scenario {
+    starting_positions: Vec<u8>,
+    rolls: Vec<DiceRoll>,
+    speed_die_faces: Vec<SpeedDieEnum>,
+    chance_order: Vec<usize>,
+    community_chest_order: Vec<usize>,
+    edition: GameEditionEnum,
+    board: Option<Vec<Box<dyn BoardSpace>>>,
+    rules: RuleSet
+}
+
+impl Scenario {
+    pub fn starting_at(starting_positions: &[u8]) -> Self {
+        Scenario {
+            starting_positions: starting_positions.to_vec(),
+            rolls: Vec::new(),
+            speed_die_faces: Vec::new(),
+            chance_order: Vec::new(),
+            community_chest_order: Vec::new(),
+            edition: GameEditionEnum::Classic,
+            board: None,
+            rules: RuleSet::default()
+        }
+    }
+
+    //Rolls are written as the dice joined with '+', e.g. "3+4"
+    pub fn rolling(mut self, rolls: &[&str]) -> Self {
+        self.rolls = rolls.iter().map(|roll| DiceRoll::from_spec(roll).unwrap()).collect();
+        self
+    }
+
+    //Faces are 1, 2, 3, M (Mr. Monopoly) or B (the bus), one for each roll the speed die is used on
+    pub fn speed_die(mut self, faces: &[&str]) -> Self {
+        self.speed_die_faces = faces.iter()
+            .map(|face| match *face {
+                "M" => SpeedDieEnum::MrMonopoly,
+                "B" => SpeedDieEnum::Bus,
+                number => SpeedDieEnum::Number(number.parse().unwrap())
+            })
+            .collect();
+        self
+    }
+
+    pub fn chance_cards(mut self, card_order: &[usize]) -> Self {
+        self.chance_order = card_order.to_vec();
+        self
+    }
+
+    pub fn community_chest_cards(mut self, card_order: &[usize]) -> Self {
+        self.community_chest_order = card_order.to_vec();
+        self
+    }
+
+    //Plays on the edition's board with its default rules
+    pub fn edition(mut self, edition: GameEditionEnum) -> Self {
+        self.edition = edition;
+        self.rules = edition.get_default_rules();
+        self
+    }
+
+    pub fn on_board(mut self, board: Vec<Box<dyn BoardSpace>>) -> Self {
+        self.board = Some(board);
+        self
+    }
+
+    //Applied on top of the edition's rules
+    pub fn rules(mut self, spec: &str) -> Self {
+        self.rules = self.rules.with_spec(spec).unwrap();
+        self
+    }
+
+    pub fn run(self, turn_count: u32) -> SimulationResults {
+        let mut card_decks = self.edition.create_card_decks();
+        card_decks.get_chance_deck().stack(&self.chance_order);
+        card_decks.get_community_chest_deck().stack(&self.community_chest_order);
+
+        let mut simulation = Simulation::new()
+            .player_count(self.starting_positions.len() as u32)
+            .turn_count(turn_count)
+            .starting_positions(self.starting_positions)
+            .seed(0)
+            .edition(self.edition)
+            .dice_model(Box::new(ScriptedDice::new(self.rolls).unwrap().with_speed_die(self.speed_die_faces)))
+            .card_decks(card_decks)
+            .rules(self.rules);
+
+        if let Some(board) = self.board {
+            simulation = simulation.board(board);
+        }
+
+        simulation.run()
+    }
+}
+
+
+pub fn landed_count(results: &SimulationResults, space_index: u8) -> u64 {
+    results.spaces[space_index as usize].landed_count
+}
+
+
+
+//Just enough of a space to build small teaching boards from
+pub struct TinySpace {
+    name: ColoredString,
+    group: SpaceGroupEnum,
+    landed_count: u32
+}
+
+impl TinySpace {
+    pub fn boxed(name: &str, group: SpaceGroupEnum) -> Box<dyn BoardSpace> {
+        Box::new(TinySpace { name: name.normal(), group, landed_count: 0 })
+    }
+}
+
+impl BoardSpace for TinySpace {
+    fn get_space_name(&self) -> &ColoredString {
+        &self.name
+    }
+
+    fn get_space_group(&self) -> SpaceGroupEnum {
+        self.group
+    }
+
+    fn get_landed_count(&self) -> u32 {
+        self.landed_count
+    }
+
+    fn get_space_action(&self) -> SpaceActionEnum {
+        match &*self.name {
+            "Go" => SpaceActionEnum::LandOnGo,
+            "Go To Jail" => SpaceActionEnum::SendToJail,
+            _ => SpaceActionEnum::NoAction
+        }
+    }
+
+    fn increment_landed(&mut self) -> SpaceActionEnum {
+        self.landed_count += 1;
+        self.get_space_action()
+    }
+
+    fn is_jail(&self) -> bool {
+        &*self.name == "Jail"
+    }
+}
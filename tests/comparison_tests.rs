@@ -0,0 +1,83 @@
+use std::io::BufReader;
+
+mod common;
+
+use common::classic_chain;
+
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::comparison::{self, DEFAULT_SIGNIFICANCE};
+use monopoly_space_calc::game_simulation::precision::PrecisionTarget;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::saved_results::SavedResults;
+
+
+const TURN_COUNT: u32 = 200_000;
+
+
+
+fn run_and_save(rules: RuleSet, seed: u64) -> SavedResults {
+    let results = Simulation::new()
+        .player_count(4)
+        .seed(seed)
+        .rules(rules)
+        .until_precision(PrecisionTarget::fixed_turns(TURN_COUNT, 0.95).unwrap())
+        .run_fast()
+        .unwrap();
+
+    assert_eq!(results.turn_count, TURN_COUNT);
+
+    SavedResults::from_results(&results).unwrap()
+}
+
+
+
+#[test]
+fn saved_results_round_trip() {
+    let saved_results = run_and_save(RuleSet::official(), 1);
+
+    let mut file = Vec::new();
+    saved_results.write_to(&mut file).unwrap();
+
+    assert_eq!(SavedResults::read_from(BufReader::new(file.as_slice())).unwrap(), saved_results);
+}
+
+
+//Different seeds, same game: any difference is noise and none of it should be flagged
+#[test]
+fn reseeded_run_shows_no_real_changes() {
+    let comparison = comparison::compare_results(&run_and_save(RuleSet::default(), 1), &run_and_save(RuleSet::default(), 2), DEFAULT_SIGNIFICANCE).unwrap();
+
+    assert_eq!(comparison.setting_differences, vec!["seed"]);
+    assert_eq!(comparison.get_significant_spaces().count(), 0);
+    assert_eq!(comparison.get_significant_groups().count(), 0);
+}
+
+
+//Staying in jail for up to three turns instead of paying out straight away is a real change.  Every space the exact chain moves by
+//more than a tenth of a percent should be flagged
+#[test]
+fn jail_rule_change_is_flagged() {
+    let comparison = comparison::compare_results(&run_and_save(RuleSet::default(), 1), &run_and_save(RuleSet::official(), 2), DEFAULT_SIGNIFICANCE).unwrap();
+
+    let exact = |rules: &RuleSet| classic_chain(rules).get_landing_probabilities();
+    let (exact_default, exact_official) = (exact(&RuleSet::default()), exact(&RuleSet::official()));
+
+    assert_eq!(comparison.setting_differences, vec!["seed", "rules"]);
+
+    let big_movers: Vec<usize> = (0..comparison.spaces.len()).filter(|space_index| (exact_official[*space_index] - exact_default[*space_index]).abs() > 0.001).collect();
+    assert!(!big_movers.is_empty());
+
+    for space_index in big_movers {
+        let difference = &comparison.spaces[space_index];
+        assert!(difference.significant, "{} moved by {:.5} but wasn't flagged", difference.name, difference.get_change());
+    }
+}
+
+
+#[test]
+fn boards_of_different_sizes_cant_be_compared() {
+    let mut results_b = run_and_save(RuleSet::default(), 2);
+    results_b.spaces.pop();
+
+    assert!(comparison::compare_results(&run_and_save(RuleSet::default(), 1), &results_b, DEFAULT_SIGNIFICANCE).is_err());
+}
@@ -0,0 +1,77 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use monopoly_space_calc::game_simulation::dice_model::{DiceModel, SingleDie, ThreeDice, WeightedDice};
+
+
+//Enough rolls that every share below is well inside its tolerance, fixed seed so a failure always reproduces
+const ROLL_COUNT: usize = 120_000;
+const SEED: u64 = 33;
+
+const SHARE_TOLERANCE: f64 = 0.005;
+
+
+
+//How often each total from 0 to 18 came up, as a share of all the rolls
+fn roll_shares(dice_model: &mut dyn DiceModel, dice_count: usize) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut counts = [0; 19];
+
+    for _ in 0..ROLL_COUNT {
+        let roll = dice_model.roll(&mut rng);
+
+        assert_eq!(roll.get_dice().len(), dice_count);
+        assert!(!roll.is_doubles());
+
+        counts[roll.get_total() as usize] += 1;
+    }
+
+    counts.iter().map(|count| *count as f64 / ROLL_COUNT as f64).collect()
+}
+
+
+fn assert_share(shares: &[f64], total: usize, expected: f64) {
+    assert!((shares[total] - expected).abs() < SHARE_TOLERANCE, "{} came up {:.4} of the time, expected {:.4}", total, shares[total], expected);
+}
+
+
+
+#[test]
+fn single_die_is_uniform_over_one_to_six() {
+    let shares = roll_shares(&mut SingleDie, 1);
+
+    for total in 1..=6 {
+        assert_share(&shares, total, 1.0 / 6.0);
+    }
+
+    assert_eq!(shares[0] + shares[7..].iter().sum::<f64>(), 0.0);
+}
+
+
+#[test]
+fn three_dice_total_three_to_eighteen() {
+    let shares = roll_shares(&mut ThreeDice, 3);
+
+    //Ways to make each total from three dice, out of 216
+    let ways = [1, 3, 6, 10, 15, 21, 25, 27, 27, 25, 21, 15, 10, 6, 3, 1];
+
+    for (total, ways) in (3..=18).zip(ways) {
+        assert_share(&shares, total, ways as f64 / 216.0);
+    }
+
+    assert_eq!(shares[..3].iter().sum::<f64>(), 0.0);
+}
+
+
+#[test]
+fn weighted_faces_follow_their_weights() {
+    //A die loaded towards 6, three times as likely as any other face
+    let mut loaded_die = WeightedDice::new(1, vec![(1, 1.0), (2, 1.0), (3, 1.0), (4, 1.0), (5, 1.0), (6, 3.0)]).unwrap();
+    let shares = roll_shares(&mut loaded_die, 1);
+
+    for total in 1..=5 {
+        assert_share(&shares, total, 1.0 / 8.0);
+    }
+
+    assert_share(&shares, 6, 3.0 / 8.0);
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+mod common;
+
+use common::{classic_chain, GO, ILLINOIS_AVENUE, JAIL, READING_RAILROAD};
+
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::markov_chain::ChainStateEnum;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::simulation_events::{SimulationEventEnum, SimulationObserver};
+
+
+//Short games, and enough of them (every player's game is a separate sample) that the simulated shares are within a few tenths
+//of a percent of the exact ones
+const PLAYER_COUNT: u32 = 8;
+const GAME_COUNT: u64 = 4000;
+const TURN_COUNT: u32 = 3;
+
+//Largest gap allowed between a simulated and exact probability.  Decks are shuffled once per game rather than drawn
+//independently, which the chain doesn't follow, so this is a little looser than sampling noise alone needs
+const MAX_DIFFERENCE: f64 = 0.01;
+
+
+
+fn start_of_game() -> ChainStateEnum {
+    ChainStateEnum::OnSpace { space: GO as u8, doubles_count: 0 }
+}
+
+
+
+//Remembers which players have landed on any of the target spaces
+struct HitObserver {
+    target_spaces: Vec<u8>,
+    players_hit: HashSet<String>
+}
+
+impl SimulationObserver for HitObserver {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        if let SimulationEventEnum::LandedOnSpace { player_name, space_index, .. } = event {
+            if self.target_spaces.contains(space_index) {
+                self.players_hit.insert(player_name.to_string());
+            }
+        }
+    }
+}
+
+
+
+//On a third doubles the only ways to finish the turn anywhere but where the roll lands are the dice sending them to jail, and
+//the Chance card that does the same.  Nothing rolls again
+#[test]
+fn third_doubles_ends_the_turn_in_jail() {
+    let distribution = classic_chain(&RuleSet::default()).get_turn_distribution(ChainStateEnum::OnSpace { space: GO as u8, doubles_count: 2 }, 1).unwrap();
+
+    //Doubles, 4+6 or 6+4 straight onto the jail, or 7 onto Chance and its one Go To Jail card out of 16
+    let expected_jail = 6.0 / 36.0 + 2.0 / 36.0 + (6.0 / 36.0) * (1.0 / 16.0);
+
+    assert!((distribution[JAIL] - expected_jail).abs() < 1e-12, "{}", distribution[JAIL]);
+    assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+}
+
+
+#[test]
+fn turn_distribution_matches_short_simulated_games() {
+    let rules = RuleSet::official();
+    let exact = classic_chain(&rules).get_turn_distribution(start_of_game(), TURN_COUNT).unwrap();
+
+    let mut finished_on = vec![0u64; exact.len()];
+
+    for game in 0..GAME_COUNT {
+        let results = Simulation::new().player_count(PLAYER_COUNT).turn_count(TURN_COUNT).seed(game).rules(rules.clone()).run_fast().unwrap();
+
+        for final_space in results.final_positions {
+            finished_on[final_space as usize] += 1;
+        }
+    }
+
+    let sample_count = (GAME_COUNT * PLAYER_COUNT as u64) as f64;
+
+    for (space, exact_probability) in exact.iter().enumerate() {
+        let simulated = finished_on[space] as f64 / sample_count;
+
+        assert!((simulated - exact_probability).abs() < MAX_DIFFERENCE, "space {}: simulated {:.4}, exact {:.4}", space, simulated, exact_probability);
+    }
+}
+
+
+#[test]
+fn hitting_probability_matches_short_simulated_games() {
+    let rules = RuleSet::official();
+    let target_spaces = vec![READING_RAILROAD as u8, ILLINOIS_AVENUE as u8];
+    let exact = classic_chain(&rules).get_hitting_probability(start_of_game(), &target_spaces, TURN_COUNT).unwrap();
+
+    let mut hit_count = 0;
+
+    for game in 0..GAME_COUNT {
+        let mut observer = HitObserver { target_spaces: target_spaces.clone(), players_hit: HashSet::new() };
+
+        Simulation::new().player_count(PLAYER_COUNT).turn_count(TURN_COUNT).seed(game).rules(rules.clone()).add_observer(&mut observer).run();
+
+        hit_count += observer.players_hit.len();
+    }
+
+    let simulated = hit_count as f64 / (GAME_COUNT * PLAYER_COUNT as u64) as f64;
+
+    assert!((simulated - exact).abs() < MAX_DIFFERENCE, "simulated {:.4}, exact {:.4}", simulated, exact);
+}
+
+
+#[test]
+fn hitting_probabilities_only_ever_grow() {
+    let hitting_probabilities = classic_chain(&RuleSet::default()).get_hitting_probabilities(start_of_game(), &[JAIL as u8], 20).unwrap();
+
+    assert_eq!(hitting_probabilities.len(), 21);
+    assert_eq!(hitting_probabilities[0], 0.0);
+    assert!(hitting_probabilities.windows(2).all(|pair| pair[1] >= pair[0]));
+    assert!(hitting_probabilities[20] > 0.5 && hitting_probabilities[20] < 1.0);
+}
+
+
+#[test]
+fn impossible_start_states_are_rejected() {
+    let markov_chain = classic_chain(&RuleSet::default());
+
+    assert!(markov_chain.get_turn_distribution(ChainStateEnum::OnSpace { space: 40, doubles_count: 0 }, 1).is_err());
+    assert!(markov_chain.get_turn_distribution(ChainStateEnum::OnSpace { space: GO as u8, doubles_count: 3 }, 1).is_err());
+    assert!(markov_chain.get_turn_distribution(ChainStateEnum::InJail { jail_turns: 1 }, 1).is_err());
+    assert!(markov_chain.get_hitting_probability(start_of_game(), &[40], 1).is_err());
+
+    //Paying straight away, starting in jail is a normal turn from the jail space
+    assert!(markov_chain.get_turn_distribution(ChainStateEnum::InJail { jail_turns: 0 }, 1).is_ok());
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{classic_chain, BOARDWALK, GO, GO_TO_JAIL, ILLINOIS_AVENUE, JAIL, READING_RAILROAD};
+
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::passage_times::{PassageTimeObserver, PassageTimes};
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+
+
+//Long enough that every player lands on every space hundreds of times, so the simulated means are within a few percent
+const PLAYER_COUNT: u32 = 4;
+const TURN_COUNT: u32 = 40_000;
+const SEED: u64 = 7;
+
+//Far enough ahead that hardly any return is left off the end of the exact distributions
+const MAX_RETURN_TURNS: u32 = 2000;
+
+//Largest relative gap allowed between a simulated and exact mean
+const MAX_RELATIVE_DIFFERENCE: f64 = 0.06;
+
+
+
+fn exact_passage_times(rules: &RuleSet) -> PassageTimes {
+    classic_chain(rules).get_passage_times(MAX_RETURN_TURNS)
+}
+
+
+fn simulated_passage_times(rules: &RuleSet) -> PassageTimes {
+    let mut observer = PassageTimeObserver::new(40);
+
+    Simulation::new().player_count(PLAYER_COUNT).turn_count(TURN_COUNT).seed(SEED).rules(rules.clone()).add_observer(&mut observer).run();
+
+    observer.get_passage_times()
+}
+
+
+fn assert_close(simulated: f64, exact: f64, what: &str) {
+    assert!(((simulated - exact) / exact).abs() < MAX_RELATIVE_DIFFERENCE, "{}: simulated {:.2}, exact {:.2}", what, simulated, exact);
+}
+
+
+
+#[test]
+fn simulated_passage_times_match_exact() {
+    let rules = RuleSet::official();
+    let exact = exact_passage_times(&rules);
+    let simulated = simulated_passage_times(&rules);
+
+    for (from_space, to_space) in [(GO, BOARDWALK), (JAIL, ILLINOIS_AVENUE), (BOARDWALK, READING_RAILROAD), (ILLINOIS_AVENUE, JAIL)] {
+        assert_close(simulated.first_passage_turns[from_space][to_space], exact.first_passage_turns[from_space][to_space], &format!("{} to {}", from_space, to_space));
+    }
+
+    for space in [READING_RAILROAD, JAIL, ILLINOIS_AVENUE, BOARDWALK] {
+        assert_close(simulated.mean_return_turns[space], exact.mean_return_turns[space], &format!("returns to {}", space));
+        assert_close(simulated.get_return_probability_within(space, 10), exact.get_return_probability_within(space, 10), &format!("returns to {} within 10 turns", space));
+    }
+
+    //No turn ever ends on Go To Jail, so nothing is measured from it
+    assert!(simulated.first_passage_turns[GO_TO_JAIL][BOARDWALK].is_infinite());
+}
+
+
+//The mean return time comes straight from how often turns land on a space.  Working it out from the whole distribution instead
+//has to give the same answer
+#[test]
+fn exact_return_time_distributions_agree_with_their_means() {
+    let exact = exact_passage_times(&RuleSet::default());
+
+    for space in 0..40 {
+        let distribution = &exact.return_time_distributions[space];
+
+        assert_eq!(distribution[0], 0.0);
+        assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-6, "space {}", space);
+
+        let mean: f64 = distribution.iter().enumerate().map(|(turns, probability)| turns as f64 * probability).sum();
+        assert!((mean - exact.mean_return_turns[space]).abs() < 1e-3, "space {}: {} from the distribution, {} from the landing rate", space, mean, exact.mean_return_turns[space]);
+    }
+}
@@ -0,0 +1,94 @@
+use monopoly_space_calc::SimulationResults;
+use monopoly_space_calc::game_simulation::game_board::{SpaceGroupEnum, StreetDetails};
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::rent_returns::{self, DevelopmentLevelEnum, RentReturnRow};
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::simulation_results::{RuleStatistics, SpaceResult};
+
+
+const PLAYER_TURNS: u32 = 1000;
+
+
+
+fn space(space_index: u8, name: &str, group: SpaceGroupEnum, street_details: Option<StreetDetails>, landed_count: u64) -> SpaceResult {
+    SpaceResult { space_index, name: String::from(name), group, street_details, landed_count }
+}
+
+
+//One player over 1000 turns, landing on Mediterranean Avenue 100 times and Boardwalk 50 times
+fn hand_made_results() -> SimulationResults {
+    SimulationResults {
+        player_count: 1,
+        turn_count: PLAYER_TURNS,
+        seed: 0,
+        edition: GameEditionEnum::Classic,
+        dice_model: String::from("2d6"),
+        rules: RuleSet::default(),
+        spaces: vec![
+            space(0, "Go", SpaceGroupEnum::Special, None, 400),
+            space(1, "Mediterranean Avenue", SpaceGroupEnum::Brown, Some(StreetDetails { price: 60, house_cost: 50, rents: [2, 10, 30, 90, 160, 250] }), 100),
+            space(2, "Boardwalk", SpaceGroupEnum::DarkBlue, Some(StreetDetails { price: 400, house_cost: 200, rents: [50, 200, 600, 1400, 1700, 2000] }), 50)
+        ],
+        final_positions: vec![0],
+        final_in_jail: vec![false],
+        rule_statistics: RuleStatistics::default(),
+        precision: None,
+        interrupted: false
+    }
+}
+
+
+fn find_row<'a>(rows: &'a [RentReturnRow], space_name: &str, level: DevelopmentLevelEnum) -> &'a RentReturnRow {
+    rows.iter().find(|row| row.space_name.contains(space_name) && row.level == level).unwrap()
+}
+
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "got {}, expected {}", actual, expected);
+}
+
+
+
+#[test]
+fn rent_returns_follow_the_rent_schedule() {
+    let rows = rent_returns::calculate_rent_returns(&hand_made_results());
+
+    //Every level of both streets, and nothing for Go
+    assert_eq!(rows.len(), 2 * DevelopmentLevelEnum::ALL.len());
+
+    let unimproved = find_row(&rows, "Mediterranean Avenue", DevelopmentLevelEnum::Unimproved);
+    assert_close(unimproved.landing_probability, 0.1);
+    assert_close(unimproved.expected_rent, 0.2);
+    assert_eq!(unimproved.investment, 60);
+    assert_close(unimproved.break_even_turns, 300.0);
+
+    //Owning the whole group doubles the unimproved rent without costing anything more
+    let monopoly = find_row(&rows, "Mediterranean Avenue", DevelopmentLevelEnum::Monopoly);
+    assert_close(monopoly.expected_rent, 0.4);
+    assert_eq!(monopoly.investment, 60);
+    assert_close(monopoly.break_even_turns, 150.0);
+
+    //A hotel is the street plus five houses
+    let hotel = find_row(&rows, "Boardwalk", DevelopmentLevelEnum::Hotel);
+    assert_close(hotel.expected_rent, 100.0);
+    assert_eq!(hotel.investment, 1400);
+    assert_close(hotel.break_even_turns, 14.0);
+    assert_close(hotel.roi_percent, 100.0 * 100.0 / 1400.0);
+}
+
+
+#[test]
+fn rent_returns_are_ranked_by_roi() {
+    let rows = rent_returns::calculate_rent_returns(&hand_made_results());
+
+    assert!(rows.windows(2).all(|pair| pair[0].roi_percent >= pair[1].roi_percent));
+
+    //$25 a turn on $310 beats $100 a turn on $1400
+    assert!(rows[0].space_name.contains("Mediterranean Avenue"));
+    assert_eq!(rows[0].level, DevelopmentLevelEnum::Hotel);
+    assert!(rows[1].space_name.contains("Boardwalk"));
+    assert_eq!(rows[1].level, DevelopmentLevelEnum::Hotel);
+
+    //Unimproved streets are the worst return of all
+    assert_eq!(rows[rows.len() - 1].level, DevelopmentLevelEnum::Unimproved);
+}
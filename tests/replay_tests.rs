@@ -0,0 +1,63 @@
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::replay_log::{self, ReplayLog, ReplayRecorder};
+
+
+//Header lines ahead of the first roll: version, players, turns, seed, edition, dice and rules
+const HEADER_LINES: usize = 7;
+
+//The roll edited when checking a tampered log is caught
+const EDITED_ROLL: usize = 5;
+
+
+
+fn record_game() -> ReplayLog {
+    let mut recorder = ReplayRecorder::new();
+
+    let results = Simulation::new().player_count(3).turn_count(50).seed(11).add_observer(&mut recorder).run();
+
+    recorder.finish(&results)
+}
+
+
+fn write_log(log: &ReplayLog) -> String {
+    let mut buffer = Vec::<u8>::new();
+    log.write_to(&mut buffer).unwrap();
+
+    String::from_utf8(buffer).unwrap()
+}
+
+
+
+#[test]
+fn recorded_game_reloads_and_verifies() {
+    let log = record_game();
+    let reloaded = ReplayLog::read_from(write_log(&log).as_bytes()).unwrap();
+
+    assert_eq!(reloaded, log);
+
+    let results = replay_log::verify_replay(&reloaded).unwrap();
+
+    assert_eq!(results.final_positions, log.final_positions);
+    assert_eq!(results.spaces.iter().map(|space| space.landed_count).collect::<Vec<_>>(), log.landed_counts);
+}
+
+
+#[test]
+fn edited_dice_are_caught_at_the_roll_they_change() {
+    let log = record_game();
+    let mut lines: Vec<String> = write_log(&log).lines().map(String::from).collect();
+
+    //Swap the dice for a roll with a different total, leaving the rest of the line (cards, final space) as it was
+    let roll_line = &mut lines[HEADER_LINES + EDITED_ROLL - 1];
+    let (dice, rest) = roll_line.split_once(' ').unwrap();
+    let edited_dice = if dice == "1+2" || dice == "2+1" { "1+3" } else { "1+2" };
+    *roll_line = format!("{} {}", edited_dice, rest);
+
+    let edited = ReplayLog::read_from(lines.join("\n").as_bytes()).unwrap();
+    let error = match replay_log::verify_replay(&edited) {
+        Ok(_) => panic!("The edited log verified"),
+        Err(error) => error
+    };
+
+    assert!(error.starts_with(&format!("Roll {} diverged", EDITED_ROLL)), "{}", error);
+}
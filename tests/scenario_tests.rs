@@ -0,0 +1,289 @@
+mod common;
+
+use common::{landed_count, Scenario};
+
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::dice_model::{DiceRoll, ScriptedDice};
+use monopoly_space_calc::game_simulation::game_board::{self, SpaceGroupEnum};
+use monopoly_space_calc::game_simulation::game_cards::{CardDecks, CardMoveEnum};
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::simulation_events::{SimulationEventEnum, SimulationObserver};
+
+
+//Card indices as defined in game_cards.rs
+const CHANCE_GO_BACK_3: usize = 9;
+const CHANCE_GO_TO_JAIL: usize = 10;
+const CHANCE_NEAREST_RAILROAD: usize = 4;
+const COMMUNITY_CHEST_ADVANCE_TO_GO: usize = 0;
+const COMMUNITY_CHEST_BANK_ERROR: usize = 1;
+
+const JAIL: u8 = 10;
+
+
+
+#[test]
+fn third_double_goes_to_jail() {
+    let results = Scenario::starting_at(&[0])
+        .rolling(&["1+1", "2+2", "3+3"])
+        .community_chest_cards(&[COMMUNITY_CHEST_BANK_ERROR])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![JAIL]);
+    assert_eq!(results.final_in_jail, vec![true]);
+    assert_eq!(landed_count(&results, 2), 1);
+    assert_eq!(landed_count(&results, 6), 1);
+    assert_eq!(landed_count(&results, JAIL), 1);
+    //The third roll never moves the player, they go straight to jail
+    assert_eq!(landed_count(&results, 12), 0);
+    assert_eq!(results.get_total_landed(), 3);
+}
+
+
+#[test]
+fn two_doubles_then_a_normal_roll_stays_out_of_jail() {
+    let results = Scenario::starting_at(&[0])
+        .rolling(&["2+2", "3+3", "1+2"])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![13]);
+    assert_eq!(results.final_in_jail, vec![false]);
+    //Passing through jail on the dice is just visiting
+    assert_eq!(landed_count(&results, JAIL), 1);
+    assert_eq!(results.get_total_landed(), 3);
+}
+
+
+#[test]
+fn doubles_turned_off_never_go_to_jail() {
+    let results = Scenario::starting_at(&[0])
+        .rolling(&["1+1", "2+2", "3+3", "4+4", "1+2"])
+        .community_chest_cards(&[COMMUNITY_CHEST_BANK_ERROR])
+        .rules("max_doubles=0")
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![23]);
+    assert_eq!(results.final_in_jail, vec![false]);
+    assert_eq!(results.get_total_landed(), 5);
+}
+
+
+#[test]
+fn chance_go_back_3_chains_into_community_chest() {
+    let results = Scenario::starting_at(&[29])
+        .rolling(&["3+4"])
+        .chance_cards(&[CHANCE_GO_BACK_3])
+        .community_chest_cards(&[COMMUNITY_CHEST_ADVANCE_TO_GO])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![0]);
+    assert_eq!(landed_count(&results, 36), 1);
+    assert_eq!(landed_count(&results, 33), 1);
+    assert_eq!(landed_count(&results, 0), 1);
+    assert_eq!(results.get_total_landed(), 3);
+}
+
+
+#[test]
+fn chance_go_back_3_stops_on_community_chest_without_a_move_card() {
+    let results = Scenario::starting_at(&[29])
+        .rolling(&["3+4"])
+        .chance_cards(&[CHANCE_GO_BACK_3])
+        .community_chest_cards(&[COMMUNITY_CHEST_BANK_ERROR])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![33]);
+    assert_eq!(results.get_total_landed(), 2);
+}
+
+
+#[test]
+fn chance_nearest_railroad_wraps_past_go() {
+    let results = Scenario::starting_at(&[29])
+        .rolling(&["3+4"])
+        .chance_cards(&[CHANCE_NEAREST_RAILROAD])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![5]);
+    assert_eq!(landed_count(&results, 5), 1);
+}
+
+
+//The same cards land on the right spaces of the 52 space Mega board, wrapping past Go both ways
+#[test]
+fn card_moves_resolve_against_the_mega_board() {
+    let board = game_board::initialize_mega_board();
+
+    assert_eq!(CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Railroads).resolve(&board, 9), Some(20));
+    assert_eq!(CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Railroads).resolve(&board, 46), Some(6));
+    assert_eq!(CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Utilities).resolve(&board, 28), Some(36));
+    assert_eq!(CardMoveEnum::AdvanceToNearest(SpaceGroupEnum::Utilities).resolve(&board, 46), Some(10));
+    assert_eq!(CardMoveEnum::AdvanceTo("Boardwalk").resolve(&board, 9), Some(51));
+    assert_eq!(CardMoveEnum::AdvanceToGo.resolve(&board, 28), Some(0));
+    assert_eq!(CardMoveEnum::GoToJail.resolve(&board, 28), Some(13));
+    assert_eq!(CardMoveEnum::MoveBy(-3).resolve(&board, 1), Some(50));
+    assert_eq!(CardMoveEnum::StayPut.resolve(&board, 9), None);
+}
+
+
+#[test]
+fn card_naming_a_missing_space_is_rejected() {
+    let board = game_board::initialize_game_board();
+
+    let error = CardMoveEnum::AdvanceTo("Mayfair").for_board(&board).err().unwrap();
+    assert!(error.contains("Mayfair"), "{}", error);
+
+    //UK cards on the US board
+    let error = Simulation::new().card_decks(CardDecks::uk()).run_fast().err().unwrap();
+    assert!(error.contains("can't be played"), "{}", error);
+}
+
+
+#[test]
+fn card_targets_are_looked_up_once_for_the_board() {
+    let board = game_board::initialize_game_board();
+
+    assert_eq!(CardMoveEnum::AdvanceTo("Boardwalk").for_board(&board), Ok(CardMoveEnum::AdvanceToSpace(39)));
+    assert_eq!(CardMoveEnum::AdvanceToSpace(39).resolve(&board, 7), Some(39));
+    assert_eq!(CardMoveEnum::MoveBy(-3).resolve(&board, 2), Some(39));
+}
+
+
+#[test]
+fn chance_go_to_jail() {
+    let results = Scenario::starting_at(&[0])
+        .rolling(&["3+4"])
+        .chance_cards(&[CHANCE_GO_TO_JAIL])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![JAIL]);
+    assert_eq!(results.final_in_jail, vec![true]);
+    assert_eq!(landed_count(&results, JAIL), 1);
+}
+
+
+#[test]
+fn go_to_jail_space_ends_the_turn_even_after_doubles() {
+    let results = Scenario::starting_at(&[20])
+        .rolling(&["5+5", "1+2"])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![JAIL]);
+    assert_eq!(results.final_in_jail, vec![true]);
+    assert_eq!(landed_count(&results, 30), 1);
+    assert_eq!(landed_count(&results, JAIL), 1);
+    assert_eq!(results.get_total_landed(), 2);
+}
+
+
+#[test]
+fn official_rules_roll_doubles_out_of_jail_without_rolling_again() {
+    let results = Scenario::starting_at(&[20])
+        .rolling(&["4+6", "1+2", "2+2", "1+2"])
+        .rules("official")
+        .run(3);
+
+    assert_eq!(results.final_positions, vec![14]);
+    assert_eq!(results.final_in_jail, vec![false]);
+    assert_eq!(results.rule_statistics.jail_turns_served, 1);
+    assert_eq!(results.rule_statistics.jail_fines_paid, 0);
+}
+
+
+//Keeps the doubles count of every roll made
+struct DoublesObserver {
+    doubles_counts: Vec<u8>
+}
+
+impl SimulationObserver for DoublesObserver {
+    fn on_event(&mut self, event: &SimulationEventEnum) {
+        if let SimulationEventEnum::DiceRolled { doubles_count, .. } = event {
+            self.doubles_counts.push(*doubles_count);
+        }
+    }
+}
+
+
+#[test]
+fn doubles_out_of_jail_dont_count_towards_a_streak() {
+    let mut observer = DoublesObserver { doubles_counts: Vec::new() };
+
+    Simulation::new()
+        .player_count(1)
+        .turn_count(2)
+        .starting_positions(vec![20])
+        .seed(0)
+        .dice_model(Box::new(ScriptedDice::new(vec![DiceRoll::from_spec("4+6").unwrap(), DiceRoll::from_spec("2+2").unwrap()]).unwrap()))
+        .rules(RuleSet::official())
+        .add_observer(&mut observer)
+        .run();
+
+    assert_eq!(observer.doubles_counts, vec![0, 0]);
+}
+
+
+#[test]
+fn official_rules_pay_the_fine_after_three_failed_rolls() {
+    let results = Scenario::starting_at(&[20])
+        .rolling(&["4+6", "1+2", "1+2", "1+3"])
+        .rules("official")
+        .run(4);
+
+    //Third failed roll pays the fine and moves with that roll
+    assert_eq!(results.final_positions, vec![14]);
+    assert_eq!(results.final_in_jail, vec![false]);
+    assert_eq!(results.rule_statistics.jail_turns_served, 2);
+    assert_eq!(results.rule_statistics.jail_fines_paid, 50);
+}
+
+
+#[test]
+fn default_rules_charge_the_jail_fine_on_the_next_turn() {
+    let results = Scenario::starting_at(&[20])
+        .rolling(&["4+6", "1+2"])
+        .rules("jail_fine=75")
+        .run(2);
+
+    assert_eq!(results.final_positions, vec![13]);
+    assert_eq!(results.rule_statistics.jail_fines_paid, 75);
+}
+
+
+#[test]
+fn free_parking_jackpot_pays_out_the_taxes_paid_in() {
+    //Income Tax, then on round to Free Parking
+    let rolls = ["1+3", "6+5", "2+3"];
+
+    let jackpot = Scenario::starting_at(&[0]).rolling(&rolls).rules("free_parking_jackpot").run(3);
+    assert_eq!(jackpot.final_positions, vec![20]);
+    assert_eq!(jackpot.rule_statistics.taxes_paid, 200);
+    assert_eq!(jackpot.rule_statistics.free_parking_paid_out, 200);
+
+    let no_jackpot = Scenario::starting_at(&[0]).rolling(&rolls).run(3);
+    assert_eq!(no_jackpot.rule_statistics.free_parking_paid_out, 0);
+}
+
+
+#[test]
+fn double_salary_is_only_paid_for_landing_exactly_on_go() {
+    let landed_on_go = Scenario::starting_at(&[35]).rolling(&["2+3"]).rules("double_salary_on_go,go_salary=300").run(1);
+    assert_eq!(landed_on_go.final_positions, vec![0]);
+    assert_eq!(landed_on_go.rule_statistics.go_bonus_paid, 300);
+
+    let passed_go = Scenario::starting_at(&[35]).rolling(&["2+4"]).rules("double_salary_on_go,go_salary=300").run(1);
+    assert_eq!(passed_go.final_positions, vec![1]);
+    assert_eq!(passed_go.rule_statistics.go_bonus_paid, 0);
+
+    let without_the_rule = Scenario::starting_at(&[35]).rolling(&["2+3"]).run(1);
+    assert_eq!(without_the_rule.rule_statistics.go_bonus_paid, 0);
+}
+
+
+#[test]
+fn passing_go_wraps_round_the_board() {
+    let results = Scenario::starting_at(&[38, 35])
+        .rolling(&["1+3", "2+4"])
+        .community_chest_cards(&[COMMUNITY_CHEST_BANK_ERROR])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![2, 1]);
+}
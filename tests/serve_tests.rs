@@ -0,0 +1,149 @@
+use std::io::Cursor;
+
+use serde_json::{json, Value};
+
+use monopoly_space_calc::game_simulation::serve::{self, JobBoard, JobRequest, MAX_FINISHED_JOBS};
+
+
+//Nothing in these tests starts a worker, so every job submitted stays queued until it's cancelled
+
+
+
+fn post_job(job_board: &JobBoard, body: &str) -> (u16, Value) {
+    serve::route_request("POST", "/jobs", body.as_bytes(), job_board)
+}
+
+
+fn get_error(response: &(u16, Value)) -> &str {
+    response.1["error"].as_str().unwrap()
+}
+
+
+
+#[test]
+fn job_request_fills_in_defaults_and_edition_rules() {
+    let request = JobRequest::from_json(&json!({ "board": "mega", "seed": 7, "rules": "jail_fine=75" })).unwrap().to_json();
+
+    assert_eq!(request["players"], 4);
+    assert_eq!(request["turns"], 1000);
+    assert_eq!(request["games"], 1);
+    assert_eq!(request["seed"], 7);
+    assert_eq!(request["dice"], "2d6");
+    //Rules go on top of the board's own, so Mega keeps its speed die
+    assert!(request["rules"].as_str().unwrap().contains("jail_fine=75"));
+    assert!(request["rules"].as_str().unwrap().contains("speed_die=true"));
+}
+
+
+#[test]
+fn job_request_rejects_bad_parameters() {
+    for (body, expected) in [
+        (json!({ "players": 9 }), "players"),
+        (json!({ "players": "four" }), "players"),
+        (json!({ "games": 0 }), "games"),
+        (json!({ "board": "atlantis" }), "atlantis"),
+        (json!({ "colour": "red" }), "colour"),
+        (json!([4, 1000]), "JSON object")
+    ] {
+        let error = JobRequest::from_json(&body).err().unwrap();
+        assert!(error.contains(expected), "{}: {}", body, error);
+    }
+}
+
+
+#[test]
+fn bad_job_requests_are_answered_with_400() {
+    let job_board = JobBoard::new(MAX_FINISHED_JOBS);
+
+    let response = post_job(&job_board, r#"{"players": 1}"#);
+    assert_eq!(response.0, 400);
+    assert!(get_error(&response).contains("players"));
+
+    let response = post_job(&job_board, r#"{"turns": 100, "speed": 3}"#);
+    assert_eq!(response.0, 400);
+    assert!(get_error(&response).contains("speed"));
+
+    let response = post_job(&job_board, "{players");
+    assert_eq!(response.0, 400);
+
+    //None of them made it onto the board
+    assert_eq!(serve::route_request("GET", "/jobs", &[], &job_board).1["jobs"], json!([]));
+}
+
+
+#[test]
+fn unfinished_job_has_no_results() {
+    let job_board = JobBoard::new(MAX_FINISHED_JOBS);
+
+    let (status_code, job) = post_job(&job_board, r#"{"games": 2}"#);
+    assert_eq!(status_code, 202);
+    assert_eq!(job["status"], "queued");
+
+    let response = serve::route_request("GET", &format!("/jobs/{}/results", job["id"]), &[], &job_board);
+    assert_eq!(response.0, 409);
+    assert!(get_error(&response).contains("queued"));
+}
+
+
+#[test]
+fn deleting_a_queued_job_cancels_it() {
+    let job_board = JobBoard::new(MAX_FINISHED_JOBS);
+    let job_id = post_job(&job_board, "{}").1["id"].as_u64().unwrap();
+    let job_path = format!("/jobs/{}", job_id);
+
+    let (status_code, job) = serve::route_request("DELETE", &job_path, &[], &job_board);
+    assert_eq!(status_code, 200);
+    assert_eq!(job["status"], "cancelled");
+
+    assert_eq!(serve::route_request("GET", &job_path, &[], &job_board).1["status"], "cancelled");
+    assert_eq!(serve::route_request("GET", &format!("{}/results", job_path), &[], &job_board).0, 409);
+    assert_eq!(serve::route_request("DELETE", "/jobs/999", &[], &job_board).0, 404);
+}
+
+
+#[test]
+fn only_the_most_recent_finished_jobs_are_kept() {
+    let job_board = JobBoard::new(2);
+
+    for _ in 0..4 {
+        let job_id = post_job(&job_board, "{}").1["id"].as_u64().unwrap();
+        serve::route_request("DELETE", &format!("/jobs/{}", job_id), &[], &job_board);
+    }
+
+    //Submitting clears out the oldest finished jobs, but never one still waiting to run
+    post_job(&job_board, "{}");
+
+    let jobs = serve::route_request("GET", "/jobs", &[], &job_board).1;
+    let job_ids: Vec<u64> = jobs["jobs"].as_array().unwrap().iter().map(|job| job["id"].as_u64().unwrap()).collect();
+
+    assert_eq!(job_ids, vec![3, 4, 5]);
+    assert_eq!(serve::route_request("GET", "/jobs/1", &[], &job_board).0, 404);
+}
+
+
+#[test]
+fn read_request_takes_the_method_path_and_body() {
+    let request = "POST /jobs/?wait=1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 15\r\n\r\n{\"players\": 3}\n";
+    let (method, path, body) = serve::read_request(&mut Cursor::new(request)).unwrap();
+
+    assert_eq!(method, "POST");
+    assert_eq!(path, "/jobs");
+    assert_eq!(body, b"{\"players\": 3}\n");
+}
+
+
+#[test]
+fn read_request_rejects_malformed_requests() {
+    let error = serve::read_request(&mut Cursor::new("GARBAGE\r\n\r\n")).err().unwrap();
+    assert!(error.contains("Malformed"), "{}", error);
+
+    let error = serve::read_request(&mut Cursor::new("POST /jobs HTTP/1.1\r\nContent-Length: many\r\n\r\n")).err().unwrap();
+    assert!(error.contains("Content-Length"), "{}", error);
+
+    let error = serve::read_request(&mut Cursor::new("POST /jobs HTTP/1.1\r\nContent-Length: 1000000\r\n\r\n")).err().unwrap();
+    assert!(error.contains("over"), "{}", error);
+
+    //Shorter than it said it would be
+    let error = serve::read_request(&mut Cursor::new("POST /jobs HTTP/1.1\r\nContent-Length: 20\r\n\r\n{}")).err().unwrap();
+    assert!(error.contains("body"), "{}", error);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::{landed_count, Scenario, TinySpace};
+
+use monopoly_space_calc::Simulation;
+use monopoly_space_calc::game_simulation::game_board::{BoardSpace, SpaceGroupEnum};
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+
+
+//Mega board space indices
+const PARK_PLACE: u8 = 49;
+const MEDITERRANEAN_AVENUE: u8 = 1;
+const BALTIC_AVENUE: u8 = 3;
+const ARCTIC_AVENUE: u8 = 4;
+const READING_RAILROAD: u8 = 6;
+const GAS_COMPANY: u8 = 10;
+const JAIL: u8 = 13;
+
+//Card indices as defined in game_cards.rs
+const CHANCE_ADVANCE_TO_ST_CHARLES_PLACE: usize = 3;
+const UK_COMMUNITY_CHEST_OLD_KENT_ROAD: usize = 1;
+
+//Three spaces from Park Place lands exactly on Go, which counts as passing it
+const ROLL_TO_GO: &str = "1+2";
+
+
+
+//Go, A, Jail, B.  A and B can be bought
+fn tiny_property_board() -> Vec<Box<dyn BoardSpace>> {
+    vec![
+        TinySpace::boxed("Go", SpaceGroupEnum::Special),
+        TinySpace::boxed("A", SpaceGroupEnum::Brown),
+        TinySpace::boxed("Jail", SpaceGroupEnum::Special),
+        TinySpace::boxed("B", SpaceGroupEnum::Brown)
+    ]
+}
+
+
+
+#[test]
+fn no_speed_die_before_passing_go() {
+    let results = Scenario::starting_at(&[GAS_COMPANY])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&["1+2"])
+        .speed_die(&["3"])
+        .run(1);
+
+    assert_eq!(results.final_positions, vec![JAIL]);
+
+    //Once round the board the speed die adds to the white dice
+    let results = Scenario::starting_at(&[PARK_PLACE])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&[ROLL_TO_GO, "1+2"])
+        .speed_die(&["3"])
+        .run(2);
+
+    assert_eq!(results.final_positions, vec![READING_RAILROAD]);
+}
+
+
+#[test]
+fn triples_move_to_the_next_unowned_property_and_end_the_turn() {
+    let results = Scenario::starting_at(&[PARK_PLACE])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&[ROLL_TO_GO, "2+2", "3+4"])
+        .speed_die(&["2"])
+        .run(2);
+
+    assert_eq!(results.final_positions, vec![MEDITERRANEAN_AVENUE]);
+    //No second roll for the doubles, so the 3+4 is never used
+    assert_eq!(landed_count(&results, MEDITERRANEAN_AVENUE), 1);
+    assert_eq!(results.get_total_landed(), 2);
+}
+
+
+#[test]
+fn mr_monopoly_moves_on_to_the_next_unowned_property() {
+    let results = Scenario::starting_at(&[PARK_PLACE])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&[ROLL_TO_GO, "1+3"])
+        .speed_die(&["M"])
+        .run(2);
+
+    //Arctic Avenue is bought first, then Mr. Monopoly skips the Income Tax for the railroad
+    assert_eq!(landed_count(&results, ARCTIC_AVENUE), 1);
+    assert_eq!(results.final_positions, vec![READING_RAILROAD]);
+}
+
+
+#[test]
+fn mr_monopoly_moves_to_an_opponents_property_once_everything_is_owned() {
+    //Player 1 buys B, player 2 goes round to buy A.  Next turn player 2 rolls Mr. Monopoly onto Go, with nothing left to buy
+    let results = Scenario::starting_at(&[0, 0])
+        .on_board(tiny_property_board())
+        .rules("speed_die")
+        .rolling(&["1+2", "1+4", "1+2", "1+2"])
+        .speed_die(&["M"])
+        .run(2);
+
+    //Player 2 skips their own A for player 1's B
+    assert_eq!(results.final_positions, vec![2, 3]);
+    assert_eq!(landed_count(&results, 0), 1);
+}
+
+
+#[test]
+fn bus_takes_the_total_when_it_reaches_an_unowned_property() {
+    let results = Scenario::starting_at(&[PARK_PLACE])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&[ROLL_TO_GO, "1+3"])
+        .speed_die(&["B"])
+        .run(2);
+
+    assert_eq!(results.final_positions, vec![ARCTIC_AVENUE]);
+}
+
+
+#[test]
+fn bus_takes_one_die_when_the_total_misses_a_property() {
+    //The total of 5 only reaches the Income Tax, the 3 reaches Baltic Avenue
+    let results = Scenario::starting_at(&[PARK_PLACE])
+        .edition(GameEditionEnum::Mega)
+        .rolling(&[ROLL_TO_GO, "2+3"])
+        .speed_die(&["B"])
+        .run(2);
+
+    assert_eq!(results.final_positions, vec![BALTIC_AVENUE]);
+}
+
+
+//A card taking the player forward past Go counts as passing it, so the speed die comes into play on the next roll
+#[test]
+fn advancing_past_go_on_a_card_brings_in_the_speed_die() {
+    let results = Scenario::starting_at(&[31])
+        .rules("speed_die")
+        .rolling(&["2+3", "1+2"])
+        .chance_cards(&[CHANCE_ADVANCE_TO_ST_CHARLES_PLACE])
+        .speed_die(&["1"])
+        .run(2);
+
+    //St. Charles Place, then the dice and the speed die on to Pennsylvania Railroad
+    assert_eq!(results.final_positions, vec![15]);
+}
+
+
+//Going back to Old Kent Road from beyond Go doesn't go round the board, so it doesn't count as passing Go
+#[test]
+fn going_back_on_a_card_never_passes_go() {
+    let results = Scenario::starting_at(&[28])
+        .edition(GameEditionEnum::Uk)
+        .rules("speed_die")
+        .rolling(&["2+3", "1+2"])
+        .community_chest_cards(&[UK_COMMUNITY_CHEST_OLD_KENT_ROAD])
+        .speed_die(&["1"])
+        .run(2);
+
+    //From Old Kent Road on the dice alone, to Income Tax
+    assert_eq!(results.final_positions, vec![4]);
+}
+
+
+//Bus tickets come out of a shared pool of 16, so a long game can't keep handing them out
+#[test]
+fn long_mega_game_finishes() {
+    let results = Simulation::new()
+        .edition(GameEditionEnum::Mega)
+        .player_count(2)
+        .turn_count(20_000)
+        .seed(1)
+        .run();
+
+    assert_eq!(results.turn_count, 20_000);
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::{classic_chain, B_AND_O_RAILROAD, GO, ILLINOIS_AVENUE, JAIL, MEDITERRANEAN_AVENUE, PENNSYLVANIA_RAILROAD, READING_RAILROAD, SHORT_LINE};
+
+use monopoly_space_calc::{Simulation, SimulationResults};
+use monopoly_space_calc::game_simulation::game_board;
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::markov_chain::MarkovChain;
+use monopoly_space_calc::game_simulation::precision::PrecisionTarget;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+
+
+//Big enough batch that sampling noise is well inside the tolerances below, fixed seed so a failure always reproduces
+const PLAYER_COUNT: u32 = 8;
+const TURN_COUNT: u32 = 40_000;
+const SEED: u64 = 20_240_101;
+
+//Chi-square critical value for 39 degrees of freedom (40 spaces) at p = 0.001.  Successive landings aren't independent, which
+//inflates the statistic a little, so anything under this is comfortably consistent with the Markov chain
+const CHI_SQUARE_CRITICAL_39: f64 = 72.05;
+
+//Largest gap allowed between any one space's simulated and exact share of all landings
+const MAX_SPACE_DIFFERENCE: f64 = 0.0015;
+
+//Tolerance on the pinned exact figures, well under the gap between neighbouring spaces
+const PINNED_TOLERANCE: f64 = 0.0002;
+
+
+
+fn run_batch(rules: &RuleSet) -> SimulationResults {
+    Simulation::new()
+        .player_count(PLAYER_COUNT)
+        .turn_count(TURN_COUNT)
+        .seed(SEED)
+        .rules(rules.clone())
+        .run()
+}
+
+
+//The fast engine is cheap enough to run a much bigger batch
+fn run_fast_batch(rules: &RuleSet) -> SimulationResults {
+    Simulation::new()
+        .player_count(PLAYER_COUNT)
+        .turn_count(TURN_COUNT * 10)
+        .seed(SEED)
+        .rules(rules.clone())
+        .run_fast()
+        .unwrap()
+}
+
+
+fn exact_landing_probabilities(rules: &RuleSet) -> Vec<f64> {
+    classic_chain(rules).get_landing_probabilities()
+}
+
+
+fn chi_square(results: &SimulationResults, expected_probabilities: &[f64]) -> f64 {
+    let total_landed = results.get_total_landed() as f64;
+
+    results.spaces.iter()
+        .zip(expected_probabilities.iter())
+        .map(|(space, probability)| {
+            let expected = probability * total_landed;
+            (space.landed_count as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+
+//Largest gap between the simulated and exact share of landings on any one space (a KS style check, space by space)
+fn max_space_difference(results: &SimulationResults, expected_probabilities: &[f64]) -> f64 {
+    expected_probabilities.iter()
+        .enumerate()
+        .map(|(space_index, probability)| (results.get_landing_probability(space_index as u8) - probability).abs())
+        .fold(0.0, f64::max)
+}
+
+
+fn most_landed(probabilities: &[f64], candidates: impl Iterator<Item = usize>) -> usize {
+    candidates.max_by(|a, b| probabilities[*a].total_cmp(&probabilities[*b])).unwrap()
+}
+
+
+fn check_against_markov_chain(rules: RuleSet) {
+    check_results_against_markov_chain(&run_batch(&rules), &rules);
+}
+
+
+fn check_results_against_markov_chain(results: &SimulationResults, rules: &RuleSet) {
+    let expected_probabilities = exact_landing_probabilities(rules);
+
+    let chi_square = chi_square(results, &expected_probabilities);
+    let max_difference = max_space_difference(results, &expected_probabilities);
+
+    assert!(chi_square < CHI_SQUARE_CRITICAL_39, "Chi-square {:.2} against the Markov steady state is over {} ({})", chi_square, CHI_SQUARE_CRITICAL_39, rules.to_spec());
+    assert!(max_difference < MAX_SPACE_DIFFERENCE, "A space is {:.5} away from the Markov steady state ({})", max_difference, rules.to_spec());
+}
+
+
+
+#[test]
+fn default_rules_match_markov_steady_state() {
+    check_against_markov_chain(RuleSet::default());
+}
+
+
+#[test]
+fn official_rules_match_markov_steady_state() {
+    check_against_markov_chain(RuleSet::official());
+}
+
+
+#[test]
+fn house_rules_without_doubles_jail_match_markov_steady_state() {
+    check_against_markov_chain(RuleSet { max_doubles_before_jail: 0, ..RuleSet::official() });
+}
+
+
+#[test]
+fn fast_engine_matches_markov_steady_state() {
+    for rules in [RuleSet::default(), RuleSet::official()] {
+        check_results_against_markov_chain(&run_fast_batch(&rules), &rules);
+    }
+}
+
+
+//Money moved is a per turn average, so both engines should land within a few percent of each other
+#[test]
+fn fast_engine_rule_statistics_match() {
+    let rules = RuleSet { free_parking_jackpot: true, double_salary_on_go: true, ..RuleSet::official() };
+
+    let results = run_batch(&rules);
+    let fast_results = run_fast_batch(&rules);
+
+    let per_turn = |results: &SimulationResults, total: u64| total as f64 / results.get_total_player_turns() as f64;
+    let check = |name: &str, total: u64, fast_total: u64| {
+        let (per_turn, fast_per_turn) = (per_turn(&results, total), per_turn(&fast_results, fast_total));
+        assert!((per_turn - fast_per_turn).abs() / per_turn < 0.03, "{} per turn: {:.3} vs {:.3} on the fast engine", name, per_turn, fast_per_turn);
+    };
+
+    check("Go bonus", results.rule_statistics.go_bonus_paid, fast_results.rule_statistics.go_bonus_paid);
+    check("Taxes", results.rule_statistics.taxes_paid, fast_results.rule_statistics.taxes_paid);
+    check("Card fees", results.rule_statistics.card_fees_paid, fast_results.rule_statistics.card_fees_paid);
+    check("Jail fines", results.rule_statistics.jail_fines_paid, fast_results.rule_statistics.jail_fines_paid);
+    check("Free Parking", results.rule_statistics.free_parking_paid_out, fast_results.rule_statistics.free_parking_paid_out);
+    check("Jail turns", results.rule_statistics.jail_turns_served, fast_results.rule_statistics.jail_turns_served);
+}
+
+
+#[test]
+fn steady_state_is_a_probability_distribution() {
+    for rules in [RuleSet::default(), RuleSet::official()] {
+        let chain = classic_chain(&rules);
+
+        let steady_state = chain.get_steady_state();
+        let landing_probabilities = chain.get_landing_probabilities();
+
+        assert!((steady_state.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((landing_probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(steady_state.iter().all(|probability| *probability >= 0.0));
+    }
+}
+
+
+//Well known figures from published analyses of the classic board under the official rules
+#[test]
+fn official_rules_match_published_reference_figures() {
+    let probabilities = exact_landing_probabilities(&RuleSet::official());
+    let board = game_board::initialize_game_board();
+
+    let properties = || (0..board.len()).filter(|space_index| board[*space_index].is_purchasable());
+    let railroads = || (0..board.len()).filter(|space_index| board[*space_index].get_space_group() == game_board::SpaceGroupEnum::Railroads);
+    let least_landed = |candidates: Vec<usize>| candidates.into_iter().min_by(|a, b| probabilities[*a].total_cmp(&probabilities[*b])).unwrap();
+
+    //Jail is the most landed space on the board, and Illinois Avenue the most landed property
+    assert_eq!(most_landed(&probabilities, 0..board.len()), JAIL);
+    assert_eq!(most_landed(&probabilities, properties()), ILLINOIS_AVENUE);
+
+    //Mediterranean Avenue is the least landed property, and Short Line the least landed railroad (both nearest railroad cards
+    //from the last Chance space skip it)
+    assert_eq!(least_landed(properties().collect()), MEDITERRANEAN_AVENUE);
+    assert_eq!(least_landed(railroads().collect()), SHORT_LINE);
+}
+
+
+//Exact figures from the Markov chain, pinned.  The simulation and the chain share the board and the card moves, so a mistake
+//in either would move both together and the chi-square checks above would never notice
+#[test]
+fn official_rules_exact_figures_are_unchanged() {
+    let probabilities = exact_landing_probabilities(&RuleSet::official());
+
+    let pinned = [(GO, 0.02856), (READING_RAILROAD, 0.02745), (JAIL, 0.05730), (ILLINOIS_AVENUE, 0.02932), (SHORT_LINE, 0.02244)];
+
+    for (space_index, pinned_probability) in pinned {
+        assert!((probabilities[space_index] - pinned_probability).abs() < PINNED_TOLERANCE,
+            "Space {} lands {:.5} of the time, expected {:.5}", space_index, probabilities[space_index], pinned_probability);
+    }
+}
+
+
+//The 2008 decks traded the Chance crossword competition card for a second nearest Railroad card.  Chance players all head for whichever of the
+//first three railroads is next, so each gains about a tenth of a percent of all landings while Short Line, which no Chance space
+//is nearest to, barely moves
+#[test]
+fn current_decks_send_more_players_to_the_railroads_than_pre_2008_decks() {
+    let rules = RuleSet::official();
+    let board = game_board::initialize_game_board();
+    let current = MarkovChain::new(&board, &mut GameEditionEnum::Classic.create_card_decks(), &rules).unwrap().get_landing_probabilities();
+    let pre_2008 = MarkovChain::new(&board, &mut GameEditionEnum::ClassicPre2008.create_card_decks(), &rules).unwrap().get_landing_probabilities();
+
+    for railroad in [READING_RAILROAD, PENNSYLVANIA_RAILROAD, B_AND_O_RAILROAD] {
+        let shift = current[railroad] - pre_2008[railroad];
+        assert!(shift > 0.0009 && shift < 0.002, "Space {} moved by {:.5}", railroad, shift);
+    }
+
+    assert!((current[SHORT_LINE] - pre_2008[SHORT_LINE]).abs() < 0.0002);
+}
+
+
+//Both engines stop on a batch boundary once every space is precise enough, and the intervals they report mostly cover the exact
+//figures.  At 99% confidence a couple of the 40 spaces missing is still plausible, more than that means the intervals are too tight
+#[test]
+fn precision_target_intervals_cover_markov_steady_state() {
+    let rules = RuleSet::official();
+    let expected_probabilities = exact_landing_probabilities(&rules);
+    let target = PrecisionTarget::new(0.001, 0.99).unwrap().batch_turns(500);
+
+    let simulation = || Simulation::new().player_count(4).seed(SEED).rules(rules.clone()).until_precision(target);
+
+    for results in [simulation().run(), simulation().run_fast().unwrap()] {
+        let precision = results.precision.as_ref().unwrap();
+
+        assert!(precision.target_reached);
+        assert!(precision.get_largest_half_width() < 0.001);
+        assert_eq!(results.turn_count, precision.batch_count * 500);
+
+        let uncovered = expected_probabilities.iter()
+            .enumerate()
+            .filter(|(space_index, probability)| (results.get_landing_probability(*space_index as u8) - *probability).abs() > precision.half_widths[*space_index])
+            .count();
+
+        assert!(uncovered <= 2, "{} spaces' exact landing probabilities are outside their intervals", uncovered);
+    }
+}
@@ -0,0 +1,72 @@
+use monopoly_space_calc::game_simulation::game_edition::GameEditionEnum;
+use monopoly_space_calc::game_simulation::rule_set::RuleSet;
+use monopoly_space_calc::game_simulation::sweep::{self, SweepGrid};
+
+
+const MEDITERRANEAN_AVENUE: usize = 1;
+
+
+
+fn small_grid() -> SweepGrid {
+    SweepGrid {
+        edition: GameEditionEnum::Classic,
+        player_counts: vec![2, 4],
+        turn_counts: vec![1, 100],
+        rule_variants: vec![(String::from("default"), RuleSet::default()), (String::from("official"), RuleSet::official())],
+        game_count: 50,
+        seed: 3
+    }
+}
+
+
+
+#[test]
+fn sweep_plays_every_cell_and_writes_a_tidy_table() {
+    let grid = small_grid();
+    let results = sweep::run_sweep(&grid, None, None).unwrap();
+
+    assert_eq!(results.cells.len(), 8);
+    assert!(!results.interrupted);
+
+    //Grid order is players, then turns, then rules
+    let cell_names: Vec<String> = results.cells.iter().map(|cell| cell.get_cell_name()).collect();
+    assert_eq!(&cell_names[..3], ["2p/1t/default", "2p/1t/official", "2p/100t/default"]);
+
+    let mut table = Vec::new();
+    results.write_table(&mut table).unwrap();
+    let table = String::from_utf8(table).unwrap();
+
+    assert_eq!(table.lines().count(), 1 + 8 * 40);
+    assert!(table.starts_with("players,turns,rules,space_index,space,group,landed_count,probability,rank\n"));
+
+    let rerun = sweep::run_sweep(&grid, None, None).unwrap();
+    assert!(results.cells.iter().zip(rerun.cells.iter()).all(|(cell, rerun_cell)| cell.landed_counts == rerun_cell.landed_counts));
+}
+
+
+//Nothing rolled from Go in a single turn, doubles included, ends on the first space past it.  Games long enough to lap the board
+//land there like anywhere else
+#[test]
+fn one_turn_games_never_reach_mediterranean_avenue() {
+    let results = sweep::run_sweep(&small_grid(), None, None).unwrap();
+
+    for cell in results.cells.iter() {
+        match cell.turn_count {
+            1 => assert_eq!(cell.landed_counts[MEDITERRANEAN_AVENUE], 0, "{}", cell.get_cell_name()),
+            _ => assert!(cell.get_landing_probability(MEDITERRANEAN_AVENUE) > 0.01, "{}", cell.get_cell_name())
+        }
+    }
+
+    //Every space that was never landed on shares the place below the last one that was
+    let one_turn_cell = &results.cells[0];
+    let never_landed = one_turn_cell.landed_counts.iter().filter(|landed_count| **landed_count == 0).count();
+    assert_eq!(one_turn_cell.get_ranks()[MEDITERRANEAN_AVENUE], 41 - never_landed);
+}
+
+
+#[test]
+fn sweep_rejects_an_empty_grid() {
+    let grid = SweepGrid { turn_counts: Vec::new(), ..small_grid() };
+
+    assert!(sweep::run_sweep(&grid, None, None).is_err());
+}